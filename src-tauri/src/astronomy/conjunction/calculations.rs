@@ -1,7 +1,7 @@
 //! Logika perhitungan konjungsi
 
 use super::estimation::estimate_conjunction_time;
-use super::refinement::{refine_conjunction_time, RefinementConfig};
+use super::refinement::{refine_conjunction_time, refine_full_moon_time, RefinementConfig};
 use super::types::Conjunction;
 use crate::GregorianDate;
 
@@ -79,6 +79,12 @@ pub fn find_conjunction_after(date: &GregorianDate) -> Conjunction {
     find_conjunction(&search_date)
 }
 
+/// Cari bulan purnama (istiqbal) setelah konjungsi `conjunction_jd`, dalam JD UT
+pub fn find_full_moon_after(conjunction_jd: f64) -> f64 {
+    // Estimasi: setengah bulan sinodis setelah konjungsi
+    refine_full_moon_time(conjunction_jd + 14.765, RefinementConfig::default())
+}
+
 /// Hitung informasi lengkap konjungsi dari JD
 fn compute_conjunction_info(jd: f64) -> Conjunction {
     let gregorian = crate::calendar::jd_to_gregorian(jd);
@@ -246,6 +252,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_full_moon_after_conjunction() {
+        // Konjungsi 11 Jan 2024 11:57 UT, purnama 25 Jan 2024 17:54 UT
+        let conj = find_conjunction_for_month(2024, 1);
+        let full_moon = find_full_moon_after(conj.jd_utc);
+        let expected = crate::calendar::gregorian_to_jd(&GregorianDate {
+            year: 2024,
+            month: 1,
+            day: 25.0 + (17.0 + 54.0 / 60.0) / 24.0,
+        });
+        assert!((full_moon - expected).abs() * 1440.0 < 5.0);
+    }
+
     #[test]
     fn test_conjunction_progression() {
         let conj1 = find_conjunction_for_month(2024, 1);
@@ -18,7 +18,7 @@ mod types;
 // Re-export public API
 pub use calculations::{
     find_conjunction, find_conjunction_after, find_conjunction_before, find_conjunction_for_month,
-    find_conjunction_with_config, find_topocentric_conjunction,
+    find_conjunction_with_config, find_full_moon_after, find_topocentric_conjunction,
 };
 pub use refinement::RefinementConfig;
 pub use types::Conjunction;
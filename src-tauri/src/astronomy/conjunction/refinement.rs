@@ -80,6 +80,35 @@ pub fn refine_conjunction_time(jd_initial: f64, config: RefinementConfig) -> f64
     jd
 }
 
+/// Refine waktu bulan purnama (istiqbal)
+///
+/// Sama seperti konjungsi, tetapi target selisih longitude bulan-matahari = 180°.
+pub fn refine_full_moon_time(jd_initial: f64, config: RefinementConfig) -> f64 {
+    let mut jd = jd_initial;
+
+    for _ in 0..config.max_iterations {
+        let offset = compute_opposition_offset(jd);
+        let delta = 1.0 / 24.0;
+        let derivative = (compute_opposition_offset(jd + delta) - offset) / delta;
+        if derivative.abs() < 1e-10 || !derivative.is_finite() {
+            break;
+        }
+
+        let correction = -offset / derivative;
+        jd += correction;
+        if correction.abs() < config.tolerance {
+            break;
+        }
+    }
+
+    jd
+}
+
+/// Selisih longitude bulan-matahari terhadap 180°, dinormalisasi ke [-180, 180]
+fn compute_opposition_offset(jd: f64) -> f64 {
+    compute_longitude_difference(jd).rem_euclid(360.0) - 180.0
+}
+
 /// Hitung selisih longitude dan derivative-nya
 fn compute_longitude_difference_and_derivative(jd: f64) -> (f64, f64) {
     let longitude_diff = compute_longitude_difference(jd);
@@ -49,6 +49,29 @@ pub fn true_obliquity(jd: f64) -> f64 {
     mean_obliquity_laskar(jd)
 }
 
+/// Tinggi benda langit (airless) dari hour angle dan deklinasi
+///
+/// Semua argumen dan hasil dalam derajat
+pub fn altitude_from_hour_angle(latitude: f64, hour_angle: f64, declination: f64) -> f64 {
+    let lat = latitude.to_radians();
+    let ha = hour_angle.to_radians();
+    let dec = declination.to_radians();
+
+    (lat.sin() * dec.sin() + lat.cos() * dec.cos() * ha.cos())
+        .asin()
+        .to_degrees()
+}
+
+/// Jarak sudut antara dua titik RA/Dec (derajat)
+pub fn angular_separation(ra1: f64, dec1: f64, ra2: f64, dec2: f64) -> f64 {
+    let dec1 = dec1.to_radians();
+    let dec2 = dec2.to_radians();
+    let dra = (ra1 - ra2).to_radians();
+
+    let cos_sep = dec1.sin() * dec2.sin() + dec1.cos() * dec2.cos() * dra.cos();
+    cos_sep.clamp(-1.0, 1.0).acos().to_degrees()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,214 @@
+//! Tabel ephemeris berlangkah waktu (time-stepped) antara dua instan
+//!
+//! Dipakai oleh command `get_ephemeris_data` dan `get_ephemeris_data_hijri`.
+//! Setiap baris berisi posisi geosentris matahari & bulan, fase, umur bulan
+//! dan konjungsi (ijtimak) terdekat.
+
+use super::conjunction::{find_conjunction, Conjunction as ConjunctionInfo};
+use crate::{AstronomicalData, Conjunction, JulianDay};
+
+/// Rata-rata panjang bulan sinodis (hari)
+const SYNODIC_MONTH_DAYS: f64 = 29.530588;
+
+/// Batas jumlah baris agar permintaan yang keliru tidak membekukan UI
+pub const MAX_EPHEMERIS_ROWS: usize = 10_000;
+
+/// Satuan langkah tabel ephemeris
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EphemerisStepUnit {
+    Minutes,
+    Hours,
+    Days,
+}
+
+/// Langkah waktu tabel ephemeris (misal: 30 minutes, 1 hours, 1 days)
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EphemerisStep {
+    pub value: f64,
+    pub unit: EphemerisStepUnit,
+}
+
+impl EphemerisStep {
+    pub fn minutes(value: f64) -> Self {
+        Self {
+            value,
+            unit: EphemerisStepUnit::Minutes,
+        }
+    }
+
+    pub fn hours(value: f64) -> Self {
+        Self {
+            value,
+            unit: EphemerisStepUnit::Hours,
+        }
+    }
+
+    pub fn days(value: f64) -> Self {
+        Self {
+            value,
+            unit: EphemerisStepUnit::Days,
+        }
+    }
+
+    /// Panjang langkah dalam hari
+    pub fn as_days(&self) -> f64 {
+        match self.unit {
+            EphemerisStepUnit::Minutes => self.value / 1440.0,
+            EphemerisStepUnit::Hours => self.value / 24.0,
+            EphemerisStepUnit::Days => self.value,
+        }
+    }
+}
+
+/// Hitung tabel ephemeris dari `start_jd` sampai `end_jd` (inklusif, UT)
+///
+/// Posisi diambil dari `astronomy::sun_position` / `moon_position`,
+/// umur bulan dihitung dari ijtimak terakhir sebelum tiap baris.
+pub fn generate_ephemeris_table(
+    start_jd: JulianDay,
+    end_jd: JulianDay,
+    step: EphemerisStep,
+) -> Result<Vec<AstronomicalData>, String> {
+    let step_days = step.as_days();
+    if !step_days.is_finite() || step_days <= 0.0 {
+        return Err("Step must be a positive number".to_string());
+    }
+    if end_jd < start_jd {
+        return Err("End date must not be before start date".to_string());
+    }
+
+    // Toleransi kecil agar baris terakhir tidak hilang karena pembulatan JD
+    let row_count = ((end_jd - start_jd) / step_days + 1e-6).floor() as usize + 1;
+    if row_count > MAX_EPHEMERIS_ROWS {
+        return Err(format!(
+            "Range too large: {} rows requested (max {})",
+            row_count, MAX_EPHEMERIS_ROWS
+        ));
+    }
+
+    let mut rows = Vec::with_capacity(row_count);
+    let mut bracket: Option<(ConjunctionInfo, ConjunctionInfo)> = None;
+
+    for i in 0..row_count {
+        let jd = start_jd + i as f64 * step_days;
+
+        // Ijtimak hanya dicari ulang ketika baris keluar dari rentang lunasi sebelumnya
+        let (previous, next) = match bracket.take() {
+            Some((p, n)) if p.jd_utc <= jd && jd < n.jd_utc => (p, n),
+            _ => conjunction_bracket(jd),
+        };
+
+        let nearest = if jd - previous.jd_utc <= next.jd_utc - jd {
+            &previous
+        } else {
+            &next
+        };
+
+        rows.push(AstronomicalData {
+            sun_position: super::sun_position(jd),
+            moon_position: super::moon_position(jd),
+            conjunction: Some(to_conjunction_data(nearest)),
+            moon_phase: super::phase(jd),
+            moon_age: (jd - previous.jd_utc) * 24.0,
+        });
+
+        bracket = Some((previous, next));
+    }
+
+    Ok(rows)
+}
+
+/// Cari ijtimak sebelum dan sesudah `jd` (previous <= jd < next)
+fn conjunction_bracket(jd: JulianDay) -> (ConjunctionInfo, ConjunctionInfo) {
+    // find_conjunction bisa konvergen ke ijtimak sebelum ATAU sesudah tanggal,
+    // tergantung fase bulan, jadi pasangan dilengkapi dari hasil pertamanya.
+    let found = find_conjunction(&crate::calendar::jd_to_gregorian(jd));
+
+    if found.jd_utc <= jd {
+        let next = conjunction_near(found.jd_utc + SYNODIC_MONTH_DAYS);
+        (found, next)
+    } else {
+        let previous = conjunction_near(found.jd_utc - SYNODIC_MONTH_DAYS);
+        (previous, found)
+    }
+}
+
+/// Ijtimak yang paling dekat dengan perkiraan `jd_estimate`
+fn conjunction_near(jd_estimate: JulianDay) -> ConjunctionInfo {
+    find_conjunction(&crate::calendar::jd_to_gregorian(jd_estimate))
+}
+
+/// Konversi hasil pencarian ijtimak ke struktur `Conjunction` milik API
+fn to_conjunction_data(conjunction: &ConjunctionInfo) -> Conjunction {
+    // Bulan Hijriah yang dibuka oleh ijtimak ini: ambil tanggal beberapa hari
+    // setelahnya agar tidak jatuh di akhir bulan sebelumnya (kalender tabular)
+    let hijri = crate::calendar::hijri::jd_to_hijri(conjunction.jd_utc + 3.0);
+
+    Conjunction {
+        jd: conjunction.jd_utc,
+        gregorian: crate::calendar::jd_to_gregorian(conjunction.jd_utc),
+        hijri_month: hijri.month,
+        hijri_year: hijri.year,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_conversion() {
+        assert!((EphemerisStep::minutes(30.0).as_days() - 30.0 / 1440.0).abs() < 1e-12);
+        assert!((EphemerisStep::hours(6.0).as_days() - 0.25).abs() < 1e-12);
+        assert!((EphemerisStep::days(2.0).as_days() - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_daily_table_feb_2026() {
+        // 10 - 25 Feb 2026, melewati ijtimak 17 Feb 2026 ~12:01 UTC
+        let start = crate::calendar::gregorian_to_jd(&crate::GregorianDate {
+            year: 2026,
+            month: 2,
+            day: 10.0,
+        });
+        let rows = generate_ephemeris_table(start, start + 15.0, EphemerisStep::days(1.0))
+            .expect("table should be generated");
+
+        assert_eq!(rows.len(), 16);
+
+        for (i, row) in rows.iter().enumerate() {
+            let jd = start + i as f64;
+            let conj = row.conjunction.as_ref().expect("nearest conjunction");
+            assert!(
+                (conj.jd - jd).abs() <= SYNODIC_MONTH_DAYS / 2.0 + 1.0,
+                "Conjunction {} is not the nearest one to {}",
+                conj.jd,
+                jd
+            );
+            assert!(row.moon_age >= 0.0 && row.moon_age < 24.0 * 30.0);
+            assert!(row.moon_phase >= 0.0 && row.moon_phase <= 1.0);
+        }
+
+        // Umur bulan harus di-reset setelah ijtimak
+        let reset = rows
+            .windows(2)
+            .position(|w| w[1].moon_age < w[0].moon_age)
+            .expect("moon age should reset at the conjunction");
+        let conj = rows[reset + 1].conjunction.as_ref().unwrap();
+        assert_eq!(conj.gregorian.year, 2026);
+        assert_eq!(conj.gregorian.month, 2);
+        assert_eq!(conj.gregorian.day.floor() as i32, 17);
+        assert_eq!(conj.hijri_month, 9); // Ramadhan 1447
+        assert_eq!(conj.hijri_year, 1447);
+    }
+
+    #[test]
+    fn test_invalid_ranges() {
+        assert!(generate_ephemeris_table(2461000.0, 2460999.0, EphemerisStep::days(1.0)).is_err());
+        assert!(generate_ephemeris_table(2461000.0, 2461001.0, EphemerisStep::hours(0.0)).is_err());
+        assert!(
+            generate_ephemeris_table(2461000.0, 2461100.0, EphemerisStep::minutes(1.0)).is_err()
+        );
+    }
+}
@@ -73,6 +73,45 @@ pub fn calculate_moonset(location: &GeoLocation, date: &GregorianDate) -> f64 {
     moonset_hour_local
 }
 
+/// Find the moonset (JD UT) closest to `around_jd` within ±12 hours
+///
+/// Scans in 10-minute steps, then bisects on the VB6 topocentric altitude
+/// (positive -> negative crossing). Returns None if the moon does not set
+/// within the window (high latitudes).
+pub fn find_moonset_jd(location: &GeoLocation, around_jd: f64) -> Option<f64> {
+    let step = 10.0 / 1440.0;
+    let altitude = |jd: f64| super::topocentric::moon_altitude_topocentric(location, jd);
+
+    let mut best: Option<f64> = None;
+    let mut jd = around_jd - 0.5;
+    let mut prev_alt = altitude(jd);
+    while jd < around_jd + 0.5 {
+        let next_jd = jd + step;
+        let next_alt = altitude(next_jd);
+
+        if prev_alt > 0.0 && next_alt <= 0.0 {
+            let (mut lo, mut hi) = (jd, next_jd);
+            for _ in 0..20 {
+                let mid = (lo + hi) / 2.0;
+                if altitude(mid) > 0.0 {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            let moonset = (lo + hi) / 2.0;
+            if best.is_none_or(|b| (moonset - around_jd).abs() < (b - around_jd).abs()) {
+                best = Some(moonset);
+            }
+        }
+
+        jd = next_jd;
+        prev_alt = next_alt;
+    }
+
+    best
+}
+
 /// Calculate lag time (difference between sunset and moonset)
 /// Returns formatted string like "-00h 03m 42s" or "+00h 15m 30s"
 pub fn calculate_lag_time(sunset_hour: f64, moonset_hour: f64) -> String {
@@ -1,7 +1,32 @@
 //! Modul untuk perhitungan parameter hilal (crescent moon visibility)
 //! Port dari KumpulanFungsiAtSunset.bas di VB6
+//!
+//! Setiap besaran tersedia dalam dua bentuk: `*_at_jd` (pada JD UT tertentu) dan
+//! `*_at_sunset` (menghitung maghrib lalu memanggil versi `*_at_jd`).
 
-use crate::{GeoLocation, GregorianDate};
+use super::rise_set::RiseSetOptions;
+use crate::{GeoLocation, GregorianDate, JulianDay};
+
+/// Hitung JD (UT) saat maghrib pada tanggal lokal `date`
+///
+/// Bagian pecahan hari pada `date` dibuang agar waktu maghrib tidak terhitung dua kali.
+/// Fungsi `*_at_sunset` di modul ini memakai `RiseSetOptions` default.
+pub fn sunset_jd(
+    location: &GeoLocation,
+    date: &GregorianDate,
+    options: &RiseSetOptions,
+) -> JulianDay {
+    let date_only = crate::GregorianDate {
+        year: date.year,
+        month: date.month,
+        day: date.day.floor(),
+    };
+    let jd = crate::calendar::gregorian_to_jd(&date_only);
+
+    // Convert sunset from local time to UT before adding to JD
+    let sunset_hour = super::rise_set::sunset(location, &date_only, options);
+    jd + (sunset_hour - location.timezone) / 24.0
+}
 
 /// Hitung umur bulan pada saat maghrib (sunset)
 ///
@@ -14,17 +39,11 @@ use crate::{GeoLocation, GregorianDate};
 /// # Returns
 /// Umur bulan dalam jam sejak ijtimak (new moon) - TOPOCENTRIC
 pub fn moon_age_at_sunset(location: &GeoLocation, date: &GregorianDate) -> f64 {
-    let jd = crate::calendar::gregorian_to_jd(date);
-
-    // Hitung waktu maghrib
-    let sunset_hour = crate::astronomy::calculate_sunset(location, date);
-
-    // Konversi ke Julian Day pada waktu maghrib (convert local to UT first)
-    let sunset_hour_ut = sunset_hour - location.timezone;
-    let sunset_jd = jd + (sunset_hour_ut / 24.0);
-
     // Use TOPOCENTRIC calculation (matches VB6)
-    super::topocentric::moon_age_topocentric(location, sunset_jd)
+    super::topocentric::moon_age_topocentric(
+        location,
+        sunset_jd(location, date, &RiseSetOptions::default()),
+    )
 }
 
 /// Hitung elongasi (jarak sudut bulan-matahari) pada saat maghrib
@@ -44,86 +63,30 @@ pub fn elongation_at_sunset(
     date: &GregorianDate,
     topocentric: bool,
 ) -> f64 {
-    let jd = crate::calendar::gregorian_to_jd(date);
-
-    // Konversi ke Julian Day pada waktu maghrib
-    let sunset_hour = crate::astronomy::calculate_sunset(location, date);
-    let sunset_hour_ut = sunset_hour - location.timezone;
-    let sunset_jd = jd + (sunset_hour_ut / 24.0);
+    elongation_at_jd(
+        location,
+        sunset_jd(location, date, &RiseSetOptions::default()),
+        topocentric,
+    )
+}
 
+/// Hitung elongasi pada JD (UT) tertentu
+pub fn elongation_at_jd(location: &GeoLocation, jd: JulianDay, topocentric: bool) -> f64 {
     // Use topocentric if requested (VB6 default)
     if topocentric {
-        return super::topocentric::elongation_topocentric(location, sunset_jd);
-    }
-
-    // Hitung posisi bulan (equatorial coordinates)
-    let moon_pos = crate::astronomy::moon_position(sunset_jd);
-    let obliquity = astro::ecliptic::mn_oblq_laskar(astro::time::julian_cent(sunset_jd));
-
-    let lambda_moon = moon_pos.longitude.to_radians();
-    let beta_moon = moon_pos.latitude.to_radians();
-
-    // Convert moon ecliptic to equatorial
-    let mut moon_ra = (lambda_moon.sin() * obliquity.cos() - beta_moon.tan() * obliquity.sin())
-        .atan2(lambda_moon.cos())
-        .to_degrees();
-    if moon_ra < 0.0 {
-        moon_ra += 360.0;
-    }
-
-    let moon_dec = (beta_moon.sin() * obliquity.cos()
-        + beta_moon.cos() * obliquity.sin() * lambda_moon.sin())
-    .asin()
-    .to_degrees();
-
-    // Hitung posisi matahari (equatorial coordinates)
-    let sun_pos = crate::astronomy::sun_position(sunset_jd);
-    let lambda_sun = sun_pos.longitude.to_radians();
-    let beta_sun = sun_pos.latitude.to_radians();
-
-    // Convert sun ecliptic to equatorial
-    let mut sun_ra = (lambda_sun.sin() * obliquity.cos() - beta_sun.tan() * obliquity.sin())
-        .atan2(lambda_sun.cos())
-        .to_degrees();
-    if sun_ra < 0.0 {
-        sun_ra += 360.0;
+        return super::topocentric::elongation_topocentric(location, jd);
     }
 
-    let sun_dec = (beta_sun.sin() * obliquity.cos()
-        + beta_sun.cos() * obliquity.sin() * lambda_sun.sin())
-    .asin()
-    .to_degrees();
-
     // Angular separation formula (VB6: AngularSeparation)
     // cos(d) = sin(dec1)*sin(dec2) + cos(dec1)*cos(dec2)*cos(ra1-ra2)
-    let dec1_rad = moon_dec.to_radians();
-    let dec2_rad = sun_dec.to_radians();
-    let ra_diff_rad = (moon_ra - sun_ra).to_radians();
-
-    let cos_elongation =
-        dec1_rad.sin() * dec2_rad.sin() + dec1_rad.cos() * dec2_rad.cos() * ra_diff_rad.cos();
-
-    // Clamp to avoid numerical errors in acos
-    let cos_elongation = cos_elongation.clamp(-1.0, 1.0);
-    let mut elongation = cos_elongation.acos().to_degrees();
-
-    // Koreksi toposentrik jika diminta
-    // Untuk topocentric, perlu apply parallax correction ke bulan
-    if topocentric {
-        let horizontal_parallax = horizontal_moon_parallax(sunset_jd);
-
-        // Koreksi sederhana untuk elongasi toposentris
-        // Parallax akan mengurangi elongasi sedikit
-        // VB6: menggunakan parallax correction pada altitude
-        let altitude = altitude_at_sunset(location, date, false);
-        let parallax_correction =
-            (horizontal_parallax.to_degrees() * altitude.to_radians().cos()).abs();
-
-        // Elongasi toposentris biasanya sedikit lebih kecil dari geosentris
-        elongation = (elongation - parallax_correction * 0.5).max(0.0);
-    }
-
-    elongation
+    let moon_pos = crate::astronomy::moon_position(jd);
+    let sun_pos = crate::astronomy::sun_position(jd);
+    super::coordinates::angular_separation(
+        moon_pos.right_ascension,
+        moon_pos.declination,
+        sun_pos.right_ascension,
+        sun_pos.declination,
+    )
 }
 
 /// Hitung tinggi bulan (altitude) pada saat maghrib
@@ -136,108 +99,53 @@ pub fn elongation_at_sunset(
 /// # Returns
 /// Tinggi bulan dalam derajat (negatif jika di bawah horizon)
 pub fn altitude_at_sunset(location: &GeoLocation, date: &GregorianDate, topocentric: bool) -> f64 {
-    // Hitung waktu maghrib
-    let sunset_hour = crate::astronomy::calculate_sunset(location, date);
-
-    // Modifikasi: Pastikan JD dasar hanya mewakili tanggal (dengan membuang bagian pecahan hari)
-    // untuk menghindari double-counting waktu sunset jika GregorianDate mengandung waktu.
-    let date_only = crate::GregorianDate {
-        year: date.year,
-        month: date.month,
-        day: date.day.floor(),
-    };
-    let jd = crate::calendar::gregorian_to_jd(&date_only);
-
-    // Convert sunset from local time to UT before adding to JD
-    let sunset_hour_ut = sunset_hour - location.timezone;
-    let sunset_jd = jd + (sunset_hour_ut / 24.0);
+    altitude_at_jd(
+        location,
+        sunset_jd(location, date, &RiseSetOptions::default()),
+        topocentric,
+    )
+}
 
+/// Hitung tinggi bulan pada JD (UT) tertentu
+///
+/// Toposentris: tinggi tampak VB6 (refraksi + parallax).
+/// Geosentris: tinggi dari LST dengan koreksi refraksi (tanpa parallax).
+pub fn altitude_at_jd(location: &GeoLocation, jd: JulianDay, topocentric: bool) -> f64 {
     // Use TOPOCENTRIC altitude if requested (VB6 default)
     if topocentric {
-        return super::topocentric::moon_altitude_topocentric(location, sunset_jd);
-    }
-
-    // Hitung posisi bulan
-    let moon_pos = crate::astronomy::moon_position(sunset_jd);
-
-    // Konversi dari ekliptika ke equatorial
-    let obliquity = astro::ecliptic::mn_oblq_laskar(astro::time::julian_cent(sunset_jd));
-
-    let lambda = moon_pos.longitude.to_radians();
-    let beta = moon_pos.latitude.to_radians();
-
-    let mut ra = (lambda.sin() * obliquity.cos() - beta.tan() * obliquity.sin())
-        .atan2(lambda.cos())
-        .to_degrees();
-
-    // Normalisasi RA ke range 0-360
-    if ra < 0.0 {
-        ra += 360.0;
+        return super::topocentric::moon_altitude_topocentric(location, jd);
     }
 
-    let dec = (beta.sin() * obliquity.cos() + beta.cos() * obliquity.sin() * lambda.sin())
-        .asin()
-        .to_degrees();
+    // Hitung posisi bulan (equatorial)
+    let moon_pos = crate::astronomy::moon_position(jd);
 
     // Hitung Local Sidereal Time dalam JAM (bukan derajat)
     // VB6-compatible calculation
-    let lst_hours = super::topocentric::local_sidereal_time_hours(location.longitude, sunset_jd);
-
-    // Konversi RA dari derajat ke jam (15° = 1 hour)
-    let ra_hours = ra / 15.0;
+    let lst_hours = super::topocentric::local_sidereal_time_hours(location.longitude, jd);
 
     // Hour angle dalam jam menggunakan formula VB6: ha_hours = LST - RA/15
-    let mut ha_hours = lst_hours - ra_hours;
-
-    // Modulus ke 0-24 jam dengan handling yang benar
-    ha_hours = ha_hours.rem_euclid(24.0);
-
-    // Konversi hour angle ke radian (VB6: h = deg2rad(15 * ha_hours))
-    let ha_rad = (ha_hours * 15.0).to_radians();
+    let ha_hours = (lst_hours - moon_pos.right_ascension / 15.0).rem_euclid(24.0);
 
-    // Hitung altitude menggunakan formula VB6:
     // Alt = Asin(cos(h) * cos(D) * cos(La) + sin(D) * sin(La))
-    let lat_rad = location.latitude.to_radians();
-    let dec_rad = dec.to_radians();
-
-    // Formula altitude dari spherical astronomy
-    let mut altitude = (ha_rad.cos() * dec_rad.cos() * lat_rad.cos()
-        + dec_rad.sin() * lat_rad.sin())
-    .asin()
-    .to_degrees();
+    let mut altitude = super::coordinates::altitude_from_hour_angle(
+        location.latitude,
+        ha_hours * 15.0,
+        moon_pos.declination,
+    );
 
     // Koreksi atmosfer (refraction) - Airy model
     // VB6: menggunakan refraksi standar untuk altitude rendah
     // Pada horizon (altitude = 0°), refraction ≈ 34' ≈ 0.5667°
-    // Untuk altitude negatif, gunakan approximasi
-    if altitude < -1.0 {
-        // Di bawah horizon, tidak ada refraksi atmosfer yang signifikan
-        altitude += 0.0;
-    } else if altitude < 0.0 {
+    if (-1.0..0.0).contains(&altitude) {
         // Near horizon, interpolasi linear
         altitude += 0.5667 * (1.0 + altitude);
-    } else if altitude < 15.0 {
+    } else if (0.0..15.0).contains(&altitude) {
         // Formula Bennett (simplified)
         // R = 1.02 / tan(h + 10.3/(h + 5.11)) arcminutes
-        let h_deg = altitude;
-        let refraction = 1.02 / (h_deg + 10.3 / (h_deg + 5.11)).to_radians().tan();
+        let refraction = 1.02 / (altitude + 10.3 / (altitude + 5.11)).to_radians().tan();
         altitude += refraction / 60.0; // Convert arcminutes to degrees
     }
 
-    // Koreksi toposentrik jika diminta
-    // VB6: parallax_correction = Asin(Sin(horizontal_parallax) * Cos(altitude))
-    if topocentric {
-        let horizontal_parallax_rad = horizontal_moon_parallax(sunset_jd);
-
-        // Formula VB6 untuk parallax correction dalam altitude
-        // parallax_in_altitude = Asin(Sin(hp) * Cos(alt))
-        let parallax_correction = (horizontal_parallax_rad.sin() * altitude.to_radians().cos())
-            .asin()
-            .to_degrees();
-
-        altitude -= parallax_correction;
-    }
-
     altitude
 }
 
@@ -257,23 +165,23 @@ pub fn crescent_width_at_sunset(
     date: &GregorianDate,
     topocentric: bool,
 ) -> f64 {
-    let jd = crate::calendar::gregorian_to_jd(date);
-
-    // Konversi ke Julian Day pada waktu maghrib (convert local to UT first)
-    let sunset_hour = crate::astronomy::calculate_sunset(location, date);
-    let sunset_hour_ut = sunset_hour - location.timezone;
-    let sunset_jd = jd + (sunset_hour_ut / 24.0);
-
-    // Hitung posisi bulan dan matahari
-    let _moon_pos = crate::astronomy::moon_position(sunset_jd);
-    let _sun_pos = crate::astronomy::sun_position(sunset_jd);
+    crescent_width_at_jd(
+        location,
+        sunset_jd(location, date, &RiseSetOptions::default()),
+        topocentric,
+    )
+}
 
-    // Hitung jarak sudut bulan-matahari (elongasi) menggunakan angular separation
-    let elong = elongation_at_sunset(location, date, topocentric);
-    let elong_rad = elong.to_radians();
+/// Hitung lebar hilal (arc-minutes) pada JD (UT) tertentu
+pub fn crescent_width_at_jd(location: &GeoLocation, jd: JulianDay, topocentric: bool) -> f64 {
+    let elongation = elongation_at_jd(location, jd, topocentric);
+    crescent_width_from(elongation, illumination_at_jd(jd) / 100.0)
+}
 
-    // Hitung illumination fraction (fase bulan)
-    let illum = illumination_at_sunset(location, date, topocentric) / 100.0;
+/// Lebar hilal (arc-minutes) dari elongasi (derajat) dan fraksi iluminasi (0-1)
+pub fn crescent_width_from(elongation: f64, illumination_fraction: f64) -> f64 {
+    let elong_rad = elongation.to_radians();
+    let illum = illumination_fraction;
 
     // Hitung apparent semi-diameter bulan
     // Semi-diameter bulan ≈ 0.2725 derajat (sudut diameter piringan bulan dari Bumi)
@@ -304,19 +212,17 @@ pub fn illumination_at_sunset(
     date: &GregorianDate,
     _topocentric: bool,
 ) -> f64 {
-    let jd = crate::calendar::gregorian_to_jd(date);
-
-    // Konversi ke Julian Day pada waktu maghrib (convert local to UT first)
-    let sunset_hour = crate::astronomy::calculate_sunset(location, date);
-    let sunset_hour_ut = sunset_hour - location.timezone;
-    let sunset_jd = jd + (sunset_hour_ut / 24.0);
+    illumination_at_jd(sunset_jd(location, date, &RiseSetOptions::default()))
+}
 
+/// Hitung pencahayaan hilal (persen) pada JD (UT) tertentu
+pub fn illumination_at_jd(jd: JulianDay) -> f64 {
     // Hitung fraction piringan yang diterangi (0.0 - 1.0)
     // moon::phase(jd) mengembalikan (1.0 - cos(phase_angle)) / 2.0
-    let illumination_fraction = crate::astronomy::phase(sunset_jd);
+    let illumination_fraction = crate::astronomy::phase(jd);
 
     // Konversi ke persen dan kembalikan
-    (illumination_fraction * 100.0).max(0.0).min(100.0)
+    (illumination_fraction * 100.0).clamp(0.0, 100.0)
 }
 
 /// Hitung horizontal parallax bulan (dalam radian)
@@ -3,6 +3,7 @@
 pub mod arcv;
 pub mod conjunction;
 pub mod coordinates;
+pub mod ephemeris;
 pub mod ephemeris_utils;
 pub mod hilal;
 pub mod lunar_position;
@@ -11,6 +12,8 @@ pub mod moon_phases;
 pub mod nutation;
 pub mod parallax;
 pub mod prayer;
+pub mod prayer_format;
+pub mod rise_set;
 pub mod sun;
 pub mod sun_meeus;
 pub mod sun_vb6;
@@ -28,8 +31,9 @@ pub use sun::geocentric_position as sun_position;
 
 // Re-export hilal functions
 pub use hilal::{
-    altitude_at_sunset, crescent_width_at_sunset, elongation_at_sunset, horizontal_moon_parallax,
-    illumination_at_sunset, moon_age_at_sunset,
+    altitude_at_jd, altitude_at_sunset, crescent_width_at_jd, crescent_width_at_sunset,
+    elongation_at_jd, elongation_at_sunset, horizontal_moon_parallax, illumination_at_jd,
+    illumination_at_sunset, moon_age_at_sunset, sunset_jd,
 };
 
 // Re-export parallax functions
@@ -41,5 +45,5 @@ pub use arcv::{calculate_arcv, calculate_arcv_yallop};
 // Re-export conjunction functions
 pub use conjunction::{
     find_conjunction, find_conjunction_after, find_conjunction_before, find_conjunction_for_month,
-    find_conjunction_with_config, Conjunction, RefinementConfig,
+    find_conjunction_with_config, find_full_moon_after, Conjunction, RefinementConfig,
 };
@@ -1,55 +1,259 @@
 //! Module untuk perhitungan waktu shalat (Prayer Times)
 //! Porting dari PrayingTimes.bas (VB6)
 
+use super::rise_set::RiseSetOptions;
 use super::sun;
 use crate::{GeoLocation, GregorianDate};
 use std::collections::HashMap;
 
 /// Konfigurasi sudut dan parameter waktu shalat
-#[derive(Debug, Clone)]
+///
+/// Field yang tidak diisi saat deserialisasi memakai nilai default (Kemenag RI).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct PrayerConfig {
     pub shubuh_angle: f64,      // Sudut dip matahari untuk Shubuh (biasanya 20°)
     pub ashr_shadow_ratio: f64, // Rasio bayangan untuk Ashr (1.0 = Syafi'i, 2.0 = Hanafi)
     pub isya_angle: f64,        // Sudut dip matahari untuk Isya (biasanya 18°)
+    pub isya_interval: Option<f64>, // Isya = Maghrib + menit ini (mengabaikan isya_angle)
+    pub maghrib_angle: Option<f64>, // Maghrib pada sudut dip ini, bukan saat terbenam
     pub imsak_margin: f64,      // Margin waktu Imsak sebelum Shubuh (biasanya 10 menit)
     pub dhuha_angle: f64,       // Tinggi matahari untuk Dhuha (biasanya 4.5°)
+    pub isfirar_angle: f64,     // Tinggi matahari saat mulai menguning sore hari (biasanya 4.5°)
+    pub zawal_minutes: f64,     // Lama waktu karahah menjelang istiwa (menit)
+    pub high_latitude: HighLatitudeRule, // Aturan bila Shubuh/Isya tidak terdefinisi
+    pub horizon: RiseSetOptions, // Tekanan, suhu dan jenis ufuk untuk terbit/terbenam
     pub ihtiyat: HashMap<String, f64>, // Koreksi keamanan untuk setiap waktu
 }
 
 impl Default for PrayerConfig {
     fn default() -> Self {
-        let mut ihtiyat = HashMap::new();
-        ihtiyat.insert("shubuh".to_string(), 2.0);
-        ihtiyat.insert("dhuha".to_string(), 2.0);
-        ihtiyat.insert("dzuhur".to_string(), 2.0); // +4m di VB6? (Ihtiyat(1))
-        ihtiyat.insert("ashr".to_string(), 2.0);
-        ihtiyat.insert("maghrib".to_string(), 2.0);
-        ihtiyat.insert("isya".to_string(), 2.0);
+        PrayerMethod::Kemenag.config()
+    }
+}
+
+/// Aturan lintang tinggi untuk Shubuh dan Isya
+///
+/// Di lintang tinggi saat musim panas matahari tidak turun sampai sudut Shubuh/Isya,
+/// atau turun terlalu lambat sehingga Isya hampir bertemu Shubuh. Aturan porsi malam
+/// (`MiddleOfNight`, `OneSeventh`, `AngleBased`) membatasi jarak Isya dari Maghrib dan
+/// Shubuh dari Terbit pada sebagian malam; dua aturan lain hanya dipakai bila waktunya
+/// tidak terdefinisi.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum HighLatitudeRule {
+    /// Tanpa penyesuaian (waktu tidak terdefinisi ditulis "Invalid")
+    None,
+    /// Paling lambat tengah malam (1/2 malam)
+    MiddleOfNight,
+    /// Paling lambat 1/7 malam
+    OneSeventh,
+    /// Paling lambat sudut/60 bagian malam (mis. 18° = 3/10 malam)
+    #[default]
+    AngleBased,
+    /// Hitung pada lintang acuan (biasanya 45° atau 48°) dengan bujur yang sama
+    NearestLatitude { latitude: f64 },
+    /// Pakai waktu hari terdekat yang matahari masih mencapai sudutnya
+    NearestDay,
+}
+
+impl HighLatitudeRule {
+    /// Nama singkat aturan
+    pub fn name(&self) -> &'static str {
+        match self {
+            HighLatitudeRule::None => "none",
+            HighLatitudeRule::MiddleOfNight => "middle_of_night",
+            HighLatitudeRule::OneSeventh => "one_seventh",
+            HighLatitudeRule::AngleBased => "angle_based",
+            HighLatitudeRule::NearestLatitude { .. } => "nearest_latitude",
+            HighLatitudeRule::NearestDay => "nearest_day",
+        }
+    }
+}
+
+/// Waktu yang disesuaikan dengan aturan lintang tinggi
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HighLatitudeAdjustment {
+    pub prayer: String, // "shubuh" atau "isya"
+    pub rule: HighLatitudeRule,
+}
+
+/// Metode perhitungan waktu shalat dari lembaga resmi
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrayerMethod {
+    Kemenag,   // Kementerian Agama RI
+    Jakim,     // Jabatan Kemajuan Islam Malaysia
+    Muis,      // Majlis Ugama Islam Singapura
+    Mwl,       // Muslim World League
+    Isna,      // Islamic Society of North America
+    Egyptian,  // Egyptian General Authority of Survey
+    UmmAlQura, // Umm al-Qura University, Makkah
+    Karachi,   // University of Islamic Sciences, Karachi
+    Tehran,    // Institute of Geophysics, University of Tehran
+    Diyanet,   // Diyanet İşleri Başkanlığı, Turki
+}
+
+impl PrayerMethod {
+    pub const ALL: [PrayerMethod; 10] = [
+        PrayerMethod::Kemenag,
+        PrayerMethod::Jakim,
+        PrayerMethod::Muis,
+        PrayerMethod::Mwl,
+        PrayerMethod::Isna,
+        PrayerMethod::Egyptian,
+        PrayerMethod::UmmAlQura,
+        PrayerMethod::Karachi,
+        PrayerMethod::Tehran,
+        PrayerMethod::Diyanet,
+    ];
+
+    /// Nama lengkap metode
+    pub fn name(&self) -> &'static str {
+        match self {
+            PrayerMethod::Kemenag => "Kementerian Agama RI",
+            PrayerMethod::Jakim => "JAKIM (Malaysia)",
+            PrayerMethod::Muis => "MUIS (Singapura)",
+            PrayerMethod::Mwl => "Muslim World League",
+            PrayerMethod::Isna => "Islamic Society of North America",
+            PrayerMethod::Egyptian => "Egyptian General Authority of Survey",
+            PrayerMethod::UmmAlQura => "Umm al-Qura, Makkah",
+            PrayerMethod::Karachi => "University of Islamic Sciences, Karachi",
+            PrayerMethod::Tehran => "Institute of Geophysics, University of Tehran",
+            PrayerMethod::Diyanet => "Diyanet İşleri Başkanlığı (Turki)",
+        }
+    }
+
+    /// Konfigurasi lengkap metode
+    ///
+    /// Metode Asia Tenggara memakai ihtiyat 2 menit; metode lain tanpa ihtiyat kecuali
+    /// Diyanet yang memakai temkin (Terbit -7, Dzuhur +5, Ashr +4, Maghrib +7 menit).
+    pub fn config(&self) -> PrayerConfig {
+        let (shubuh_angle, isya_angle) = match self {
+            PrayerMethod::Kemenag | PrayerMethod::Jakim | PrayerMethod::Muis => (20.0, 18.0),
+            PrayerMethod::Mwl => (18.0, 17.0),
+            PrayerMethod::Isna => (15.0, 15.0),
+            PrayerMethod::Egyptian => (19.5, 17.5),
+            PrayerMethod::UmmAlQura => (18.5, 0.0),
+            PrayerMethod::Karachi => (18.0, 18.0),
+            PrayerMethod::Tehran => (17.7, 14.0),
+            PrayerMethod::Diyanet => (18.0, 17.0),
+        };
+        let ihtiyat: &[(&str, f64)] = match self {
+            PrayerMethod::Kemenag | PrayerMethod::Jakim | PrayerMethod::Muis => &[
+                ("shubuh", 2.0),
+                ("dhuha", 2.0),
+                ("dzuhur", 2.0), // +4m di VB6? (Ihtiyat(1))
+                ("ashr", 2.0),
+                ("maghrib", 2.0),
+                ("isya", 2.0),
+            ],
+            PrayerMethod::Diyanet => &[
+                ("syuruq", 7.0),
+                ("dzuhur", 5.0),
+                ("ashr", 4.0),
+                ("maghrib", 7.0),
+            ],
+            _ => &[],
+        };
 
         PrayerConfig {
-            shubuh_angle: 20.0,
+            shubuh_angle,
             ashr_shadow_ratio: 1.0,
-            isya_angle: 18.0,
+            isya_angle,
+            isya_interval: (*self == PrayerMethod::UmmAlQura).then_some(90.0),
+            maghrib_angle: (*self == PrayerMethod::Tehran).then_some(4.5),
             imsak_margin: 10.0,
             dhuha_angle: 4.5,
-            ihtiyat,
+            isfirar_angle: 4.5,
+            zawal_minutes: 5.0,
+            high_latitude: HighLatitudeRule::default(),
+            horizon: RiseSetOptions::default(),
+            ihtiyat: ihtiyat
+                .iter()
+                .map(|(name, minutes)| (name.to_string(), *minutes))
+                .collect(),
+        }
+    }
+}
+
+/// Pilihan perhitungan dari frontend: nama metode (`"mwl"`) atau konfigurasi lengkap
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum PrayerSettings {
+    Method(PrayerMethod),
+    Config(PrayerConfig),
+}
+
+impl PrayerSettings {
+    /// Konfigurasi efektif
+    pub fn config(&self) -> PrayerConfig {
+        match self {
+            PrayerSettings::Method(method) => method.config(),
+            PrayerSettings::Config(config) => config.clone(),
         }
     }
 }
 
+/// Satu waktu shalat
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PrayerEvent {
+    pub jd_utc: f64,
+    pub local: String, // Waktu lokal RFC 3339 dengan offset, mis. "2026-02-18T04:31:07+07:00"
+    pub hours: f64,    // Jam lokal desimal (0-24)
+    pub altitude: f64, // Tinggi geometrik matahari (derajat, tanpa refraksi)
+    pub azimuth: f64,  // Azimuth matahari dari Utara searah jarum jam (derajat)
+}
+
+/// Jenis rentang waktu fiqh
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrayerIntervalKind {
+    /// Karahah: terbit sampai matahari setinggi tombak (`dhuha_angle`)
+    KarahahSyuruq,
+    /// Karahah: menjelang istiwa sampai matahari tergelincir
+    Zawal,
+    /// Waktu ikhtiyari Ashr: awal Ashr sampai bayangan 2x benda (Syafi'i), atau sampai
+    /// matahari menguning bila awal Ashr sudah memakai bayangan 2x (Hanafi)
+    AshrIkhtiyari,
+    /// Karahah: matahari menguning (`isfirar_angle`) sampai terbenam
+    KarahahGhurub,
+}
+
+/// Rentang waktu fiqh beserta tinggi matahari yang mendefinisikan batasnya
+///
+/// Batas dihitung tanpa ihtiyat.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PrayerInterval {
+    pub kind: PrayerIntervalKind,
+    pub start: PrayerEvent,
+    pub end: PrayerEvent,
+    pub start_altitude: f64, // Tinggi matahari (derajat, termasuk koreksi terbit/terbenam)
+    pub end_altitude: f64,
+}
+
 /// Hasil perhitungan waktu shalat
+///
+/// Waktu yang tidak terdefinisi (matahari tidak mencapai ketinggiannya) bernilai `None`.
+/// Untuk teks "HH:MM" gunakan [`super::prayer_format::format_prayer_times`].
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PrayerTimes {
-    pub imsak: String,
-    pub shubuh: String,
-    pub terbit: String, // Syuruq
-    pub dhuha: String,
-    pub dzuhur: String,
-    pub ashr: String,
-    pub maghrib: String,
-    pub isya: String,
-    pub tengah_malam: String, // Midnight
-    pub p3_malam: String,     // 1/3 Last Night
+    pub timezone: f64,
+    pub imsak: Option<PrayerEvent>,
+    pub shubuh: Option<PrayerEvent>,
+    pub terbit: Option<PrayerEvent>, // Syuruq
+    pub dhuha: Option<PrayerEvent>,
+    pub dzuhur: Option<PrayerEvent>,
+    pub ashr: Option<PrayerEvent>,
+    pub maghrib: Option<PrayerEvent>,
+    pub isya: Option<PrayerEvent>,
+    pub tengah_malam: Option<PrayerEvent>, // Midnight
+    pub p3_malam: Option<PrayerEvent>,     // 1/3 Last Night
+    pub istiwa: Option<PrayerEvent>,       // Kulminasi matahari (tanpa ihtiyat)
+    #[serde(default)]
+    pub intervals: Vec<PrayerInterval>, // Zawal, karahah dan ikhtiyari Ashr
+    #[serde(default)]
+    pub high_latitude: Vec<HighLatitudeAdjustment>, // Waktu yang memakai aturan lintang tinggi
 }
 
 /// Hitung semua waktu shalat
@@ -63,168 +267,667 @@ pub fn calculate_prayer_times(
     let jd = crate::calendar::gregorian_to_jd(date);
     let jd_noon = jd + 0.5;
 
-    // 2. Hitung parameter matahari pada siang hari
-    let sun_pos = sun::geocentric_position(jd_noon);
-    let declination = sun::declination(jd_noon);
-    let eq_of_time = sun::equation_of_time(jd_noon);
-
-    // Calculate Sun Semi-Diameter (used in VB6)
-    // SD = Sun.SD0 / 60
-    // Standard approx: 16 arcminutes = 0.2666 degrees
-    // VB6 might use exact calculation: 959.63 / Distance(AU) arcseconds
-    let sun_sd_deg = (959.63 / sun_pos.distance) / 3600.0;
-
-    // kwd = Tz - Lon/15
-    let kwd = location.timezone - (location.longitude / 15.0);
-
-    // --- DZUHUR & ISTIWA ---
-    // JS0.Duhr = 12# - Sun.ET + cKWD
-    let mut dzuhur_val = 12.0 - eq_of_time + kwd;
-
-    // Recalculate accurately? VB6 does:
-    // dL = Int(d) + JS0.Duhr / 24#
-    // Call SunPosition(Zon, L, B, Tz, y, m, dL)
-    // JS0.Duhr = 12# - Zon.ET + cKWD + 4# * SD / 60#
-    // Note: VB6 adds 4 * SD/60 (approx 1 min) to Dzuhur? Or is that safety limit?
-    // "JS0.Duhr = 12# - Zon.ET + cKWD + 4# * SD / 60#" -> This adds ~1 min shift?
-    // Commonly Dzuhur is just Transit + margin usually.
-    // Let's stick to standard first: Transit = 12 - EoT + KwD.
-    // VB6 adds "4# * SD / 60#" which is 4 minutes * SD_degrees? No.
-    // SD is in deg? If SD ~ 0.25 deg. 4*0.25 = 1 deg?
-    // Wait, SD in VB6 code: "SD = Sun.SD0 / 60". If SD0 is arcmin, SD is deg?
-    // If SD0 is deg, SD is likely deg.
-    // Actually, "4# * SD" looks like a correction.
-    // Let's implement standard transit first.
-    let transit = 12.0 - eq_of_time + kwd;
+    // 2. Istiwa: transit matahari, diiterasi dengan posisi matahari pada waktu itu
+    // (setiap waktu shalat diiterasi ulang dengan cara yang sama, lihat `solve_solar_time`)
+    let transit = solve_solar_time(location, jd_noon, |_| Some(0.0));
+    // Deklinasi saat istiwa untuk tinggi kulminasi dan batas rentang fiqh
+    let declination = sun::declination(jd_noon + (transit - 12.0 - location.timezone) / 24.0);
 
     // Apply Ihtiyat Dzuhur (usually +2-4 mins)
     let dzuhur = transit + (config.ihtiyat.get("dzuhur").unwrap_or(&0.0) / 60.0);
 
     // --- ASHR ---
     // cot(h_ashr) = shadow_ratio + tan(|lat - dec|)
-    let lat_rad = location.latitude.to_radians();
-    let dec_rad = declination.to_radians();
-    let zen_angle = (lat_rad - dec_rad).abs();
-    let cot_h = config.ashr_shadow_ratio + zen_angle.tan();
-    let h_ashr_rad = (1.0 / cot_h).atan();
-    let h_ashr = h_ashr_rad.to_degrees(); // Altitude of sun at Ashr
+    let h_ashr = ashr_altitude(location.latitude, declination, config.ashr_shadow_ratio);
 
-    let ashr_time = calculate_time_for_altitude(location, declination, eq_of_time, h_ashr, 1.0); // 1 = afternoon
+    let ashr_time = calculate_time_for_shadow(location, jd_noon, config.ashr_shadow_ratio);
     let ashr = ashr_time + (config.ihtiyat.get("ashr").unwrap_or(&0.0) / 60.0);
+    let ashr = (ashr_time < 24.0).then_some(ashr);
 
     // --- MAGHRIB (Sunset) ---
-    // h_maghrib = -SD - Refraction - Dip
-    // Refraction at horizon approx 34' = 0.566 deg
-    // SD approx 16' = 0.266 deg
-    // Dip = 0.0293 * sqrt(h_meters) in degrees? VB6: 0.0321 * Sqr(Lokasi.tinggi) (arcminutes?)
-    // Standard: 0.8333 deg depression usually covers SD+Refraction.
-    // VB6 code: hs = -SD - r. r = Refraction(-SD).
-    let h_maghrib = -0.8333 - (0.0347 * location.elevation.sqrt() / 60.0); // Dip correction
-
-    let maghrib_time =
-        calculate_time_for_altitude(location, declination, eq_of_time, h_maghrib, 1.0);
-    let maghrib = maghrib_time + (config.ihtiyat.get("maghrib").unwrap_or(&0.0) / 60.0);
-    let syuruq_time =
-        calculate_time_for_altitude(location, declination, eq_of_time, h_maghrib, -1.0); // Morning
-    let syuruq = syuruq_time - (config.ihtiyat.get("syuruq").unwrap_or(&0.0) / 60.0); // Syuruq minus ihtiyat? Usually syuruq displayed is start of sunrise or end?
-                                                                                      // PrayerTimes.bas: JS0.Syuruq = TPray(...) - Ihtiyat. So Syuruq time is earlier?
-                                                                                      // Usually "Terbit" displayed is when disk appears.
+    // h_maghrib = -SD - Refraction - Dip, sama dengan acuan ghurub hilal (rise_set)
+    let h_maghrib = config.horizon.sun_altitude(location, jd_noon);
+
+    let sunset = calculate_time_for_altitude(location, jd_noon, h_maghrib, 1.0);
+
+    // Waktu tidak terdefinisi (calculate_time_for_altitude >= 24) menjadi None
+    let defined = |raw: f64, time: f64| (raw < 24.0).then_some(time);
+
+    // Metode Tehran: Maghrib saat matahari pada sudut dip tertentu
+    let maghrib_time = match config.maghrib_angle {
+        Some(angle) => calculate_time_for_altitude(location, jd_noon, -angle, 1.0),
+        None => sunset,
+    };
+    let maghrib = defined(
+        maghrib_time,
+        maghrib_time + (config.ihtiyat.get("maghrib").unwrap_or(&0.0) / 60.0),
+    );
+    let syuruq_time = calculate_time_for_altitude(location, jd_noon, h_maghrib, -1.0); // Morning
+    let syuruq = defined(
+        syuruq_time,
+        syuruq_time - (config.ihtiyat.get("syuruq").unwrap_or(&0.0) / 60.0),
+    ); // Syuruq minus ihtiyat? Usually syuruq displayed is start of sunrise or end?
+       // PrayerTimes.bas: JS0.Syuruq = TPray(...) - Ihtiyat. So Syuruq time is earlier?
+       // Usually "Terbit" displayed is when disk appears.
+
+    // Penyesuaian lintang tinggi (Shubuh/Isya) dihitung dari waktu sebelum ihtiyat
+    let mut high_latitude = Vec::new();
+    let mut adjust = |prayer: &'static str, time: f64, angle: f64, sign: f64| {
+        let night = HighLatitudeNight {
+            jd_noon,
+            sunrise: syuruq_time,
+            sunset,
+        };
+        match high_latitude_time(config, location, &night, time, angle, sign) {
+            Some(adjusted) => {
+                high_latitude.push(HighLatitudeAdjustment {
+                    prayer: prayer.to_string(),
+                    rule: config.high_latitude,
+                });
+                adjusted
+            }
+            None => time,
+        }
+    };
 
     // --- ISYA ---
     // h_isya = -Isya_Angle
     let h_isya = -config.isya_angle;
-    let isya_time = calculate_time_for_altitude(location, declination, eq_of_time, h_isya, 1.0);
-    let isya = isya_time + (config.ihtiyat.get("isya").unwrap_or(&0.0) / 60.0);
+    let isya = match config.isya_interval {
+        // Umm al-Qura: interval tetap setelah Maghrib
+        Some(minutes) => maghrib.map(|maghrib| maghrib + minutes / 60.0),
+        None => {
+            let isya_time = calculate_time_for_altitude(location, jd_noon, h_isya, 1.0);
+            let isya_time = adjust("isya", isya_time, config.isya_angle, 1.0);
+            defined(
+                isya_time,
+                isya_time + (config.ihtiyat.get("isya").unwrap_or(&0.0) / 60.0),
+            )
+        }
+    };
 
     // --- SHUBUH ---
     // h_shubuh = -Shubuh_Angle
     let h_shubuh = -config.shubuh_angle;
-    let shubuh_time =
-        calculate_time_for_altitude(location, declination, eq_of_time, h_shubuh, -1.0);
-    let shubuh = shubuh_time + (config.ihtiyat.get("shubuh").unwrap_or(&0.0) / 60.0); // Add ihtiyat to start of prayer window
+    let shubuh_time = calculate_time_for_altitude(location, jd_noon, h_shubuh, -1.0);
+    let shubuh_time = adjust("shubuh", shubuh_time, config.shubuh_angle, -1.0);
+    // Add ihtiyat to start of prayer window
+    let shubuh = defined(
+        shubuh_time,
+        shubuh_time + (config.ihtiyat.get("shubuh").unwrap_or(&0.0) / 60.0),
+    );
 
     // --- IMSAK ---
     // Imsak = Shubuh - 10 mins
-    let imsak = shubuh - (config.imsak_margin / 60.0);
+    let imsak = shubuh.map(|shubuh| shubuh - (config.imsak_margin / 60.0));
 
     // --- DHUHA ---
     // h_dhuha = Dhuha Angle (4.5 deg)
     let h_dhuha = config.dhuha_angle;
-    let dhuha_time = calculate_time_for_altitude(location, declination, eq_of_time, h_dhuha, -1.0);
-    let dhuha = dhuha_time + (config.ihtiyat.get("dhuha").unwrap_or(&0.0) / 60.0);
+    let dhuha_time = calculate_time_for_altitude(location, jd_noon, h_dhuha, -1.0);
+    let dhuha = defined(
+        dhuha_time,
+        dhuha_time + (config.ihtiyat.get("dhuha").unwrap_or(&0.0) / 60.0),
+    );
+
+    // --- ISTIWA, ZAWAL & KARAHAH ---
+    // Batas rentang fiqh: (jenis, awal, tinggi awal, akhir, tinggi akhir), jam lokal
+    let culmination = 90.0 - (location.latitude - declination).abs();
+    let zawal_start = transit - config.zawal_minutes / 60.0;
+    let zawal_altitude = super::coordinates::altitude_from_hour_angle(
+        location.latitude,
+        config.zawal_minutes / 4.0, // 1 menit = 0.25° hour angle
+        declination,
+    );
+    let isfirar_time = calculate_time_for_altitude(location, jd_noon, config.isfirar_angle, 1.0);
+    let (ikhtiyari_altitude, ikhtiyari_end) = if config.ashr_shadow_ratio < 2.0 {
+        let altitude = ashr_altitude(location.latitude, declination, 2.0);
+        (altitude, calculate_time_for_shadow(location, jd_noon, 2.0))
+    } else {
+        (config.isfirar_angle, isfirar_time)
+    };
+    let boundaries = [
+        (
+            PrayerIntervalKind::KarahahSyuruq,
+            syuruq_time,
+            h_maghrib,
+            dhuha_time,
+            config.dhuha_angle,
+        ),
+        (
+            PrayerIntervalKind::Zawal,
+            zawal_start,
+            zawal_altitude,
+            transit,
+            culmination,
+        ),
+        (
+            PrayerIntervalKind::AshrIkhtiyari,
+            ashr_time,
+            h_ashr,
+            ikhtiyari_end,
+            ikhtiyari_altitude,
+        ),
+        (
+            PrayerIntervalKind::KarahahGhurub,
+            isfirar_time,
+            config.isfirar_angle,
+            sunset,
+            h_maghrib,
+        ),
+    ];
 
     // --- MIDNIGHT & 1/3 NIGHT ---
     // Midnight = (Shubuh_Next_Day - Maghrib) / 2 + Maghrib
     // Simplify: (Shubuh + 24 - Maghrib) / 2 + Maghrib
-    let diff = (shubuh + 24.0 - maghrib) % 24.0;
-    let tengah_malam = (maghrib + diff / 2.0) % 24.0;
-    let p3_malam = (maghrib + diff * (2.0 / 3.0)) % 24.0;
+    let night = shubuh
+        .zip(maghrib)
+        .map(|(shubuh, maghrib)| (maghrib, (shubuh + 24.0 - maghrib) % 24.0));
+    let tengah_malam = night.map(|(maghrib, diff)| maghrib + diff / 2.0);
+    let p3_malam = night.map(|(maghrib, diff)| maghrib + diff * (2.0 / 3.0));
+
+    // Waktu lokal dihitung dari tengah malam tanggal hitungan; waktu malam yang jatuh
+    // setelah pukul 00:00 (Isya lintang tinggi, tengah malam, sepertiga malam) masuk
+    // tanggal berikutnya
+    let local_midnight = crate::calendar::gregorian_to_jd(&GregorianDate {
+        year: date.year,
+        month: date.month,
+        day: date.day.floor(),
+    }) - location.timezone / 24.0;
+    let event = |hours: Option<f64>, night: bool| {
+        hours.map(|hours| {
+            let hours = if night && hours < 12.0 {
+                hours + 24.0
+            } else {
+                hours
+            };
+            prayer_event(location, local_midnight + hours / 24.0)
+        })
+    };
 
     PrayerTimes {
-        imsak: format_time(imsak),
-        shubuh: format_time(shubuh),
-        terbit: format_time(syuruq),
-        dhuha: format_time(dhuha),
-        dzuhur: format_time(dzuhur),
-        ashr: format_time(ashr),
-        maghrib: format_time(maghrib),
-        isya: format_time(isya),
-        tengah_malam: format_time(tengah_malam),
-        p3_malam: format_time(p3_malam),
+        timezone: location.timezone,
+        imsak: event(imsak, false),
+        shubuh: event(shubuh, false),
+        terbit: event(syuruq, false),
+        dhuha: event(dhuha, false),
+        dzuhur: event(Some(dzuhur), false),
+        ashr: event(ashr, false),
+        maghrib: event(maghrib, false),
+        isya: event(isya, true),
+        tengah_malam: event(tengah_malam, true),
+        p3_malam: event(p3_malam, true),
+        istiwa: event(Some(transit), false),
+        intervals: boundaries
+            .into_iter()
+            .filter(|(_, start, _, end, _)| *start < 24.0 && *end < 24.0)
+            .map(
+                |(kind, start, start_altitude, end, end_altitude)| PrayerInterval {
+                    kind,
+                    start: prayer_event(location, local_midnight + start / 24.0),
+                    end: prayer_event(location, local_midnight + end / 24.0),
+                    start_altitude,
+                    end_altitude,
+                },
+            )
+            .collect(),
+        high_latitude,
+    }
+}
+
+/// Tinggi matahari saat bayangan = `shadow_ratio` x benda + bayangan saat istiwa
+///
+/// cot(h) = shadow_ratio + tan(|lat - dec|); 1.0 = Syafi'i, 2.0 = Hanafi
+fn ashr_altitude(latitude: f64, declination: f64, shadow_ratio: f64) -> f64 {
+    let zenith = (latitude - declination).abs().to_radians();
+    (1.0 / (shadow_ratio + zenith.tan())).atan().to_degrees()
+}
+
+/// Waktu shalat pada JD UT beserta posisi matahari saat itu
+fn prayer_event(location: &GeoLocation, jd_utc: f64) -> PrayerEvent {
+    let sun = sun::geocentric_position(jd_utc);
+    let lst = super::topocentric::local_sidereal_time_hours(location.longitude, jd_utc) * 15.0;
+    let hour_angle = lst - sun.right_ascension;
+
+    let offset = chrono::FixedOffset::east_opt((location.timezone * 3600.0).round() as i32)
+        .unwrap_or(chrono::FixedOffset::east_opt(0).unwrap());
+    let seconds = ((jd_utc - 2440587.5) * 86400.0).round() as i64;
+    let local = chrono::DateTime::from_timestamp(seconds, 0)
+        .map(|utc| {
+            utc.with_timezone(&offset)
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+        })
+        .unwrap_or_default();
+
+    PrayerEvent {
+        jd_utc,
+        local,
+        hours: ((jd_utc + 0.5).fract() * 24.0 + location.timezone).rem_euclid(24.0),
+        altitude: super::coordinates::altitude_from_hour_angle(
+            location.latitude,
+            hour_angle,
+            sun.declination,
+        ),
+        azimuth: (super::ephemeris_utils::calculate_azimuth(
+            hour_angle,
+            sun.declination,
+            location.latitude,
+        ) + 180.0)
+            .rem_euclid(360.0),
     }
 }
 
+/// Terbit, terbenam dan tengah hari untuk aturan lintang tinggi
+struct HighLatitudeNight {
+    jd_noon: f64,
+    sunrise: f64,
+    sunset: f64,
+}
+
+/// Waktu pengganti Shubuh (`sign` -1) atau Isya (`sign` 1) menurut aturan lintang tinggi
+///
+/// `None` jika waktu asli tetap dipakai (aturan tidak berlaku atau tidak dapat dihitung).
+fn high_latitude_time(
+    config: &PrayerConfig,
+    location: &GeoLocation,
+    night: &HighLatitudeNight,
+    time: f64,
+    angle: f64,
+    sign: f64,
+) -> Option<f64> {
+    let undefined = time >= 24.0;
+    let fraction = match config.high_latitude {
+        HighLatitudeRule::None => return None,
+        HighLatitudeRule::MiddleOfNight => 0.5,
+        HighLatitudeRule::OneSeventh => 1.0 / 7.0,
+        HighLatitudeRule::AngleBased => angle / 60.0,
+        HighLatitudeRule::NearestLatitude { latitude } => {
+            if !undefined || location.latitude.abs() <= latitude {
+                return None;
+            }
+            let nearest = GeoLocation {
+                latitude: latitude.copysign(location.latitude),
+                ..location.clone()
+            };
+            let time = calculate_time_for_altitude(&nearest, night.jd_noon, -angle, sign);
+            return (time < 24.0).then_some(time);
+        }
+        HighLatitudeRule::NearestDay => {
+            if !undefined {
+                return None;
+            }
+            return (1..=183)
+                .flat_map(|days| [-(days as f64), days as f64])
+                .find_map(|offset| {
+                    let time =
+                        calculate_time_for_altitude(location, night.jd_noon + offset, -angle, sign);
+                    (time < 24.0).then_some(time)
+                });
+        }
+    };
+
+    // Aturan porsi malam butuh terbit dan terbenam (tidak berlaku saat matahari tidak terbenam)
+    if night.sunrise >= 24.0 || night.sunset >= 24.0 {
+        return None;
+    }
+    let portion = (night.sunrise - night.sunset).rem_euclid(24.0) * fraction;
+    let (limit, distance) = if sign < 0.0 {
+        (
+            night.sunrise - portion,
+            (night.sunrise - time).rem_euclid(24.0),
+        )
+    } else {
+        (
+            night.sunset + portion,
+            (time - night.sunset).rem_euclid(24.0),
+        )
+    };
+    (undefined || distance > portion).then(|| limit.rem_euclid(24.0))
+}
+
 /// Helper function to calculate time for a specific sun altitude
 /// sign: -1 for morning (East), 1 for afternoon (West)
 fn calculate_time_for_altitude(
     location: &GeoLocation,
-    declination: f64,
-    eq_of_time: f64,
+    jd_noon: f64,
     altitude: f64,
     sign: f64,
 ) -> f64 {
-    let lat_rad = location.latitude.to_radians();
+    solve_solar_time(location, jd_noon, |declination| {
+        hour_angle_for_altitude(location.latitude, declination, altitude).map(|h| sign * h)
+    })
+}
+
+/// Waktu Ashr (sore) saat bayangan = `shadow_ratio` x benda + bayangan saat istiwa
+fn calculate_time_for_shadow(location: &GeoLocation, jd_noon: f64, shadow_ratio: f64) -> f64 {
+    solve_solar_time(location, jd_noon, |declination| {
+        let altitude = ashr_altitude(location.latitude, declination, shadow_ratio);
+        hour_angle_for_altitude(location.latitude, declination, altitude)
+    })
+}
+
+/// Sudut jam (jam) saat matahari pada tinggi `altitude`, `None` jika tidak pernah tercapai
+fn hour_angle_for_altitude(latitude: f64, declination: f64, altitude: f64) -> Option<f64> {
+    let lat_rad = latitude.to_radians();
     let dec_rad = declination.to_radians();
     let alt_rad = altitude.to_radians();
 
     // cos(H) = (sin(Alt) - sin(Lat)sin(Dec)) / (cos(Lat)cos(Dec))
     let cos_h = (alt_rad.sin() - lat_rad.sin() * dec_rad.sin()) / (lat_rad.cos() * dec_rad.cos());
-
-    if cos_h < -1.0 || cos_h > 1.0 {
-        return 99.99; // Never reaches this altitude
+    if !(-1.0..=1.0).contains(&cos_h) {
+        return None; // Never reaches this altitude
     }
+    Some(cos_h.acos().to_degrees() / 15.0)
+}
 
-    let h_rad = cos_h.acos();
-    let h_deg = h_rad.to_degrees();
-    let h_hours = h_deg / 15.0;
-
+/// Waktu lokal (jam) saat sudut jam matahari = `hour_angle(deklinasi)`
+///
+/// Seperti `PrayingTimes.bas`, deklinasi dan equation of time dihitung ulang pada waktu
+/// hasil iterasi sebelumnya sampai konvergen, bukan sekali pada tengah hari. Mengembalikan
+/// 99.99 jika waktu tidak terdefinisi.
+fn solve_solar_time(
+    location: &GeoLocation,
+    jd_noon: f64,
+    hour_angle: impl Fn(f64) -> Option<f64>,
+) -> f64 {
     let kwd = location.timezone - (location.longitude / 15.0);
 
-    // Time = 12 - EoT + sign*H + kwd
-    let mut time = 12.0 - eq_of_time + (sign * h_hours) + kwd;
-    time = time.rem_euclid(24.0);
-    time
+    // Tebakan awal: tengah hari waktu setempat
+    let mut time = 12.0 + kwd;
+    for _ in 0..5 {
+        let jd = jd_noon + (time - 12.0 - location.timezone) / 24.0;
+        let Some(h) = hour_angle(sun::declination(jd)) else {
+            return 99.99;
+        };
+
+        // Time = 12 - EoT + sign*H + kwd
+        let new_time = 12.0 - sun::equation_of_time(jd) + h + kwd;
+        let converged = (new_time - time).abs() < 1e-5; // ~0.04 detik
+        time = new_time;
+        if converged {
+            break;
+        }
+    }
+    time.rem_euclid(24.0)
 }
 
-fn format_time(hours: f64) -> String {
-    if hours >= 24.0 {
-        return "Invalid".to_string();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minutes(event: &Option<PrayerEvent>) -> i32 {
+        (event.as_ref().unwrap().hours * 60.0).round() as i32
+    }
+
+    #[test]
+    fn test_method_presets() {
+        let makkah = GeoLocation {
+            name: Some("Makkah".to_string()),
+            latitude: 21.4225,
+            longitude: 39.8262,
+            elevation: 0.0,
+            timezone: 3.0,
+        };
+        let date = GregorianDate {
+            year: 2026,
+            month: 3,
+            day: 1.0,
+        };
+        let times = |method: PrayerMethod| calculate_prayer_times(&makkah, &date, &method.config());
+
+        // Umm al-Qura: Isya tepat 90 menit setelah Maghrib
+        let uq = times(PrayerMethod::UmmAlQura);
+        assert_eq!(minutes(&uq.isya) - minutes(&uq.maghrib), 90);
+
+        // Tehran: Maghrib pada dip 4.5°, lebih lambat dari terbenam (MWL)
+        let mwl = times(PrayerMethod::Mwl);
+        let tehran = times(PrayerMethod::Tehran);
+        assert!(minutes(&tehran.maghrib) - minutes(&mwl.maghrib) >= 15);
+
+        // ISNA (15°) Shubuh lebih lambat dari MWL (18°)
+        assert!(minutes(&times(PrayerMethod::Isna).shubuh) > minutes(&mwl.shubuh));
+        assert_eq!(PrayerConfig::default(), PrayerMethod::Kemenag.config());
     }
-    let h = hours.floor() as u32;
-    let m_float = (hours - h as f64) * 60.0;
-    let m = m_float.floor() as u32;
-    let s = ((m_float - m as f64) * 60.0).round() as u32; // Round to nearest second
 
-    // Adjust for second overflow
-    if s == 60 {
-        let m_new = m + 1;
-        let s_new = 0;
-        if m_new == 60 {
-            let h_new = (h + 1) % 24;
-            return format!("{:02}:{:02}", h_new, 0);
+    #[test]
+    fn test_high_latitude_rules() {
+        // London, 21 Juni: matahari hanya turun ~15°, Isya MWL (17°) tidak terdefinisi
+        let london = GeoLocation {
+            name: Some("London".to_string()),
+            latitude: 51.5074,
+            longitude: -0.1278,
+            elevation: 0.0,
+            timezone: 1.0,
+        };
+        let date = GregorianDate {
+            year: 2026,
+            month: 6,
+            day: 21.0,
+        };
+        let times = |rule: HighLatitudeRule| {
+            let config = PrayerConfig {
+                high_latitude: rule,
+                ..PrayerMethod::Mwl.config()
+            };
+            calculate_prayer_times(&london, &date, &config)
+        };
+
+        let none = times(HighLatitudeRule::None);
+        assert!(none.isya.is_none() && none.shubuh.is_none());
+        assert!(none.high_latitude.is_empty());
+
+        // Tengah malam antara terbenam ~21:21 dan terbit ~04:43 BST
+        let middle = times(HighLatitudeRule::MiddleOfNight);
+        assert!(
+            (55..=70).contains(&minutes(&middle.isya)),
+            "{:?}",
+            middle.isya
+        );
+        assert_eq!(middle.high_latitude.len(), 2);
+        assert_eq!(middle.high_latitude[0].prayer, "isya");
+
+        for rule in [
+            HighLatitudeRule::OneSeventh,
+            HighLatitudeRule::AngleBased,
+            HighLatitudeRule::NearestLatitude { latitude: 48.0 },
+            HighLatitudeRule::NearestDay,
+        ] {
+            let adjusted = times(rule);
+            assert!(minutes(&adjusted.isya) >= 22 * 60 || minutes(&adjusted.isya) < 2 * 60);
+            assert!(
+                (60..5 * 60).contains(&minutes(&adjusted.shubuh)),
+                "{:?}",
+                rule
+            );
+            assert!(adjusted.high_latitude.iter().all(|a| a.rule == rule));
         }
-        return format!("{:02}:{:02}", h, m_new);
+
+        // Di Jakarta aturan default tidak mengubah apa pun
+        let jakarta = GeoLocation {
+            name: None,
+            latitude: -6.2,
+            longitude: 106.8167,
+            elevation: 0.0,
+            timezone: 7.0,
+        };
+        let times = calculate_prayer_times(&jakarta, &date, &PrayerConfig::default());
+        assert!(times.high_latitude.is_empty());
+    }
+
+    #[test]
+    fn test_typed_events() {
+        let jakarta = GeoLocation {
+            name: Some("Jakarta".to_string()),
+            latitude: -6.2,
+            longitude: 106.8167,
+            elevation: 0.0,
+            timezone: 7.0,
+        };
+        let date = GregorianDate {
+            year: 2026,
+            month: 2,
+            day: 18.0,
+        };
+        let times = calculate_prayer_times(&jakarta, &date, &PrayerConfig::default());
+
+        let maghrib = times.maghrib.as_ref().unwrap();
+        assert!(
+            maghrib.local.starts_with("2026-02-18T18:"),
+            "{}",
+            maghrib.local
+        );
+        assert!(maghrib.local.ends_with("+07:00"));
+        assert!((maghrib.jd_utc - (2461089.5 + (maghrib.hours - 7.0) / 24.0)).abs() < 1e-6);
+        // Ihtiyat 2 menit setelah terbenam (-0.83°), matahari di barat
+        assert!(
+            (-1.6..-0.8).contains(&maghrib.altitude),
+            "{}",
+            maghrib.altitude
+        );
+        assert!(
+            (240.0..300.0).contains(&maghrib.azimuth),
+            "{}",
+            maghrib.azimuth
+        );
+
+        let shubuh = times.shubuh.as_ref().unwrap();
+        assert!(
+            (-20.0..-19.0).contains(&shubuh.altitude),
+            "{}",
+            shubuh.altitude
+        );
+        // Sepertiga malam terakhir jatuh pada tanggal berikutnya
+        assert!(times.p3_malam.unwrap().local.starts_with("2026-02-19T"));
     }
 
-    format!("{:02}:{:02}", h, m)
+    #[test]
+    fn test_fiqh_intervals() {
+        let jakarta = GeoLocation {
+            name: Some("Jakarta".to_string()),
+            latitude: -6.2,
+            longitude: 106.8167,
+            elevation: 0.0,
+            timezone: 7.0,
+        };
+        let date = GregorianDate {
+            year: 2026,
+            month: 2,
+            day: 18.0,
+        };
+        let times = |ashr_shadow_ratio: f64| {
+            let config = PrayerConfig {
+                ashr_shadow_ratio,
+                ..PrayerConfig::default()
+            };
+            calculate_prayer_times(&jakarta, &date, &config)
+        };
+        let interval = |times: &PrayerTimes, kind: PrayerIntervalKind| {
+            times
+                .intervals
+                .iter()
+                .find(|interval| interval.kind == kind)
+                .cloned()
+                .unwrap()
+        };
+
+        let syafii = times(1.0);
+        assert_eq!(syafii.intervals.len(), 4);
+
+        // Zawal berakhir tepat saat istiwa, dua menit sebelum Dzuhur (ihtiyat)
+        let zawal = interval(&syafii, PrayerIntervalKind::Zawal);
+        let istiwa = syafii.istiwa.as_ref().unwrap();
+        assert_eq!(zawal.end.jd_utc, istiwa.jd_utc);
+        assert!((zawal.end.jd_utc - zawal.start.jd_utc - 5.0 / 1440.0).abs() < 1e-9);
+        assert!((istiwa.altitude - zawal.end_altitude).abs() < 0.1);
+        let dzuhur = syafii.dzuhur.as_ref().unwrap();
+        assert!((dzuhur.jd_utc - istiwa.jd_utc - 2.0 / 1440.0).abs() < 1e-9);
+
+        // Karahah pagi: terbit sampai Dhuha (tanpa ihtiyat), sore: menguning sampai terbenam
+        let syuruq = interval(&syafii, PrayerIntervalKind::KarahahSyuruq);
+        assert!((syuruq.end.altitude - 4.5).abs() < 0.1);
+        assert!(syuruq.start.jd_utc < syafii.dhuha.as_ref().unwrap().jd_utc);
+        let ghurub = interval(&syafii, PrayerIntervalKind::KarahahGhurub);
+        assert!(ghurub.end.jd_utc < syafii.maghrib.as_ref().unwrap().jd_utc);
+        assert!((ghurub.start.altitude - 4.5).abs() < 0.1);
+
+        // Syafi'i: ikhtiyari sampai bayangan 2x; Hanafi: awal Ashr = bayangan 2x, ikhtiyari
+        // sampai menguning
+        let ikhtiyari = interval(&syafii, PrayerIntervalKind::AshrIkhtiyari);
+        let hanafi = times(2.0);
+        let hanafi_ikhtiyari = interval(&hanafi, PrayerIntervalKind::AshrIkhtiyari);
+        assert!((ikhtiyari.end.jd_utc - hanafi_ikhtiyari.start.jd_utc).abs() < 1e-9);
+        assert_eq!(hanafi_ikhtiyari.end, ghurub.start);
+        assert!(ikhtiyari.start_altitude > ikhtiyari.end_altitude);
+    }
+
+    #[test]
+    fn test_solar_position_per_event() {
+        let jakarta = GeoLocation {
+            name: Some("Jakarta Pusat".to_string()),
+            latitude: -6.1745,
+            longitude: 106.8227,
+            elevation: 8.0,
+            timezone: 7.0,
+        };
+        let date = |year: i32, month: u8, day: f64| GregorianDate { year, month, day };
+
+        // Tanpa ihtiyat, tinggi matahari pada setiap waktu tepat sama dengan sudut kriteria
+        let exact = PrayerConfig {
+            ihtiyat: HashMap::new(),
+            ..PrayerConfig::default()
+        };
+        let times = calculate_prayer_times(&jakarta, &date(2025, 3, 1.0), &exact);
+        let altitude = |event: &Option<PrayerEvent>| event.as_ref().unwrap().altitude;
+        assert!((altitude(&times.shubuh) + 20.0).abs() < 0.01);
+        assert!((altitude(&times.isya) + 18.0).abs() < 0.01);
+        assert!((altitude(&times.dhuha) - 4.5).abs() < 0.01);
+        let zawal = times
+            .intervals
+            .iter()
+            .find(|interval| interval.kind == PrayerIntervalKind::Zawal)
+            .unwrap();
+        assert!((altitude(&times.istiwa) - zawal.end_altitude).abs() < 0.01);
+
+        // Jadwal Kemenag RI, Jakarta Pusat (HH:MM), dip ufuk dari ketinggian lokasi
+        let schedules = [
+            (
+                date(2025, 1, 1.0),
+                [(4, 19), (5, 41), (11, 59), (15, 26), (18, 13), (19, 29)],
+            ),
+            (
+                date(2025, 3, 1.0),
+                [(4, 43), (5, 58), (12, 8), (15, 11), (18, 14), (19, 24)],
+            ),
+        ];
+        let format = crate::astronomy::prayer_format::PrayerTimeFormat::kemenag();
+        for (date, expected) in schedules {
+            let times = calculate_prayer_times(&jakarta, &date, &PrayerConfig::default());
+            let text = crate::astronomy::prayer_format::format_prayer_times(&times, &format);
+            let actual = [
+                &text.shubuh,
+                &text.terbit,
+                &text.dzuhur,
+                &text.ashr,
+                &text.maghrib,
+                &text.isya,
+            ];
+            for (actual, (hour, minute)) in actual.into_iter().zip(expected) {
+                assert_eq!(*actual, format!("{:02}:{:02}", hour, minute), "{:?}", date);
+            }
+        }
+    }
+
+    #[test]
+    fn test_settings_from_name_or_config() {
+        let settings: PrayerSettings = serde_json::from_str("\"umm_al_qura\"").unwrap();
+        assert_eq!(settings.config().isya_interval, Some(90.0));
+
+        // Konfigurasi parsial: field lain memakai default
+        let settings: PrayerSettings =
+            serde_json::from_str(r#"{"shubuh_angle": 18.0, "isya_angle": 17.0}"#).unwrap();
+        let config = settings.config();
+        assert_eq!((config.shubuh_angle, config.isya_angle), (18.0, 17.0));
+        assert_eq!(config.imsak_margin, 10.0);
+        assert!(serde_json::from_str::<PrayerSettings>("\"unknown\"").is_err());
+    }
 }
@@ -0,0 +1,193 @@
+//! Format teks waktu shalat
+//!
+//! Hasil `calculate_prayer_times` bertipe (JD, waktu lokal, posisi matahari). Modul ini
+//! mengubahnya menjadi teks untuk tampilan dan jadwal cetak: format 24/12 jam, dengan
+//! atau tanpa detik, dan arah pembulatan per waktu. Default mengikuti tampilan lama
+//! (dibulatkan ke detik lalu dipotong ke menit). Kemenag RI membulatkan semua waktu ke
+//! atas kecuali Terbit yang dibulatkan ke bawah, agar tidak ada waktu yang maju.
+
+use std::collections::HashMap;
+
+use super::prayer::{PrayerEvent, PrayerTimes};
+
+/// Teks untuk waktu yang tidak terdefinisi
+pub const UNDEFINED_TIME: &str = "Invalid";
+
+/// Format jam
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockFormat {
+    #[default]
+    H24, // "04:31"
+    H12, // "04:31 AM"
+}
+
+/// Arah pembulatan ke menit (atau ke detik bila detik ditampilkan)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rounding {
+    Nearest,
+    Up,
+    Down,
+}
+
+/// Pilihan format teks waktu shalat
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct PrayerTimeFormat {
+    pub clock: ClockFormat,
+    pub seconds: bool,
+    pub rounding: Rounding,                            // Pembulatan default
+    pub rounding_overrides: HashMap<String, Rounding>, // Per waktu, mis. "terbit" => Down
+}
+
+impl Default for PrayerTimeFormat {
+    fn default() -> Self {
+        Self::truncated()
+    }
+}
+
+impl PrayerTimeFormat {
+    /// Format lama: 24 jam, tanpa detik, menit dipotong (tidak dibulatkan ke atas)
+    pub fn truncated() -> Self {
+        PrayerTimeFormat {
+            clock: ClockFormat::H24,
+            seconds: false,
+            rounding: Rounding::Down,
+            rounding_overrides: HashMap::new(),
+        }
+    }
+
+    /// Format jadwal Kemenag RI: 24 jam, tanpa detik, dibulatkan ke atas kecuali Terbit
+    pub fn kemenag() -> Self {
+        PrayerTimeFormat {
+            clock: ClockFormat::H24,
+            seconds: false,
+            rounding: Rounding::Up,
+            rounding_overrides: HashMap::from([("terbit".to_string(), Rounding::Down)]),
+        }
+    }
+
+    /// Arah pembulatan untuk satu waktu
+    pub fn rounding_for(&self, prayer: &str) -> Rounding {
+        self.rounding_overrides
+            .get(prayer)
+            .copied()
+            .unwrap_or(self.rounding)
+    }
+
+    /// Teks satu waktu ("Invalid" jika tidak terdefinisi)
+    pub fn format_event(&self, prayer: &str, event: Option<&PrayerEvent>) -> String {
+        let Some(event) = event else {
+            return UNDEFINED_TIME.to_string();
+        };
+
+        // Bulatkan ke detik dulu agar 04:31:00.0000001 tidak menjadi 04:32 saat dibulatkan ke atas
+        let seconds = (event.hours * 3600.0).round() as i64;
+        let unit = if self.seconds { 1 } else { 60 };
+        let units = match self.rounding_for(prayer) {
+            Rounding::Nearest => (seconds + unit / 2).div_euclid(unit),
+            Rounding::Up => (seconds + unit - 1).div_euclid(unit),
+            Rounding::Down => seconds.div_euclid(unit),
+        };
+        let seconds = (units * unit).rem_euclid(86400);
+        let (hours, minutes, secs) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+
+        let (hours, suffix) = match self.clock {
+            ClockFormat::H24 => (hours, ""),
+            ClockFormat::H12 => (
+                if hours % 12 == 0 { 12 } else { hours % 12 },
+                if hours < 12 { " AM" } else { " PM" },
+            ),
+        };
+        if self.seconds {
+            format!("{:02}:{:02}:{:02}{}", hours, minutes, secs, suffix)
+        } else {
+            format!("{:02}:{:02}{}", hours, minutes, suffix)
+        }
+    }
+}
+
+/// Waktu shalat sebagai teks
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FormattedPrayerTimes {
+    pub imsak: String,
+    pub shubuh: String,
+    pub terbit: String,
+    pub dhuha: String,
+    pub dzuhur: String,
+    pub ashr: String,
+    pub maghrib: String,
+    pub isya: String,
+    pub tengah_malam: String,
+    pub p3_malam: String,
+    pub istiwa: String,
+}
+
+/// Format semua waktu shalat
+pub fn format_prayer_times(times: &PrayerTimes, format: &PrayerTimeFormat) -> FormattedPrayerTimes {
+    let text =
+        |prayer: &str, event: &Option<PrayerEvent>| format.format_event(prayer, event.as_ref());
+    FormattedPrayerTimes {
+        imsak: text("imsak", &times.imsak),
+        shubuh: text("shubuh", &times.shubuh),
+        terbit: text("terbit", &times.terbit),
+        dhuha: text("dhuha", &times.dhuha),
+        dzuhur: text("dzuhur", &times.dzuhur),
+        ashr: text("ashr", &times.ashr),
+        maghrib: text("maghrib", &times.maghrib),
+        isya: text("isya", &times.isya),
+        tengah_malam: text("tengah_malam", &times.tengah_malam),
+        p3_malam: text("p3_malam", &times.p3_malam),
+        istiwa: text("istiwa", &times.istiwa),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(hours: f64) -> PrayerEvent {
+        PrayerEvent {
+            jd_utc: 0.0,
+            local: String::new(),
+            hours,
+            altitude: 0.0,
+            azimuth: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_rounding_and_clock() {
+        let kemenag = PrayerTimeFormat::kemenag();
+        let time = event(4.0 + 31.0 / 60.0 + 7.0 / 3600.0); // 04:31:07
+        assert_eq!(kemenag.format_event("shubuh", Some(&time)), "04:32");
+        assert_eq!(kemenag.format_event("terbit", Some(&time)), "04:31");
+        assert_eq!(kemenag.format_event("isya", Some(&event(19.0))), "19:00");
+        assert_eq!(kemenag.format_event("isya", None), UNDEFINED_TIME);
+
+        let format = PrayerTimeFormat {
+            clock: ClockFormat::H12,
+            seconds: true,
+            ..kemenag
+        };
+        assert_eq!(format.format_event("shubuh", Some(&time)), "04:31:07 AM");
+        assert_eq!(
+            format.format_event("maghrib", Some(&event(18.0 + 5.0 / 60.0))),
+            "06:05:00 PM"
+        );
+
+        // Default: dipotong ke menit seperti tampilan lama, 04:31:59.6 dibulatkan ke detik dulu
+        let default = PrayerTimeFormat::default();
+        assert_eq!(default.format_event("shubuh", Some(&time)), "04:31");
+        let almost = event(4.0 + 31.0 / 60.0 + 59.6 / 3600.0);
+        assert_eq!(default.format_event("shubuh", Some(&almost)), "04:32");
+
+        // Pembulatan melewati tengah malam
+        let nearest = PrayerTimeFormat {
+            rounding: Rounding::Nearest,
+            ..PrayerTimeFormat::kemenag()
+        };
+        assert_eq!(nearest.format_event("isya", Some(&event(23.999))), "00:00");
+    }
+}
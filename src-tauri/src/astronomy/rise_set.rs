@@ -0,0 +1,232 @@
+//! Terbit dan terbenam matahari
+//!
+//! Satu mesin untuk Maghrib/Syuruq waktu shalat dan acuan ghurub hisab hilal. Tinggi pusat
+//! matahari saat terbit/terbenam dihitung dari:
+//! - semidiameter sebenarnya dari jarak Bumi-Matahari (959.63" / jarak AU)
+//! - refraksi pada ufuk mar'i, dikoreksi tekanan dan suhu udara
+//! - kerendahan ufuk (dip) dari ketinggian pengamat
+//!
+//! Ufuk laut (`Horizon::Ocean`) memakai ketinggian lokasi di atas permukaan laut, cocok untuk
+//! tempat rukyat menghadap laut (mis. Bukit Condrodipo). Ufuk daratan (`Horizon::Terrain`)
+//! hanya memakai ketinggian pengamat di atas daratan sekitar (mis. atap gedung). Default
+//! ufuk laut dari `GeoLocation::elevation`; di ketinggian 0 m mendekati -0°50' klasik VB6.
+
+use serde::{Deserialize, Serialize};
+
+use super::sun;
+use crate::{GeoLocation, GregorianDate, Hours, JulianDay};
+
+/// Jenis ufuk pengamat
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Horizon {
+    /// Ufuk laut: dip dari `GeoLocation::elevation`
+    Ocean,
+    /// Ufuk daratan: dip dari tinggi pengamat di atas daratan sekitar (meter)
+    Terrain { height: f64 },
+}
+
+/// Kondisi pengamatan untuk terbit/terbenam
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RiseSetOptions {
+    pub pressure: f64,    // Tekanan udara (mbar)
+    pub temperature: f64, // Suhu udara (°C)
+    pub horizon: Horizon,
+}
+
+impl Default for RiseSetOptions {
+    fn default() -> Self {
+        // Sama dengan `topocentric::atmospheric_refraction`: 1010 mbar, 27°C (rata-rata Indonesia)
+        Self {
+            pressure: 1010.0,
+            temperature: 27.0,
+            horizon: Horizon::Ocean,
+        }
+    }
+}
+
+impl RiseSetOptions {
+    /// Kerendahan ufuk (derajat)
+    ///
+    /// Dip = 1.76' x sqrt(h meter), sudah termasuk refraksi terestris.
+    pub fn dip(&self, location: &GeoLocation) -> f64 {
+        let height = match self.horizon {
+            Horizon::Ocean => location.elevation,
+            Horizon::Terrain { height } => height,
+        };
+        1.76 * height.max(0.0).sqrt() / 60.0
+    }
+
+    /// Tinggi geometrik pusat matahari saat piringan atas menyentuh ufuk mar'i (derajat)
+    ///
+    /// h = -(semidiameter + refraksi(-dip) + dip)
+    pub fn sun_altitude(&self, location: &GeoLocation, jd: JulianDay) -> f64 {
+        let semidiameter = 959.63 / sun::geocentric_position(jd).distance / 3600.0;
+        let dip = self.dip(location);
+        // Rumus Bennett berlaku sampai sekitar -4° (singular di -4.4°)
+        let refraction =
+            super::topocentric::refraction((-dip).max(-4.0), self.pressure, self.temperature)
+                / 60.0;
+        -(semidiameter + refraction + dip)
+    }
+}
+
+/// Waktu terbenam matahari (jam lokal), 999.0 jika matahari tidak terbenam
+pub fn sunset(location: &GeoLocation, date: &GregorianDate, options: &RiseSetOptions) -> Hours {
+    rise_set_time(location, date, options, 1.0, 18.0)
+}
+
+/// Waktu terbit matahari (jam lokal), 999.0 jika matahari tidak terbit
+pub fn sunrise(location: &GeoLocation, date: &GregorianDate, options: &RiseSetOptions) -> Hours {
+    rise_set_time(location, date, options, -1.0, 6.0)
+}
+
+/// Iterasi waktu terbit (`sign` -1) atau terbenam (`sign` 1) dengan posisi matahari pada
+/// waktu hasil iterasi sebelumnya
+fn rise_set_time(
+    location: &GeoLocation,
+    date: &GregorianDate,
+    options: &RiseSetOptions,
+    sign: f64,
+    guess: Hours,
+) -> Hours {
+    let jd = crate::calendar::gregorian_to_jd(&GregorianDate {
+        year: date.year,
+        month: date.month,
+        day: date.day.floor(),
+    });
+    let kwd = location.timezone - (location.longitude / 15.0);
+
+    let mut time = guess;
+    for _ in 0..5 {
+        let jd_event = jd + (time - location.timezone) / 24.0;
+        let altitude = options.sun_altitude(location, jd_event);
+        let ha = sun::vsop_sun_hour_angle(location.latitude, altitude, jd_event);
+        if ha == 999.0 {
+            return 999.0;
+        }
+
+        let new_time = 12.0 - sun::equation_of_time(jd_event) + sign * ha + kwd;
+        let converged = (new_time - time).abs() < 0.001; // 3.6 detik
+        time = new_time;
+        if converged {
+            break;
+        }
+    }
+    time.rem_euclid(24.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dip_and_atmosphere() {
+        let condrodipo = GeoLocation {
+            name: Some("Bukit Condrodipo".to_string()),
+            latitude: -7.1667,
+            longitude: 112.6167,
+            elevation: 100.0,
+            timezone: 7.0,
+        };
+        let date = GregorianDate {
+            year: 2026,
+            month: 2,
+            day: 18.0,
+        };
+        let minutes = |options: &RiseSetOptions| sunset(&condrodipo, &date, options) * 60.0;
+
+        // Di permukaan tanah (tanpa dip): tinggi terbenam mendekati -0°50' klasik
+        let ground = RiseSetOptions {
+            horizon: Horizon::Terrain { height: 0.0 },
+            ..RiseSetOptions::default()
+        };
+        let altitude = ground.sun_altitude(&condrodipo, 2461090.0);
+        assert!((altitude + 0.8333).abs() < 0.03);
+
+        // Default ufuk laut dari ketinggian 100 m: dip ~17.6', terbenam beberapa menit lebih lambat
+        let ocean = RiseSetOptions::default();
+        assert_eq!(ocean.horizon, Horizon::Ocean);
+        assert!((ocean.dip(&condrodipo) - 0.2933).abs() < 0.001);
+        let later = minutes(&ocean) - minutes(&ground);
+        assert!(later > 1.0 && later < 3.0, "{}", later);
+
+        // Udara panas dan bertekanan rendah membiaskan lebih sedikit: terbenam lebih awal
+        let hot = RiseSetOptions {
+            pressure: 950.0,
+            temperature: 35.0,
+            ..ground.clone()
+        };
+        assert!(minutes(&hot) < minutes(&ground));
+    }
+
+    #[test]
+    fn test_reference_times() {
+        // Acuan: algoritme Meeus bab 25 + refraksi Bennett (16.4) dengan koreksi tekanan/suhu,
+        // semidiameter 959.63"/R dan dip 1.76' x sqrt(h), 18 Februari 2026 (WIB)
+        let date = GregorianDate {
+            year: 2026,
+            month: 2,
+            day: 18.0,
+        };
+        let jakarta = GeoLocation {
+            name: Some("Jakarta".to_string()),
+            latitude: -6.1745,
+            longitude: 106.8227,
+            elevation: 0.0,
+            timezone: 7.0,
+        };
+        let condrodipo = GeoLocation {
+            name: Some("Bukit Condrodipo".to_string()),
+            latitude: -7.1667,
+            longitude: 112.6167,
+            elevation: 100.0,
+            timezone: 7.0,
+        };
+        let hot = RiseSetOptions {
+            pressure: 950.0,
+            temperature: 35.0,
+            ..RiseSetOptions::default()
+        };
+        let hms = |h: f64, m: f64, s: f64| h + m / 60.0 + s / 3600.0;
+
+        let cases = [
+            // Permukaan laut, kondisi standar: 05:58:11 / 18:15:01
+            (
+                &jakarta,
+                RiseSetOptions::default(),
+                hms(5.0, 58.0, 11.0),
+                hms(18.0, 15.0, 1.0),
+            ),
+            // Ketinggian 100 m, ufuk laut: 05:32:42 / 17:54:09
+            (
+                &condrodipo,
+                RiseSetOptions::default(),
+                hms(5.0, 32.0, 42.0),
+                hms(17.0, 54.0, 9.0),
+            ),
+            // 950 mbar, 35°C: 05:58:22 / 18:14:50
+            (&jakarta, hot, hms(5.0, 58.0, 22.0), hms(18.0, 14.0, 50.0)),
+        ];
+        for (location, options, rise, set) in cases {
+            // Toleransi 5 detik (batas konvergensi iterasi 3.6 detik)
+            let (actual_rise, actual_set) = (
+                sunrise(location, &date, &options),
+                sunset(location, &date, &options),
+            );
+            assert!(
+                (actual_rise - rise).abs() * 3600.0 < 5.0,
+                "{:?}: {}",
+                options,
+                actual_rise
+            );
+            assert!(
+                (actual_set - set).abs() * 3600.0 < 5.0,
+                "{:?}: {}",
+                options,
+                actual_set
+            );
+        }
+    }
+}
@@ -118,64 +118,19 @@ pub fn equation_of_time(jd: JulianDay) -> f64 {
 
 /// Hitung waktu sunset untuk lokasi tertentu
 ///
-/// Formula VB6-compatible exact port dari Jean Meeus / VSOP
-///
-/// FIX: Menggunakan iterasi yang lebih akurat untuk menghitung sunset
-/// dengan mempertimbangkan perubahan deklinasi matahari sepanjang hari.
+/// `rise_set::sunset` dengan `RiseSetOptions` default (1010 mbar, 27°C, dip ufuk dari
+/// `GeoLocation::elevation`), sama dengan Maghrib waktu shalat default. Untuk kondisi
+/// pengamatan lain panggil `rise_set::sunset` langsung.
 pub fn calculate_sunset(
     location: &crate::GeoLocation,
     date: &crate::GregorianDate,
 ) -> crate::Hours {
-    // Konversi tanggal ke Julian Day
-    let date_only = crate::GregorianDate {
-        year: date.year,
-        month: date.month,
-        day: date.day.floor(),
-    };
-    let jd = crate::calendar::gregorian_to_jd(&date_only);
-
-    // Altitude untuk sunset = -0.8333° (VB6 default: -0°50' = -0.8333°)
-    // Ini sudah termasuk semidiameter matahari (16') + refraksi (34')
-    let altitude_sunset = -0.8333_f64;
-
-    let kwd = location.timezone - (location.longitude / 15.0);
-
-    // Iterasi untuk mencari waktu sunset yang akurat
-    let mut sunset_local = 18.0; // Initial guess
-
-    for _ in 0..5 {
-        // 5 iterasi untuk konvergensi
-        // Convert local time to UT for JD calculation
-        let sunset_ut = sunset_local - location.timezone;
-        let jd_sunset = jd + (sunset_ut / 24.0);
-
-        // Hitung equation of time dan hour angle
-        let eot = equation_of_time(jd_sunset);
-        let ha = vsop_sun_hour_angle(location.latitude, altitude_sunset, jd_sunset);
-
-        if ha == 999.0 {
-            return 999.0; // No sunset possible
-        }
-
-        // Hitung waktu sunset baru
-        let new_sunset = 12.0 - eot + ha + kwd;
-
-        // Check konvergensi
-        if (new_sunset - sunset_local).abs() < 0.001 {
-            // 3.6 detik
-            break;
-        }
-
-        sunset_local = new_sunset;
-    }
-
-    // Normalisasi ke 0-24
-    sunset_local.rem_euclid(24.0)
+    super::rise_set::sunset(location, date, &super::rise_set::RiseSetOptions::default())
 }
 
 /// VSOP Sun Hour Angle - exact VB6 port
 /// Returns hour angle in hours, or 999.0 if not possible
-fn vsop_sun_hour_angle(latitude: f64, altitude: f64, jd: JulianDay) -> f64 {
+pub(crate) fn vsop_sun_hour_angle(latitude: f64, altitude: f64, jd: JulianDay) -> f64 {
     // VB6: VSOP_SunHourAngle
     // delta = VSOP_GeoAppSunDeclination
     // cosH = (sin(Alt) - sin(delta) * sin(Lintang)) / (cos(delta) * cos(Lintang))
@@ -387,7 +387,7 @@ pub fn sun_topocentric_ra_dec(location: &GeoLocation, jd: f64) -> (f64, f64) {
 ///
 /// # Returns
 /// Refraction correction in arcminutes (add to altitude)
-fn atmospheric_refraction(h0: f64) -> f64 {
+pub fn atmospheric_refraction(h0: f64) -> f64 {
     // VB6: Default pressure 1010 mb, temperature 10°C
     // However, VB6 RefractionApparentAltitude example uses T=27°C (Indonesia average)
     const P: f64 = 1010.0;
@@ -399,12 +399,18 @@ fn atmospheric_refraction(h0: f64) -> f64 {
     // VB6 likely clamps negative altitude to 0 for refraction calculation
     // This prevents singularity at -4.4 and gives consistent horizon refraction for set objects
     // By clamping to 0.0, we get the standard horizon refraction (~34 arcmin).
-    let h_calc = h0.max(0.0);
+    refraction(h0.max(0.0), P, T)
+}
 
+/// Refraksi (menit busur) pada tinggi `h0` untuk tekanan (mbar) dan suhu (°C) tertentu
+///
+/// Rumus yang sama dengan `atmospheric_refraction`, tanpa pembatasan tinggi negatif: dipakai
+/// untuk ufuk mar'i di bawah 0° saat pengamat berada di ketinggian.
+pub fn refraction(h0: f64, pressure: f64, temperature: f64) -> f64 {
     // VB6 formula
-    let r = 1.0 / (h_calc + 7.31 / (h_calc + 4.4)).to_radians().tan() + 0.0013515;
+    let r = 1.0 / (h0 + 7.31 / (h0 + 4.4)).to_radians().tan() + 0.0013515;
     let d_r1 = -0.06 * (14.7 * r / 60.0 + 13.0).to_radians().sin();
-    let d_r2 = (P / 1010.0) * (283.0 / (273.0 + T));
+    let d_r2 = (pressure / 1010.0) * (283.0 / (273.0 + temperature));
 
     (r + d_r1 / 60.0) * d_r2
 }
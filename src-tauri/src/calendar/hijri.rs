@@ -1,11 +1,23 @@
 //! Konversi Hijri Calendar
 //!
 //! Konversi antara Gregorian dan Hijri (Islamic) calendar
-//! Referensi: Jean Meeus, Astronomical Algorithms, Chapter 9
-//! Menggunakan Kalender Hijriah Arismetik (Tabular) dengan epoch JD 1948439.5
+//! Referensi: Jean Meeus, Astronomical Algorithms, Chapter 9;
+//! Reingold & Dershowitz, Calendrical Calculations (arithmetic Islamic calendar)
+//!
+//! Kalender Hijriah Aritmetik (Tabular): siklus 30 tahun dengan 11 tahun kabisat
+//! (355 hari). Varian dibedakan oleh pola tahun kabisat dan epoch:
+//! - Epoch astronomis (Kamis, 15 Juli 622 M, JD 1948438.5)
+//! - Epoch sipil (Jumat, 16 Juli 622 M, JD 1948439.5)
+//!
+//! Default: pola 16 (Kuwaiti) dengan epoch sipil.
+
+use serde::{Deserialize, Serialize};
 
 use crate::GregorianDate;
 
+/// Jumlah hari dalam satu siklus 30 tahun
+const DAYS_PER_CYCLE: i64 = 10631;
+
 /// Tipe data untuk Hijri calendar
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct HijriDate {
@@ -39,53 +51,178 @@ impl HijriDate {
         }
     }
 
+    /// Tanggal 1 bulan berikutnya
+    pub fn next_month(&self) -> HijriDate {
+        if self.month == 12 {
+            HijriDate::new(self.year + 1, 1, 1)
+        } else {
+            HijriDate::new(self.year, self.month + 1, 1)
+        }
+    }
+
+    /// Tanggal 1 bulan sebelumnya
+    pub fn previous_month(&self) -> HijriDate {
+        if self.month == 1 {
+            HijriDate::new(self.year - 1, 12, 1)
+        } else {
+            HijriDate::new(self.year, self.month - 1, 1)
+        }
+    }
+
     /// Konversi ke string format "DD Bulan HHHH"
     pub fn to_formatted_string(&self) -> String {
         format!("{:02} {} H{}", self.day, self.month_name(), self.year)
     }
 }
 
-/// Konversi Gregorian ke Hijri
+/// Pola tahun kabisat dalam siklus 30 tahun
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeapScheme {
+    /// Kabisat tahun ke-15 (Type I)
+    Fifteen,
+    /// Kabisat tahun ke-16 (Type II, Kuwaiti), paling umum dipakai
+    #[default]
+    Sixteen,
+    /// Fatimiyah / Bohra (Type III)
+    Fatimid,
+    /// Habash al-Hasib (Type IV)
+    HabashAlHasib,
+}
+
+impl LeapScheme {
+    /// Tahun kabisat (1-30) dalam satu siklus
+    pub fn leap_years(self) -> &'static [i64; 11] {
+        match self {
+            LeapScheme::Fifteen => &[2, 5, 7, 10, 13, 15, 18, 21, 24, 26, 29],
+            LeapScheme::Sixteen => &[2, 5, 7, 10, 13, 16, 18, 21, 24, 26, 29],
+            LeapScheme::Fatimid => &[2, 5, 8, 10, 13, 16, 19, 21, 24, 27, 29],
+            LeapScheme::HabashAlHasib => &[2, 5, 8, 11, 13, 16, 19, 21, 24, 27, 30],
+        }
+    }
+}
+
+/// Epoch kalender tabular (awal 1 Muharram 1 H)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TabularEpoch {
+    /// Kamis, 15 Juli 622 M (Julian)
+    Astronomical,
+    /// Jumat, 16 Juli 622 M (Julian)
+    #[default]
+    Civil,
+}
+
+impl TabularEpoch {
+    /// JD tengah malam awal 1 Muharram 1 H
+    pub fn jd(self) -> f64 {
+        match self {
+            TabularEpoch::Astronomical => 1948438.5,
+            TabularEpoch::Civil => 1948439.5,
+        }
+    }
+}
+
+/// Varian kalender tabular: pola kabisat + epoch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TabularVariant {
+    #[serde(default)]
+    pub leap: LeapScheme,
+    #[serde(default)]
+    pub epoch: TabularEpoch,
+}
+
+impl TabularVariant {
+    pub fn new(leap: LeapScheme, epoch: TabularEpoch) -> Self {
+        TabularVariant { leap, epoch }
+    }
+
+    /// Cek tahun kabisat (355 hari)
+    pub fn is_leap_year(&self, year: i32) -> bool {
+        let position = (year as i64 - 1).rem_euclid(30) + 1;
+        self.leap.leap_years().contains(&position)
+    }
+
+    /// Jumlah hari dari epoch sampai 1 Muharram `year`
+    fn days_before_year(&self, year: i32) -> i64 {
+        let elapsed = year as i64 - 1;
+        let cycles = elapsed.div_euclid(30);
+        let remainder = elapsed.rem_euclid(30);
+        let leaps = self
+            .leap
+            .leap_years()
+            .iter()
+            .filter(|&&leap| leap <= remainder)
+            .count() as i64;
+        cycles * DAYS_PER_CYCLE + 354 * remainder + leaps
+    }
+
+    /// Konversi Hijri ke Julian Day (tengah malam awal hari)
+    pub fn hijri_to_jd(&self, hijri: &HijriDate) -> f64 {
+        let m = hijri.month as i64;
+        // Bulan ganjil 30 hari, genap 29 hari
+        let days = self.days_before_year(hijri.year) + 29 * (m - 1) + m / 2 + hijri.day as i64 - 1;
+        self.epoch.jd() + days as f64
+    }
+
+    /// Konversi Julian Day ke Hijri
+    pub fn jd_to_hijri(&self, jd: f64) -> HijriDate {
+        // Hari sipil dihitung dari tengah malam
+        let days = ((jd + 0.5).floor() - (self.epoch.jd() + 0.5)) as i64;
+
+        let mut year = (30 * days.div_euclid(DAYS_PER_CYCLE) + 1) as i32;
+        let mut remainder = days - self.days_before_year(year);
+        loop {
+            let length = if self.is_leap_year(year) { 355 } else { 354 };
+            if remainder < length {
+                break;
+            }
+            remainder -= length;
+            year += 1;
+        }
+
+        let mut month = 1;
+        while month < 12 && remainder >= 29 * month + (month + 1) / 2 {
+            month += 1;
+        }
+        let day = remainder - (29 * (month - 1) + month / 2) + 1;
+
+        HijriDate {
+            year,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+
+    /// Konversi Gregorian ke Hijri
+    pub fn gregorian_to_hijri(&self, gregorian: &GregorianDate) -> HijriDate {
+        self.jd_to_hijri(crate::calendar::gregorian_to_jd(gregorian))
+    }
+
+    /// Konversi Hijri ke Gregorian
+    pub fn hijri_to_gregorian(&self, hijri: &HijriDate) -> GregorianDate {
+        crate::calendar::jd_to_gregorian(self.hijri_to_jd(hijri))
+    }
+}
+
+/// Konversi Gregorian ke Hijri (varian default)
 pub fn gregorian_to_hijri(gregorian: &GregorianDate) -> HijriDate {
-    let jd = crate::calendar::gregorian_to_jd(gregorian);
-    jd_to_hijri(jd)
+    TabularVariant::default().gregorian_to_hijri(gregorian)
 }
 
-/// Konversi Hijri ke Gregorian
+/// Konversi Hijri ke Gregorian (varian default)
 pub fn hijri_to_gregorian(hijri: &HijriDate) -> GregorianDate {
-    let jd = hijri_to_jd(hijri);
-    crate::calendar::jd_to_gregorian(jd)
+    TabularVariant::default().hijri_to_gregorian(hijri)
 }
 
-/// Konversi Julian Day ke Hijri
-/// Berdasarkan algoritma Ku-ring-gai (tabular)
+/// Konversi Julian Day ke Hijri (varian default)
 pub fn jd_to_hijri(jd: f64) -> HijriDate {
-    let jd_int = jd.floor() + 0.5;
-    let l = jd_int as i64 - 1948440 + 10632;
-    let n = (l - 1) / 10631;
-    let l = l - 10631 * n + 354;
-    let j = ((10985 - l) / 5316) * ((50 * l) / 17719) + (l / 5616) * ((43 * l) / 15238);
-    let l = l - ((30 - j) / 15) * ((17719 * j) / 50) - (j / 16) * ((15238 * j) / 43) + 29;
-
-    let month = (24 * l) / 709;
-    let day = l - (709 * month) / 24;
-    let year = 30 * n + j - 30;
-
-    HijriDate {
-        year: year as i32,
-        month: month as u8,
-        day: day as u8,
-    }
+    TabularVariant::default().jd_to_hijri(jd)
 }
 
-/// Konversi Hijri ke Julian Day
+/// Konversi Hijri ke Julian Day (varian default)
 pub fn hijri_to_jd(hijri: &HijriDate) -> f64 {
-    let y = hijri.year as i64;
-    let m = hijri.month as i64;
-    let d = hijri.day as i64;
-
-    let jd = ((11 * y + 3) / 30) + 354 * y + 30 * m - (m - 1) / 2 + d + 1948440 - 385;
-    jd as f64 - 0.5
+    TabularVariant::default().hijri_to_jd(hijri)
 }
 
 #[cfg(test)]
@@ -103,7 +240,79 @@ mod tests {
         let hijri = gregorian_to_hijri(&jan20);
         println!("2026-01-20 -> {} {}-{}", hijri.year, hijri.month, hijri.day);
 
-        // Tergantung epoch dan varian, bisa 1 Sya'ban atau 30 Rajab
-        assert!(hijri.year == 1447);
+        // Varian default (Kuwaiti, epoch sipil): 1 Sya'ban; epoch astronomis: 2 Sya'ban
+        assert_eq!(hijri, HijriDate::new(1447, 8, 1));
+        let astronomical = TabularVariant::new(LeapScheme::Sixteen, TabularEpoch::Astronomical);
+        assert_eq!(
+            astronomical.gregorian_to_hijri(&jan20),
+            HijriDate::new(1447, 8, 2)
+        );
+    }
+
+    #[test]
+    fn test_calendrical_calculations_table() {
+        // Reingold & Dershowitz, Calendrical Calculations, Appendix C (arithmetic Islamic,
+        // tanggal Gregorian proleptik)
+        let samples = [
+            ((-586, 7, 24), (-1245, 12, 9)),
+            ((-168, 12, 5), (-813, 2, 23)),
+            ((70, 9, 24), (-568, 4, 1)),
+            ((576, 5, 20), (-47, 6, 3)),
+            ((694, 11, 10), (75, 7, 13)),
+            ((1096, 5, 24), (489, 5, 22)),
+            ((1553, 9, 19), (960, 9, 30)),
+            ((1648, 6, 10), (1058, 5, 18)),
+            ((1819, 8, 2), (1234, 10, 10)),
+            ((1941, 9, 29), (1360, 9, 8)),
+            ((1992, 3, 17), (1412, 9, 13)),
+            ((1996, 2, 25), (1416, 10, 5)),
+            ((2038, 11, 10), (1460, 10, 12)),
+            ((2094, 7, 18), (1518, 3, 5)),
+        ];
+        for ((g_year, g_month, g_day), (h_year, h_month, h_day)) in samples {
+            let gregorian = GregorianDate {
+                year: g_year,
+                month: g_month,
+                day: g_day as f64,
+            };
+            let hijri = HijriDate::new(h_year, h_month, h_day);
+            assert_eq!(gregorian_to_hijri(&gregorian), hijri, "{:?}", gregorian);
+
+            // Tabel memakai Gregorian proleptik; bandingkan lewat JD
+            assert_eq!(
+                hijri_to_jd(&hijri),
+                crate::calendar::gregorian_to_jd(&gregorian)
+            );
+        }
+    }
+
+    #[test]
+    fn test_leap_schemes_and_bohra() {
+        // Setiap pola memiliki 11 tahun kabisat: 10631 hari per siklus
+        for leap in [
+            LeapScheme::Fifteen,
+            LeapScheme::Sixteen,
+            LeapScheme::Fatimid,
+            LeapScheme::HabashAlHasib,
+        ] {
+            let variant = TabularVariant::new(leap, TabularEpoch::Civil);
+            let start = variant.hijri_to_jd(&HijriDate::new(1441, 1, 1));
+            let end = variant.hijri_to_jd(&HijriDate::new(1471, 1, 1));
+            assert_eq!(end - start, 10631.0);
+        }
+
+        // Tahun ke-15 vs ke-16 dalam siklus (1425 H = tahun ke-15)
+        let fifteen = TabularVariant::new(LeapScheme::Fifteen, TabularEpoch::Civil);
+        assert!(fifteen.is_leap_year(1425) && !fifteen.is_leap_year(1426));
+        assert!(!TabularVariant::default().is_leap_year(1425));
+        assert!(TabularVariant::default().is_leap_year(1426));
+
+        // Kalender Dawoodi Bohra (Fatimiyah, epoch astronomis):
+        // 1 Ramadan 1445 = 10 Maret 2024, 1 Ramadan 1446 = 28 Februari 2025
+        let bohra = TabularVariant::new(LeapScheme::Fatimid, TabularEpoch::Astronomical);
+        let ramadan = bohra.hijri_to_gregorian(&HijriDate::new(1445, 9, 1));
+        assert_eq!((ramadan.year, ramadan.month, ramadan.day), (2024, 3, 10.0));
+        let ramadan = bohra.hijri_to_gregorian(&HijriDate::new(1446, 9, 1));
+        assert_eq!((ramadan.year, ramadan.month, ramadan.day), (2025, 2, 28.0));
     }
 }
@@ -0,0 +1,282 @@
+//! Kalender Hijriah hisab (berbasis kriteria visibilitas)
+//!
+//! Berbeda dengan kalender tabular (`calendar::hijri`), awal bulan ditentukan dengan
+//! mengevaluasi kriteria (MABIMS, Wujudul Hilal, KHGT, dll.) pada maghrib tanggal 29
+//! di lokasi acuan. Jika kriteria terpenuhi di salah satu lokasi acuan, bulan berumur
+//! 29 hari; jika tidak, bulan digenapkan 30 hari (istikmal).
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use super::HijriDate;
+use crate::{GeoLocation, GregorianDate};
+
+/// Batas rentang tahun satu kali generate
+pub const MAX_HISAB_YEARS: i32 = 50;
+
+/// Jumlah kalender hisab yang disimpan untuk konversi berulang
+const MAX_CACHED_CALENDARS: usize = 16;
+
+/// Kalender hisab yang sudah dihitung, kunci = (kriteria, lokasi acuan, tahun awal, jumlah tahun)
+static CALENDAR_CACHE: Mutex<Vec<(String, HisabCalendar)>> = Mutex::new(Vec::new());
+
+/// Pilihan mode hisab: kriteria + lokasi acuan (satu titik atau beberapa titik wilayah)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HisabOptions {
+    pub criteria: String,
+    pub locations: Vec<GeoLocation>,
+}
+
+/// Satu bulan Hijriah hasil hisab
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HisabMonth {
+    pub year: i32,
+    pub month: u8,
+    pub start_jd: f64, // JD tengah malam (00:00) tanggal 1 sesuai kalender sipil lokal
+    pub start: GregorianDate,
+    pub length: u8,                 // 29 atau 30
+    pub conjunction_jd: f64,        // Ijtimak akhir bulan (UT)
+    pub decided_by: Option<String>, // Lokasi acuan yang memenuhi kriteria pada maghrib tgl 29
+}
+
+/// Kalender Hijriah hisab untuk rentang beberapa tahun
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HisabCalendar {
+    pub criteria: String,
+    pub months: Vec<HisabMonth>,
+}
+
+impl HisabCalendar {
+    /// Konversi Gregorian ke Hijriah; None jika tanggal di luar rentang kalender
+    pub fn gregorian_to_hijri(&self, date: &GregorianDate) -> Option<HijriDate> {
+        let jd = day_jd(date);
+        self.months
+            .iter()
+            .find(|m| jd >= m.start_jd && jd < m.start_jd + m.length as f64)
+            .map(|m| HijriDate::new(m.year, m.month, (jd - m.start_jd) as u8 + 1))
+    }
+
+    /// Konversi Hijriah ke Gregorian; None jika bulan di luar rentang atau hari > umur bulan
+    pub fn hijri_to_gregorian(&self, hijri: &HijriDate) -> Option<GregorianDate> {
+        self.months
+            .iter()
+            .find(|m| m.year == hijri.year && m.month == hijri.month)
+            .filter(|m| hijri.day >= 1 && hijri.day <= m.length)
+            .map(|m| super::jd_to_gregorian(m.start_jd + (hijri.day - 1) as f64))
+    }
+}
+
+/// Generate kalender hisab mulai 1 Muharram `start_year` selama `years` tahun
+///
+/// Awal rantai diambil dari ijtimak terdekat dengan 1 Muharram tabular, dievaluasi pada maghrib
+/// pertama setelah ijtimak di lokasi acuan utama. Setelah itu setiap bulan ditentukan dari
+/// maghrib tanggal 29.
+pub fn generate_hisab_calendar(
+    options: &HisabOptions,
+    start_year: i32,
+    years: i32,
+) -> Result<HisabCalendar, String> {
+    let criterion = crate::criteria::find_criterion(&options.criteria)
+        .ok_or_else(|| format!("Unknown criteria: {}", options.criteria))?;
+    if options.locations.is_empty() {
+        return Err("At least one reference location is required".to_string());
+    }
+    if !(1..=MAX_HISAB_YEARS).contains(&years) {
+        return Err(format!("Hisab span must be 1-{} years", MAX_HISAB_YEARS));
+    }
+
+    // Awal rantai: maghrib pertama setelah ijtimak menjelang 1 Muharram tabular. Ijtimak
+    // setelah maghrib membuat malam pertama jatuh esok harinya, sehingga 1 Muharram bisa
+    // jatuh 3 hari setelah tanggal ijtimak (istikmal).
+    let tabular_start = super::hijri_to_gregorian(&HijriDate::new(start_year, 1, 1));
+    let seed = crate::astronomy::conjunction::find_conjunction(&tabular_start);
+    let first_evening = day_jd(&crate::map::first_evening_after(
+        &options.locations[0],
+        seed.jd_utc,
+        &crate::astronomy::rise_set::RiseSetOptions::default(),
+    ));
+    let mut start_jd = match first_visible(criterion.as_ref(), options, first_evening, seed.jd_utc)
+    {
+        Some(_) => first_evening + 1.0,
+        None => first_evening + 2.0,
+    };
+
+    let mut months = Vec::with_capacity((years * 12) as usize);
+    for index in 0..years * 12 {
+        // Ijtimak akhir bulan: dicari setelah pertengahan bulan
+        let conjunction = crate::astronomy::conjunction::find_conjunction_after(
+            &super::jd_to_gregorian(start_jd + 14.0),
+        );
+
+        let day29 = start_jd + 28.0;
+        let decided_by = first_visible(criterion.as_ref(), options, day29, conjunction.jd_utc);
+        let length = if decided_by.is_some() { 29 } else { 30 };
+
+        months.push(HisabMonth {
+            year: start_year + index / 12,
+            month: (index % 12) as u8 + 1,
+            start_jd,
+            start: super::jd_to_gregorian(start_jd),
+            length,
+            conjunction_jd: conjunction.jd_utc,
+            decided_by,
+        });
+        start_jd += length as f64;
+    }
+
+    Ok(HisabCalendar {
+        criteria: criterion.id().to_string(),
+        months,
+    })
+}
+
+/// Kalender hisab yang mencakup tahun Hijriah `year` (dimulai setahun sebelumnya agar
+/// rantai bulan sudah stabil)
+///
+/// Hasil untuk kriteria bawaan di-cache, sehingga konversi tanggal berulang tidak menghitung
+/// ulang 36 bulan. Kriteria kustom tidak di-cache karena aturannya bisa diganti dengan id sama.
+pub fn hisab_calendar_around(options: &HisabOptions, year: i32) -> Result<HisabCalendar, String> {
+    let (start_year, years) = (year - 1, 3);
+    let builtin = crate::criteria::registry::builtin_criteria()
+        .iter()
+        .any(|c| c.id().eq_ignore_ascii_case(&options.criteria));
+    if !builtin {
+        return generate_hisab_calendar(options, start_year, years);
+    }
+
+    let key = serde_json::to_string(&(
+        options.criteria.to_ascii_uppercase(),
+        &options.locations,
+        start_year,
+        years,
+    ))
+    .map_err(|e| e.to_string())?;
+    if let Ok(cache) = CALENDAR_CACHE.lock() {
+        if let Some((_, calendar)) = cache.iter().find(|(k, _)| *k == key) {
+            return Ok(calendar.clone());
+        }
+    }
+
+    let calendar = generate_hisab_calendar(options, start_year, years)?;
+    if let Ok(mut cache) = CALENDAR_CACHE.lock() {
+        if cache.len() >= MAX_CACHED_CALENDARS {
+            cache.remove(0);
+        }
+        cache.push((key, calendar.clone()));
+    }
+    Ok(calendar)
+}
+
+/// Nama lokasi acuan pertama yang memenuhi kriteria pada maghrib tanggal `day_jd`
+fn first_visible(
+    criterion: &dyn crate::criteria::Criterion,
+    options: &HisabOptions,
+    day_jd: f64,
+    conjunction_jd: f64,
+) -> Option<String> {
+    let date = super::jd_to_gregorian(day_jd);
+    options
+        .locations
+        .iter()
+        .find(|location| {
+            let params = crate::criteria::HilalParameters::new(location, &date, conjunction_jd);
+            params.has_sunset() && criterion.evaluate(&params).is_visible
+        })
+        .map(|location| {
+            location
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("{:.4}, {:.4}", location.latitude, location.longitude))
+        })
+}
+
+/// JD tengah malam untuk tanggal (bagian pecahan hari diabaikan)
+fn day_jd(date: &GregorianDate) -> f64 {
+    super::gregorian_to_jd(&GregorianDate {
+        year: date.year,
+        month: date.month,
+        day: date.day.floor(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jakarta() -> GeoLocation {
+        GeoLocation {
+            name: Some("Jakarta".to_string()),
+            latitude: -6.2,
+            longitude: 106.8167,
+            elevation: 0.0,
+            timezone: 7.0,
+        }
+    }
+
+    #[test]
+    fn test_mabims_1447_month_lengths() {
+        let options = HisabOptions {
+            criteria: "MABIMS".to_string(),
+            locations: vec![jakarta()],
+        };
+        let calendar = hisab_calendar_around(&options, 1447).unwrap();
+        assert_eq!(calendar.months.len(), 36);
+        assert!(calendar
+            .months
+            .iter()
+            .all(|m| m.length == 29 || m.length == 30));
+
+        // Bulan bersambung tanpa celah
+        for pair in calendar.months.windows(2) {
+            assert_eq!(pair[0].start_jd + pair[0].length as f64, pair[1].start_jd);
+        }
+
+        // Ijtimak 17 Feb 2026: hilal di bawah 3° -> 1 Ramadan 1447 = 19 Feb 2026
+        let ramadan = calendar
+            .hijri_to_gregorian(&HijriDate::new(1447, 9, 1))
+            .unwrap();
+        assert_eq!((ramadan.year, ramadan.month, ramadan.day), (2026, 2, 19.0));
+
+        let back = calendar.gregorian_to_hijri(&ramadan).unwrap();
+        assert_eq!(back, HijriDate::new(1447, 9, 1));
+    }
+
+    #[test]
+    fn test_seed_waits_for_first_evening_after_conjunction() {
+        // Ijtimak 1 Des 2043 17:39 waktu Istanbul, setelah maghrib: malam pertama 2 Des dan
+        // hilal belum memenuhi MABIMS, sehingga 1 Muharram 1466 = 4 Des (ijtimak + 3 hari)
+        let istanbul = GeoLocation {
+            name: Some("Istanbul".to_string()),
+            latitude: 41.0,
+            longitude: 29.0,
+            elevation: 0.0,
+            timezone: 3.0,
+        };
+        let options = HisabOptions {
+            criteria: "MABIMS".to_string(),
+            locations: vec![istanbul],
+        };
+        let calendar = generate_hisab_calendar(&options, 1466, 1).unwrap();
+        let start = &calendar.months[0].start;
+        assert_eq!((start.year, start.month, start.day), (2043, 12, 4.0));
+
+        // Hasil cache sama dengan perhitungan langsung
+        let cached = hisab_calendar_around(&options, 1467).unwrap();
+        let again = hisab_calendar_around(&options, 1467).unwrap();
+        let direct = generate_hisab_calendar(&options, 1466, 3).unwrap();
+        for ((a, b), c) in cached.months.iter().zip(&again.months).zip(&direct.months) {
+            assert_eq!(a.start_jd, c.start_jd);
+            assert_eq!(b.start_jd, c.start_jd);
+        }
+    }
+
+    #[test]
+    fn test_hisab_requires_location() {
+        let options = HisabOptions {
+            criteria: "MABIMS".to_string(),
+            locations: Vec::new(),
+        };
+        assert!(generate_hisab_calendar(&options, 1447, 1).is_err());
+    }
+}
@@ -0,0 +1,262 @@
+//! Hari besar dan hari khusus Islam
+//!
+//! Tanggal Hijriah setiap hari besar tetap, tetapi tanggal Gregoriannya bergantung pada
+//! jenis kalender (tabular, hisab kriteria, Umm al-Qura, KHGT). Untuk satu tahun Hijriah,
+//! setiap hari besar dihitung pada semua jenis kalender yang diminta lalu dikelompokkan
+//! per tanggal, sehingga perbedaan ("Muhammadiyah X, pemerintah Y") terlihat berdampingan.
+
+use serde::{Deserialize, Serialize};
+
+use super::{HijriCalendarKind, HijriDate};
+use crate::GregorianDate;
+
+/// Lama hari besar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HolidaySpan {
+    Days(u8),
+    WholeMonth,
+}
+
+/// Definisi hari besar pada tanggal Hijriah tetap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct HolidayDefinition {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub months: &'static [u8], // Bulan Hijriah tempat hari besar berlaku
+    pub day: u8,               // Tanggal mulai
+    pub span: HolidaySpan,
+}
+
+/// Daftar hari besar dan hari khusus
+///
+/// Ayyamul bidh (13-15) tidak dicantumkan di Dzulhijjah karena 13 Dzulhijjah adalah hari
+/// tasyrik (haram berpuasa).
+pub const HOLIDAYS: &[HolidayDefinition] = &[
+    HolidayDefinition {
+        id: "new_year",
+        name: "Tahun Baru Hijriah",
+        months: &[1],
+        day: 1,
+        span: HolidaySpan::Days(1),
+    },
+    HolidayDefinition {
+        id: "ashura",
+        name: "Asyura",
+        months: &[1],
+        day: 10,
+        span: HolidaySpan::Days(1),
+    },
+    HolidayDefinition {
+        id: "mawlid",
+        name: "Maulid Nabi",
+        months: &[3],
+        day: 12,
+        span: HolidaySpan::Days(1),
+    },
+    HolidayDefinition {
+        id: "isra_miraj",
+        name: "Isra Mi'raj",
+        months: &[7],
+        day: 27,
+        span: HolidaySpan::Days(1),
+    },
+    HolidayDefinition {
+        id: "nisfu_syaban",
+        name: "Nisfu Sya'ban",
+        months: &[8],
+        day: 15,
+        span: HolidaySpan::Days(1),
+    },
+    HolidayDefinition {
+        id: "ramadan",
+        name: "Ramadan",
+        months: &[9],
+        day: 1,
+        span: HolidaySpan::WholeMonth,
+    },
+    HolidayDefinition {
+        id: "eid_al_fitr",
+        name: "Idul Fitri",
+        months: &[10],
+        day: 1,
+        span: HolidaySpan::Days(1),
+    },
+    HolidayDefinition {
+        id: "arafah",
+        name: "Hari Arafah",
+        months: &[12],
+        day: 9,
+        span: HolidaySpan::Days(1),
+    },
+    HolidayDefinition {
+        id: "eid_al_adha",
+        name: "Idul Adha",
+        months: &[12],
+        day: 10,
+        span: HolidaySpan::Days(1),
+    },
+    HolidayDefinition {
+        id: "tashriq",
+        name: "Hari Tasyrik",
+        months: &[12],
+        day: 11,
+        span: HolidaySpan::Days(3),
+    },
+    HolidayDefinition {
+        id: "ayyamul_bidh",
+        name: "Ayyamul Bidh",
+        months: &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+        day: 13,
+        span: HolidaySpan::Days(3),
+    },
+];
+
+/// Rentang tanggal Gregorian satu hari besar, beserta jenis kalender yang menghasilkannya
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HolidayObservance {
+    pub start_jd: f64, // JD tengah malam (00:00) hari pertama
+    pub start: GregorianDate,
+    pub end: GregorianDate, // Hari terakhir (inklusif)
+    pub days: u8,
+    pub calendars: Vec<String>, // Label jenis kalender (mis. "MABIMS (Jakarta)", "KHGT")
+}
+
+/// Satu hari besar dalam tahun Hijriah
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Holiday {
+    pub id: String,
+    pub name: String,
+    pub hijri_year: i32,
+    pub hijri_month: u8,
+    pub hijri_day: u8,
+    pub observances: Vec<HolidayObservance>, // Satu entri per tanggal berbeda
+    pub agreed: bool,                        // true jika semua jenis kalender sepakat
+}
+
+/// Hari besar yang berlaku pada tanggal Hijriah (yang sebulan penuh hanya di hari pertama)
+pub fn holidays_on(hijri: &HijriDate) -> Vec<&'static HolidayDefinition> {
+    HOLIDAYS
+        .iter()
+        .filter(|holiday| holiday.months.contains(&hijri.month))
+        .filter(|holiday| match holiday.span {
+            HolidaySpan::Days(days) => hijri.day >= holiday.day && hijri.day < holiday.day + days,
+            HolidaySpan::WholeMonth => hijri.day == holiday.day,
+        })
+        .collect()
+}
+
+/// Hitung semua hari besar tahun Hijriah `hijri_year` pada setiap jenis kalender
+pub fn islamic_holidays(
+    hijri_year: i32,
+    calendars: &[HijriCalendarKind],
+) -> Result<Vec<Holiday>, String> {
+    if calendars.is_empty() {
+        return Err("At least one calendar mode is required".to_string());
+    }
+
+    let month_starts = calendars
+        .iter()
+        .map(|calendar| Ok((calendar.label(), calendar.month_starts(hijri_year)?)))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut holidays = Vec::new();
+    for definition in HOLIDAYS {
+        for &month in definition.months {
+            let mut observances: Vec<HolidayObservance> = Vec::new();
+            for (label, starts) in &month_starts {
+                let index = month as usize - 1;
+                let month_start = starts[index];
+                let days = match definition.span {
+                    HolidaySpan::Days(days) => days,
+                    HolidaySpan::WholeMonth => (starts[index + 1] - month_start).round() as u8,
+                };
+                let start_jd = month_start + (definition.day - 1) as f64;
+
+                match observances
+                    .iter_mut()
+                    .find(|o| o.start_jd == start_jd && o.days == days)
+                {
+                    Some(observance) => observance.calendars.push(label.clone()),
+                    None => observances.push(HolidayObservance {
+                        start_jd,
+                        start: super::jd_to_gregorian(start_jd),
+                        end: super::jd_to_gregorian(start_jd + (days - 1) as f64),
+                        days,
+                        calendars: vec![label.clone()],
+                    }),
+                }
+            }
+            observances.sort_by(|a, b| a.start_jd.total_cmp(&b.start_jd));
+
+            holidays.push(Holiday {
+                id: definition.id.to_string(),
+                name: definition.name.to_string(),
+                hijri_year,
+                hijri_month: month,
+                hijri_day: definition.day,
+                agreed: observances.len() == 1,
+                observances,
+            });
+        }
+    }
+
+    holidays.sort_by_key(|holiday| (holiday.hijri_month, holiday.hijri_day));
+    Ok(holidays)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::hisab::HisabOptions;
+    use crate::GeoLocation;
+
+    #[test]
+    fn test_holidays_1447_side_by_side() {
+        let mabims = HijriCalendarKind::Hisab(HisabOptions {
+            criteria: "MABIMS".to_string(),
+            locations: vec![GeoLocation {
+                name: Some("Jakarta".to_string()),
+                latitude: -6.2,
+                longitude: 106.8167,
+                elevation: 0.0,
+                timezone: 7.0,
+            }],
+        });
+        let calendars = [HijriCalendarKind::default(), mabims];
+        let holidays = islamic_holidays(1447, &calendars).unwrap();
+
+        // 10 hari besar + ayyamul bidh di 11 bulan
+        assert_eq!(holidays.len(), 21);
+        for holiday in &holidays {
+            let total: usize = holiday.observances.iter().map(|o| o.calendars.len()).sum();
+            assert_eq!(total, calendars.len());
+            assert_eq!(holiday.agreed, holiday.observances.len() == 1);
+        }
+
+        // Ramadan: tabular 18 Feb 2026, MABIMS 19 Feb 2026
+        let ramadan = holidays.iter().find(|h| h.id == "ramadan").unwrap();
+        assert!(!ramadan.agreed);
+        let mabims = ramadan
+            .observances
+            .iter()
+            .find(|o| o.calendars.contains(&"MABIMS (Jakarta)".to_string()))
+            .unwrap();
+        assert_eq!((mabims.start.month, mabims.start.day), (2, 19.0));
+        assert!(mabims.days == 29 || mabims.days == 30);
+    }
+
+    #[test]
+    fn test_holidays_on() {
+        let ids =
+            |hijri: HijriDate| -> Vec<&str> { holidays_on(&hijri).iter().map(|h| h.id).collect() };
+        assert_eq!(ids(HijriDate::new(1447, 12, 10)), vec!["eid_al_adha"]);
+        assert_eq!(ids(HijriDate::new(1447, 12, 13)), vec!["tashriq"]);
+        assert_eq!(
+            ids(HijriDate::new(1447, 8, 15)),
+            vec!["nisfu_syaban", "ayyamul_bidh"]
+        );
+        assert_eq!(ids(HijriDate::new(1447, 9, 1)), vec!["ramadan"]);
+        assert!(ids(HijriDate::new(1447, 9, 2)).is_empty());
+    }
+}
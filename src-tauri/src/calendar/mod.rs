@@ -2,9 +2,248 @@
 
 pub mod gregorian;
 pub mod hijri;
+pub mod hisab;
+pub mod holidays;
 pub mod javanese;
 pub mod julian_day;
+pub mod month_grid;
+pub mod prayer_timetable;
+pub mod umm_al_qura;
 
 // Re-export
-pub use hijri::{gregorian_to_hijri, hijri_to_gregorian, HijriDate};
+pub use hijri::{
+    gregorian_to_hijri, hijri_to_gregorian, HijriDate, LeapScheme, TabularEpoch, TabularVariant,
+};
 pub use julian_day::*;
+
+use std::sync::Mutex;
+
+use crate::GregorianDate;
+use hisab::HisabOptions;
+
+/// Jumlah awal bulan KHGT yang disimpan (sekitar 20 tahun)
+const MAX_CACHED_KHGT_MONTHS: usize = 240;
+
+/// Awal bulan KHGT (grid global standar) yang sudah dihitung, kunci = (tahun H, bulan H)
+static KHGT_START_CACHE: Mutex<Vec<((i32, u8), f64)>> = Mutex::new(Vec::new());
+
+/// Jenis kalender Hijriah untuk konversi tanggal
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HijriCalendarKind {
+    /// Kalender aritmetik (tabular) dengan pola kabisat dan epoch tertentu
+    Tabular(TabularVariant),
+    /// Kalender resmi Arab Saudi (tabel + aturan Makkah)
+    UmmAlQura,
+    /// Kalender hisab berdasarkan kriteria di lokasi acuan
+    Hisab(HisabOptions),
+    /// Kalender Hijriah Global Tunggal (satu tanggal untuk seluruh dunia)
+    ///
+    /// Setiap awal bulan dievaluasi dengan menyapu grid global, sehingga konversi jauh lebih
+    /// lambat dibanding jenis kalender lain.
+    Khgt,
+}
+
+impl Default for HijriCalendarKind {
+    fn default() -> Self {
+        HijriCalendarKind::Tabular(TabularVariant::default())
+    }
+}
+
+impl HijriCalendarKind {
+    /// Konversi Gregorian ke Hijriah sesuai jenis kalender
+    pub fn gregorian_to_hijri(&self, date: &GregorianDate) -> Result<HijriDate, String> {
+        match self {
+            Self::Tabular(variant) => Ok(variant.gregorian_to_hijri(date)),
+            Self::UmmAlQura => umm_al_qura::gregorian_to_hijri(date),
+            Self::Hisab(options) => {
+                let year = gregorian_to_hijri(date).year;
+                hisab::hisab_calendar_around(options, year)?
+                    .gregorian_to_hijri(date)
+                    .ok_or_else(|| "Date is outside the hisab calendar range".to_string())
+            }
+            Self::Khgt => {
+                let jd = gregorian_to_jd(&GregorianDate {
+                    year: date.year,
+                    month: date.month,
+                    day: date.day.floor(),
+                });
+                // Bulan tabular berselisih paling banyak satu bulan dari KHGT
+                let mut month = gregorian_to_hijri(date);
+                let mut start = khgt_start_jd(&month)?;
+                if jd < start {
+                    month = month.previous_month();
+                    start = khgt_start_jd(&month)?;
+                } else {
+                    let next_start = khgt_start_jd(&month.next_month())?;
+                    if jd >= next_start {
+                        month = month.next_month();
+                        start = next_start;
+                    }
+                }
+                Ok(HijriDate::new(
+                    month.year,
+                    month.month,
+                    (jd - start) as u8 + 1,
+                ))
+            }
+        }
+    }
+
+    /// Konversi Hijriah ke Gregorian sesuai jenis kalender
+    pub fn hijri_to_gregorian(&self, hijri: &HijriDate) -> Result<GregorianDate, String> {
+        match self {
+            Self::Tabular(variant) => Ok(variant.hijri_to_gregorian(hijri)),
+            Self::UmmAlQura => umm_al_qura::hijri_to_gregorian(hijri),
+            Self::Hisab(options) => hisab::hisab_calendar_around(options, hijri.year)?
+                .hijri_to_gregorian(hijri)
+                .ok_or_else(|| format!("Day {} does not exist in this hisab month", hijri.day)),
+            Self::Khgt => {
+                let start = khgt_start_jd(hijri)?;
+                if hijri.day == 30 && khgt_start_jd(&hijri.next_month())? - start < 30.0 {
+                    return Err("Day 30 does not exist in this KHGT month".to_string());
+                }
+                Ok(jd_to_gregorian(start + (hijri.day - 1) as f64))
+            }
+        }
+    }
+
+    /// JD (00:00) awal 13 bulan: 1 Muharram `year` sampai 1 Muharram `year + 1`
+    pub fn month_starts(&self, year: i32) -> Result<Vec<f64>, String> {
+        match self {
+            Self::Hisab(options) => {
+                let calendar = hisab::hisab_calendar_around(options, year)?;
+                Ok(calendar
+                    .months
+                    .iter()
+                    .filter(|m| m.year == year || (m.year == year + 1 && m.month == 1))
+                    .map(|m| m.start_jd)
+                    .collect())
+            }
+            Self::Khgt => (0..13)
+                .map(|index| {
+                    let first = HijriDate::new(year + index / 12, (index % 12) as u8 + 1, 1);
+                    khgt_start_jd(&first)
+                })
+                .collect(),
+            _ => (0..13)
+                .map(|index| {
+                    let first = HijriDate::new(year + index / 12, (index % 12) as u8 + 1, 1);
+                    Ok(gregorian_to_jd(&self.hijri_to_gregorian(&first)?))
+                })
+                .collect(),
+        }
+    }
+
+    /// Nama singkat jenis kalender untuk laporan (mis. "MABIMS (Jakarta)")
+    pub fn label(&self) -> String {
+        match self {
+            Self::Tabular(variant) if *variant == TabularVariant::default() => {
+                "Tabular".to_string()
+            }
+            Self::Tabular(variant) => format!("Tabular ({:?}, {:?})", variant.leap, variant.epoch),
+            Self::UmmAlQura => "Umm al-Qura".to_string(),
+            Self::Hisab(options) => match options.locations.first().and_then(|l| l.name.as_ref()) {
+                Some(name) => format!("{} ({})", options.criteria, name),
+                None => options.criteria.clone(),
+            },
+            Self::Khgt => "KHGT".to_string(),
+        }
+    }
+
+    /// Tanggal Hijriah untuk `days` hari berurutan mulai `start`
+    ///
+    /// Konversi penuh hanya dilakukan di awal rentang dan di setiap pergantian bulan,
+    /// sehingga mode hisab tidak perlu menghitung ulang kalender untuk setiap hari.
+    pub fn hijri_dates(&self, start: &GregorianDate, days: u32) -> Result<Vec<HijriDate>, String> {
+        let start_jd = gregorian_to_jd(start);
+        let mut current = self.gregorian_to_hijri(start)?;
+        let mut month_start = start_jd - (current.day - 1) as f64;
+        let mut next_start = self.next_month_start(&current)?;
+
+        let mut dates = Vec::with_capacity(days as usize);
+        for offset in 0..days {
+            let jd = start_jd + offset as f64;
+            if jd >= next_start {
+                current = current.next_month();
+                month_start = next_start;
+                next_start = self.next_month_start(&current)?;
+            }
+            dates.push(HijriDate::new(
+                current.year,
+                current.month,
+                (jd - month_start) as u8 + 1,
+            ));
+        }
+        Ok(dates)
+    }
+
+    /// JD (00:00) tanggal 1 bulan berikutnya
+    fn next_month_start(&self, hijri: &HijriDate) -> Result<f64, String> {
+        Ok(gregorian_to_jd(
+            &self.hijri_to_gregorian(&hijri.next_month())?,
+        ))
+    }
+}
+
+/// JD (00:00) tanggal 1 bulan menurut KHGT (grid global standar)
+///
+/// Setiap awal bulan memerlukan satu penyapuan grid global, sehingga hasilnya disimpan
+/// untuk konversi berikutnya.
+fn khgt_start_jd(hijri: &HijriDate) -> Result<f64, String> {
+    let key = (hijri.year, hijri.month);
+    if let Ok(cache) = KHGT_START_CACHE.lock() {
+        if let Some(&(_, start)) = cache.iter().find(|(k, _)| *k == key) {
+            return Ok(start);
+        }
+    }
+
+    let grid = crate::map::MapGrid::uniform(crate::criteria::khgt::KHGT_GRID_STEP);
+    let result = crate::criteria::khgt::khgt_month_start(hijri.year, hijri.month, &grid)?;
+    let start = gregorian_to_jd(&result.month_start);
+    if let Ok(mut cache) = KHGT_START_CACHE.lock() {
+        if cache.len() >= MAX_CACHED_KHGT_MONTHS {
+            cache.remove(0);
+        }
+        cache.push((key, start));
+    }
+    Ok(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_khgt_month_starts_are_cached() {
+        let kind = HijriCalendarKind::Khgt;
+        let date = GregorianDate {
+            year: 2026,
+            month: 3,
+            day: 25.0,
+        };
+        let hijri = kind.gregorian_to_hijri(&date).unwrap();
+        assert_eq!(hijri, HijriDate::new(1447, 10, 6));
+
+        // Bulan yang sudah disapu diambil dari cache
+        let cached = |year, month| {
+            KHGT_START_CACHE
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|(k, _)| *k == (year, month))
+                .map(|&(_, start)| start)
+        };
+        let start = cached(1447, 10).unwrap();
+        let expected = GregorianDate {
+            year: 2026,
+            month: 3,
+            day: 20.0,
+        };
+        assert_eq!(start, gregorian_to_jd(&expected));
+        assert_eq!(khgt_start_jd(&HijriDate::new(1447, 10, 1)).unwrap(), start);
+
+        let back = kind.hijri_to_gregorian(&hijri).unwrap();
+        assert_eq!((back.year, back.month, back.day), (2026, 3, 25.0));
+    }
+}
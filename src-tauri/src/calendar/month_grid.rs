@@ -0,0 +1,289 @@
+//! Grid kalender bulanan (Gregorian, Hijriah dan pasaran Jawa)
+//!
+//! Satu grid memuat setiap hari dalam satu bulan Gregorian atau Hijriah beserta tanggal
+//! Hijriah (sesuai jenis kalender), hari, pasaran, fase bulan, dan penanda ijtimak,
+//! purnama serta hari besar Islam. Waktu penanda dan tanggalnya memakai zona waktu
+//! `timezone`, sehingga grid siap dicetak sebagai kalender dinding.
+
+use serde::{Deserialize, Serialize};
+
+use super::{HijriCalendarKind, HijriDate};
+use crate::GregorianDate;
+
+/// Rata-rata bulan sinodis (hari)
+const SYNODIC_MONTH: f64 = 29.530588861;
+
+/// Bulan acuan grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MonthGridBasis {
+    Gregorian,
+    Hijri,
+}
+
+/// Fase bulan (delapan fase berdasarkan selisih bujur bulan-matahari)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoonPhase {
+    NewMoon,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    FullMoon,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+impl MoonPhase {
+    /// Fase dari selisih bujur bulan-matahari (derajat, 0 = konjungsi)
+    pub fn from_longitude_difference(difference: f64) -> Self {
+        const PHASES: [MoonPhase; 8] = [
+            MoonPhase::NewMoon,
+            MoonPhase::WaxingCrescent,
+            MoonPhase::FirstQuarter,
+            MoonPhase::WaxingGibbous,
+            MoonPhase::FullMoon,
+            MoonPhase::WaningGibbous,
+            MoonPhase::LastQuarter,
+            MoonPhase::WaningCrescent,
+        ];
+        let octant = ((difference.rem_euclid(360.0) + 22.5) / 45.0).floor() as usize % 8;
+        PHASES[octant]
+    }
+}
+
+/// Penanda pada satu hari
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DayMarker {
+    Conjunction { jd: f64, time: String },
+    FullMoon { jd: f64, time: String },
+    Holiday { name: String },
+}
+
+/// Satu hari dalam grid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridDay {
+    pub jd: f64, // JD tengah malam (00:00) tanggal Gregorian
+    pub gregorian: GregorianDate,
+    pub hijri: HijriDate,
+    pub weekday: u8, // 0 = Ahad .. 6 = Sabtu
+    pub hari: String,
+    pub pasaran: String,
+    pub moon_illumination: f64, // Fraksi iluminasi pukul 12:00 waktu lokal (0-1)
+    pub moon_phase: MoonPhase,
+    pub markers: Vec<DayMarker>,
+}
+
+/// Grid kalender satu bulan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonthGrid {
+    pub basis: MonthGridBasis,
+    pub year: i32,
+    pub month: u8,
+    pub timezone: f64,
+    pub leading_blanks: u8, // Sel kosong sebelum tanggal 1 (minggu dimulai Ahad)
+    pub days: Vec<GridDay>,
+}
+
+/// Hitung grid kalender untuk bulan Gregorian atau Hijriah
+pub fn month_grid(
+    basis: MonthGridBasis,
+    year: i32,
+    month: u8,
+    calendar: &HijriCalendarKind,
+    timezone: f64,
+) -> Result<MonthGrid, String> {
+    if !(1..=12).contains(&month) {
+        return Err("Month must be between 1 and 12".to_string());
+    }
+
+    let (first_jd, length) = match basis {
+        MonthGridBasis::Gregorian => {
+            let first = super::gregorian_to_jd(&GregorianDate {
+                year,
+                month,
+                day: 1.0,
+            });
+            let next = if month == 12 {
+                GregorianDate {
+                    year: year + 1,
+                    month: 1,
+                    day: 1.0,
+                }
+            } else {
+                GregorianDate {
+                    year,
+                    month: month + 1,
+                    day: 1.0,
+                }
+            };
+            (first, super::gregorian_to_jd(&next) - first)
+        }
+        MonthGridBasis::Hijri => {
+            let first = calendar.hijri_to_gregorian(&HijriDate::new(year, month, 1))?;
+            let first = super::gregorian_to_jd(&first);
+            let next = calendar.hijri_dates(&super::jd_to_gregorian(first + 29.0), 1)?[0];
+            let length = if next.day == 30 { 30.0 } else { 29.0 };
+            (first, length)
+        }
+    };
+
+    let hijri_dates = calendar.hijri_dates(&super::jd_to_gregorian(first_jd), length as u32)?;
+    let events = lunar_events(first_jd, timezone);
+
+    let days = hijri_dates
+        .into_iter()
+        .enumerate()
+        .map(|(offset, hijri)| {
+            let jd = first_jd + offset as f64;
+            grid_day(jd, hijri, timezone, &events)
+        })
+        .collect();
+
+    Ok(MonthGrid {
+        basis,
+        year,
+        month,
+        timezone,
+        leading_blanks: weekday(first_jd),
+        days,
+    })
+}
+
+/// Data satu hari beserta penandanya
+fn grid_day(jd: f64, hijri: HijriDate, timezone: f64, events: &[DayMarker]) -> GridDay {
+    // Fase bulan pada tengah hari waktu lokal
+    let noon = jd + 0.5 - timezone / 24.0;
+    let difference = crate::astronomy::moon_position(noon).longitude
+        - crate::astronomy::sun_position(noon).longitude;
+
+    let mut markers: Vec<DayMarker> = events
+        .iter()
+        .filter(|event| match event {
+            DayMarker::Conjunction { jd: event_jd, .. }
+            | DayMarker::FullMoon { jd: event_jd, .. } => local_day_jd(*event_jd, timezone) == jd,
+            DayMarker::Holiday { .. } => false,
+        })
+        .cloned()
+        .collect();
+    markers.extend(
+        super::holidays::holidays_on(&hijri)
+            .into_iter()
+            .map(|holiday| DayMarker::Holiday {
+                name: holiday.name.to_string(),
+            }),
+    );
+
+    GridDay {
+        jd,
+        gregorian: super::jd_to_gregorian(jd),
+        hijri,
+        weekday: weekday(jd),
+        hari: super::javanese::get_hari_indo(jd).to_string(),
+        pasaran: super::javanese::get_pasaran(jd).to_string(),
+        moon_illumination: crate::astronomy::phase(noon),
+        moon_phase: MoonPhase::from_longitude_difference(difference),
+        markers,
+    }
+}
+
+/// Ijtimak dan purnama di sekitar rentang grid (satu lunasi sebelum sampai dua sesudah)
+fn lunar_events(first_jd: f64, timezone: f64) -> Vec<DayMarker> {
+    let format = |jd: f64| crate::astronomy::ephemeris_utils::format_jd_to_datetime(jd, timezone);
+    let anchor = crate::astronomy::find_conjunction(&super::jd_to_gregorian(first_jd)).jd_utc;
+
+    (-1..=2)
+        .flat_map(|lunation| {
+            let conjunction = if lunation == 0 {
+                anchor
+            } else {
+                let estimate = anchor + lunation as f64 * SYNODIC_MONTH;
+                crate::astronomy::find_conjunction(&super::jd_to_gregorian(estimate)).jd_utc
+            };
+            let full_moon = crate::astronomy::find_full_moon_after(conjunction);
+            [
+                DayMarker::Conjunction {
+                    jd: conjunction,
+                    time: format(conjunction),
+                },
+                DayMarker::FullMoon {
+                    jd: full_moon,
+                    time: format(full_moon),
+                },
+            ]
+        })
+        .collect()
+}
+
+/// JD tengah malam tanggal lokal dari JD UT
+fn local_day_jd(jd_utc: f64, timezone: f64) -> f64 {
+    (jd_utc + timezone / 24.0 + 0.5).floor() - 0.5
+}
+
+/// Indeks hari dalam minggu (0 = Ahad)
+fn weekday(jd: f64) -> u8 {
+    ((jd + 0.5).floor() as i64 + 1).rem_euclid(7) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gregorian_month_grid() {
+        // Februari 2026 (WIB): purnama 2 Feb 05:09, ijtimak 17 Feb 19:01
+        let grid = month_grid(
+            MonthGridBasis::Gregorian,
+            2026,
+            2,
+            &HijriCalendarKind::default(),
+            7.0,
+        )
+        .unwrap();
+
+        assert_eq!(grid.days.len(), 28);
+        assert_eq!(grid.leading_blanks, 0); // 1 Februari 2026 = Ahad
+        assert_eq!(grid.days[0].hari, "Ahad");
+
+        let marked = |predicate: fn(&DayMarker) -> bool| -> Vec<u8> {
+            grid.days
+                .iter()
+                .filter(|day| day.markers.iter().any(predicate))
+                .map(|day| day.gregorian.day as u8)
+                .collect()
+        };
+        assert_eq!(
+            marked(|m| matches!(m, DayMarker::Conjunction { .. })),
+            vec![17]
+        );
+        assert_eq!(marked(|m| matches!(m, DayMarker::FullMoon { .. })), vec![2]);
+        assert_eq!(grid.days[16].moon_phase, MoonPhase::NewMoon);
+
+        // Tanggal Hijriah berurutan tanpa celah
+        for pair in grid.days.windows(2) {
+            let (a, b) = (pair[0].hijri, pair[1].hijri);
+            assert!(b.day == a.day + 1 || (b.day == 1 && b.month != a.month));
+        }
+    }
+
+    #[test]
+    fn test_hijri_month_grid_holidays() {
+        let grid = month_grid(
+            MonthGridBasis::Hijri,
+            1447,
+            10,
+            &HijriCalendarKind::UmmAlQura,
+            3.0,
+        )
+        .unwrap();
+
+        assert!(grid.days.len() == 29 || grid.days.len() == 30);
+        assert_eq!(grid.days[0].hijri, HijriDate::new(1447, 10, 1));
+        assert!(grid.days[0]
+            .markers
+            .iter()
+            .any(|m| matches!(m, DayMarker::Holiday { name } if name == "Idul Fitri")));
+    }
+}
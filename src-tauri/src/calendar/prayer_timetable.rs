@@ -0,0 +1,207 @@
+//! Jadwal waktu shalat (imsakiyah) bulanan dan tahunan
+//!
+//! Jadwal disusun per hari untuk rentang tanggal Gregorian, satu bulan Hijriah (mis.
+//! imsakiyah Ramadan) atau satu tahun Hijriah. Setiap baris memuat tanggal Hijriah
+//! (sesuai jenis kalender), hari, pasaran dan waktu shalat hari itu.
+
+use serde::{Deserialize, Serialize};
+
+use super::{HijriCalendarKind, HijriDate};
+use crate::astronomy::prayer::{PrayerConfig, PrayerTimes};
+use crate::{GeoLocation, GregorianDate};
+
+/// Batas jumlah hari satu jadwal
+pub const MAX_TIMETABLE_DAYS: u32 = 366;
+
+/// Rentang jadwal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TimetableRange {
+    /// Rentang tanggal Gregorian (`start` sampai `end`, inklusif)
+    Gregorian {
+        start: GregorianDate,
+        end: GregorianDate,
+    },
+    /// Satu bulan Hijriah (mis. Ramadan untuk imsakiyah)
+    HijriMonth { year: i32, month: u8 },
+    /// Satu tahun Hijriah
+    HijriYear { year: i32 },
+}
+
+/// Satu baris jadwal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimetableRow {
+    pub jd: f64, // JD tengah malam (00:00) tanggal Gregorian
+    pub gregorian: GregorianDate,
+    pub hijri: HijriDate,
+    pub hari: String,
+    pub pasaran: String,
+    pub times: PrayerTimes,
+}
+
+/// Jadwal waktu shalat untuk satu lokasi
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrayerTimetable {
+    pub location: GeoLocation,
+    pub calendar: String, // Label jenis kalender Hijriah
+    pub rows: Vec<TimetableRow>,
+}
+
+/// Susun jadwal waktu shalat untuk rentang tanggal
+pub fn prayer_timetable(
+    location: &GeoLocation,
+    range: &TimetableRange,
+    calendar: &HijriCalendarKind,
+    config: &PrayerConfig,
+) -> Result<PrayerTimetable, String> {
+    let (start_jd, end_jd) = match range {
+        TimetableRange::Gregorian { start, end } => {
+            let start = super::gregorian_to_jd(&whole_day(start));
+            (start, super::gregorian_to_jd(&whole_day(end)) + 1.0)
+        }
+        TimetableRange::HijriMonth { year, month } => {
+            if !(1..=12).contains(month) {
+                return Err("Invalid Hijri month (1-12)".to_string());
+            }
+            hijri_span(calendar, HijriDate::new(*year, *month, 1), 1)?
+        }
+        TimetableRange::HijriYear { year } => {
+            hijri_span(calendar, HijriDate::new(*year, 1, 1), 12)?
+        }
+    };
+
+    let days = (end_jd - start_jd).round() as i64;
+    if days < 1 {
+        return Err("Timetable end date must not be before the start date".to_string());
+    }
+    if days > MAX_TIMETABLE_DAYS as i64 {
+        return Err(format!(
+            "Timetable is limited to {} days",
+            MAX_TIMETABLE_DAYS
+        ));
+    }
+
+    let hijri_dates = calendar.hijri_dates(&super::jd_to_gregorian(start_jd), days as u32)?;
+    let rows = hijri_dates
+        .into_iter()
+        .enumerate()
+        .map(|(offset, hijri)| {
+            let jd = start_jd + offset as f64;
+            let gregorian = super::jd_to_gregorian(jd);
+            TimetableRow {
+                jd,
+                times: crate::astronomy::prayer::calculate_prayer_times(
+                    location, &gregorian, config,
+                ),
+                gregorian,
+                hijri,
+                hari: super::javanese::get_hari_indo(jd).to_string(),
+                pasaran: super::javanese::get_pasaran(jd).to_string(),
+            }
+        })
+        .collect();
+
+    Ok(PrayerTimetable {
+        location: location.clone(),
+        calendar: calendar.label(),
+        rows,
+    })
+}
+
+/// JD (00:00) tanggal 1 bulan `first` dan tanggal 1 setelah `months` bulan
+fn hijri_span(
+    calendar: &HijriCalendarKind,
+    first: HijriDate,
+    months: u8,
+) -> Result<(f64, f64), String> {
+    let mut last = first;
+    for _ in 0..months {
+        last = last.next_month();
+    }
+    Ok((
+        super::gregorian_to_jd(&calendar.hijri_to_gregorian(&first)?),
+        super::gregorian_to_jd(&calendar.hijri_to_gregorian(&last)?),
+    ))
+}
+
+/// Tanggal tanpa jam
+fn whole_day(date: &GregorianDate) -> GregorianDate {
+    GregorianDate {
+        year: date.year,
+        month: date.month,
+        day: date.day.floor(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jakarta() -> GeoLocation {
+        GeoLocation {
+            name: Some("Jakarta".to_string()),
+            latitude: -6.2,
+            longitude: 106.8167,
+            elevation: 0.0,
+            timezone: 7.0,
+        }
+    }
+
+    #[test]
+    fn test_ramadan_imsakiyah() {
+        // Ramadan 1447 (tabular): 18 Feb - 19 Mar 2026
+        let timetable = prayer_timetable(
+            &jakarta(),
+            &TimetableRange::HijriMonth {
+                year: 1447,
+                month: 9,
+            },
+            &HijriCalendarKind::default(),
+            &PrayerConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(timetable.rows.len(), 30);
+        let first = &timetable.rows[0];
+        assert_eq!((first.gregorian.month, first.gregorian.day), (2, 18.0));
+        assert_eq!(first.hijri, HijriDate::new(1447, 9, 1));
+        assert_eq!(first.hari, "Rabu");
+        assert_eq!(timetable.rows[29].hijri, HijriDate::new(1447, 9, 30));
+        for row in &timetable.rows {
+            let jd = |event: &Option<crate::astronomy::prayer::PrayerEvent>| {
+                event.as_ref().unwrap().jd_utc
+            };
+            assert!(jd(&row.times.imsak) < jd(&row.times.shubuh));
+            assert!(jd(&row.times.dzuhur) < jd(&row.times.maghrib));
+        }
+    }
+
+    #[test]
+    fn test_gregorian_range_validation() {
+        let range = |start: (i32, u8, f64), end: (i32, u8, f64)| TimetableRange::Gregorian {
+            start: GregorianDate {
+                year: start.0,
+                month: start.1,
+                day: start.2,
+            },
+            end: GregorianDate {
+                year: end.0,
+                month: end.1,
+                day: end.2,
+            },
+        };
+        let build = |range: TimetableRange| {
+            prayer_timetable(
+                &jakarta(),
+                &range,
+                &HijriCalendarKind::default(),
+                &PrayerConfig::default(),
+            )
+        };
+
+        let year = build(range((2026, 1, 1.0), (2026, 12, 31.0))).unwrap();
+        assert_eq!(year.rows.len(), 365);
+        assert!(build(range((2026, 3, 2.0), (2026, 3, 1.0))).is_err());
+        assert!(build(range((2026, 1, 1.0), (2027, 6, 1.0))).is_err());
+    }
+}
@@ -0,0 +1,350 @@
+//! Kalender Umm al-Qura (Arab Saudi)
+//!
+//! Awal bulan 1300-1600 H diambil dari tabel resmi Umm al-Qura (data yang sama dengan
+//! kalender `islamic-umalqura` ICU). Di luar tabel dipakai aturan Umm al-Qura (sejak
+//! 1420 H) di Makkah pada hari ijtimak:
+//! 1. Ijtimak terjadi sebelum maghrib
+//! 2. Bulan terbenam setelah matahari
+//!
+//! Jika keduanya terpenuhi, esok hari adalah tanggal 1; jika tidak, lusa. Hasil dari aturan
+//! ditandai `from_table: false`.
+
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+
+use super::HijriDate;
+use crate::{GeoLocation, GregorianDate};
+
+/// JD (00:00) tanggal 1 Muharram 1300 H = 12 November 1882
+const UMM_AL_QURA_EPOCH_JD: f64 = 2408761.5;
+
+/// Panjang bulan resmi per tahun 1300-1600 H: bit ke-(bulan - 1) = 1 jika bulan 30 hari
+const UMM_AL_QURA_TABLE: [u16; 301] = [
+    0x555, 0x2AB, 0x937, 0x2B6, 0x576, 0x36C, 0xB55, 0xAAA, 0x956, 0x49E, // 1300
+    0x95D, 0x2BA, 0x5B5, 0x3AA, 0xB4B, 0xA96, 0x52E, 0x2AD, 0x56D, 0xB5A, // 1310
+    0x752, 0xF25, 0xE8A, 0xD16, 0xA56, 0xAB5, 0x6B4, 0xDA9, 0xB92, 0xB25, // 1320
+    0x64B, 0xA9B, 0x35A, 0x6D9, 0x5D4, 0xDA5, 0xD4A, 0xA95, 0x536, 0x975, // 1330
+    0x2F4, 0x6E9, 0x6D4, 0x6A9, 0x535, 0x25D, 0x4BD, 0x9BA, 0x3B4, 0xB69, // 1340
+    0xB2A, 0xA55, 0x4AD, 0xA5D, 0x2DA, 0x6D9, 0xEAA, 0xE94, 0xD2A, 0xC56, // 1350
+    0x4AE, 0xA6D, 0x56A, 0xD55, 0xD4A, 0xA93, 0x52B, 0xA5B, 0x53A, 0x6B5, // 1360
+    0xEA9, 0xD52, 0xD29, 0xA55, 0x4AD, 0x56D, 0xAEA, 0x6E4, 0xED1, 0xDA2, // 1370
+    0xAAA, 0x95A, 0x2DA, 0x5B9, 0xBB2, 0x764, 0x6C9, 0x555, 0x2AB, 0x4DB, // 1380
+    0xABA, 0x5B4, 0xDA9, 0xD52, 0xAA5, 0x92D, 0x26D, 0x8ED, 0x2DA, 0xAD5, // 1390
+    0xAA5, 0xA4B, 0x497, 0x937, 0x2B6, 0x975, 0xD69, 0xD52, 0xC95, 0x92B, // 1400
+    0x25B, 0x4DB, 0x9D5, 0x5D2, 0xDA5, 0xD4A, 0xA95, 0x54D, 0xAAD, 0x3AA, // 1410
+    0xBD2, 0xBC4, 0xB89, 0xA95, 0x52D, 0x5AD, 0xB6A, 0x6D4, 0xDC9, 0xD92, // 1420
+    0xAA6, 0x956, 0x2AE, 0x56D, 0x36A, 0xB55, 0xAAA, 0x94D, 0x49D, 0x95D, // 1430
+    0x2BA, 0x5B5, 0x5AA, 0xD55, 0xA9A, 0x92E, 0x26E, 0x55D, 0xADA, 0x6D4, // 1440
+    0x6A5, 0xB27, 0xA4D, 0x4AD, 0x56D, 0xB5A, 0x754, 0xF49, 0xE92, 0xD26, // 1450
+    0xA56, 0x356, 0x6B5, 0xBAA, 0xB92, 0xB25, 0x68B, 0xA9B, 0x55A, 0xADA, // 1460
+    0x5B4, 0xDA9, 0xB52, 0xA9A, 0x536, 0x276, 0x575, 0xAF2, 0x6D4, 0x6A9, // 1470
+    0x555, 0x2AD, 0x4BD, 0x9BA, 0x574, 0xB69, 0xB52, 0xA95, 0x52D, 0xA5D, // 1480
+    0x4DA, 0xAD9, 0x6B2, 0xE95, 0xE2A, 0xC96, 0x92E, 0xAAD, 0x56A, 0xD65, // 1490
+    0xD4A, 0xD15, 0x62B, 0xC5B, 0x53A, 0x6B5, 0xDB2, 0xD64, 0xD29, 0xA55, // 1500
+    0x4AD, 0x96D, 0xAEA, 0x6E8, 0xED1, 0xDA4, 0xD4A, 0xA6A, 0x2DA, 0x5B9, // 1510
+    0xB72, 0xB68, 0x6D1, 0x655, 0x4AB, 0x95B, 0x2BA, 0x5B5, 0xDA9, 0xD52, // 1520
+    0xCA6, 0x94E, 0x46E, 0x95D, 0x4DA, 0xAD5, 0xAAA, 0xA4D, 0x49B, 0x937, // 1530
+    0x4B6, 0x975, 0xD6A, 0xD52, 0xAA5, 0x94B, 0x2AB, 0x55B, 0xAD9, 0x5D2, // 1540
+    0xDC5, 0xD92, 0xB25, 0x555, 0xAB5, 0x5B4, 0xBA9, 0x7A2, 0x745, 0x593, // 1550
+    0xAAB, 0x4D6, 0x9D6, 0x5D2, 0xBA5, 0xB4A, 0xA95, 0x4AD, 0x15D, 0x2DD, // 1560
+    0x9DA, 0x5B4, 0x5A9, 0x52D, 0x25B, 0x8B7, 0x176, 0x56D, 0xB6A, 0xACA, // 1570
+    0xA96, 0x52B, 0x15B, 0x2BB, 0x5B6, 0xDAA, 0xB94, 0xD46, 0xA8D, 0x52D, // 1580
+    0xA9D, 0x55A, 0x755, 0x749, 0xF13, 0xE4A, 0xA96, 0x556, 0x6B5, 0xBAA, // 1590
+    0xB94, // 1600
+];
+
+/// Rentang tahun tabel Umm al-Qura resmi
+pub const UMM_AL_QURA_YEARS: RangeInclusive<i32> = 1300..=1600;
+
+/// Lokasi acuan Umm al-Qura (Ka'bah, Makkah)
+pub fn mecca() -> GeoLocation {
+    GeoLocation {
+        name: Some("Makkah".to_string()),
+        latitude: 21.4225,
+        longitude: 39.8262,
+        elevation: 277.0,
+        timezone: 3.0,
+    }
+}
+
+/// Satu bulan Umm al-Qura
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UmmAlQuraMonth {
+    pub year: i32,
+    pub month: u8,
+    pub start: GregorianDate,
+    pub length: u8,
+    pub from_table: bool, // false: dihitung dengan aturan Umm al-Qura
+}
+
+/// Tanggal-tanggal haji (Dzulhijjah)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HajjDates {
+    pub hijri_year: i32,
+    pub dhu_al_hijjah_start: GregorianDate,
+    pub tarwiyah: GregorianDate,    // 8 Dzulhijjah
+    pub arafah: GregorianDate,      // 9 Dzulhijjah
+    pub eid_al_adha: GregorianDate, // 10 Dzulhijjah
+    pub tashriq_end: GregorianDate, // 13 Dzulhijjah
+    pub from_table: bool,           // false: dihitung dengan aturan Umm al-Qura
+}
+
+/// JD (00:00) tanggal 1 bulan Hijriah Umm al-Qura, beserta asal datanya
+fn month_start(year: i32, month: u8) -> (f64, bool) {
+    // 1 Muharram 1601 H masih ditentukan tabel (akhir Dzulhijjah 1600 H)
+    let in_table =
+        UMM_AL_QURA_YEARS.contains(&year) || (year == UMM_AL_QURA_YEARS.end() + 1 && month == 1);
+    if !in_table {
+        return (rule_month_start(year, month), false);
+    }
+
+    let index = (year - UMM_AL_QURA_YEARS.start()) as usize;
+    let years: f64 = UMM_AL_QURA_TABLE[..index]
+        .iter()
+        .map(|mask| 348.0 + mask.count_ones() as f64)
+        .sum();
+    let mask = UMM_AL_QURA_TABLE.get(index).copied().unwrap_or(0);
+    let months: f64 = (0..month - 1)
+        .map(|m| 29.0 + ((mask >> m) & 1) as f64)
+        .sum();
+    (UMM_AL_QURA_EPOCH_JD + years + months, true)
+}
+
+/// Awal bulan menurut aturan Umm al-Qura di Makkah
+fn rule_month_start(year: i32, month: u8) -> f64 {
+    let tabular = super::hijri_to_gregorian(&HijriDate::new(year, month, 1));
+    let conjunction = crate::astronomy::conjunction::find_conjunction(&tabular);
+
+    // Tanggal lokal Makkah saat ijtimak
+    let location = mecca();
+    let local = super::jd_to_gregorian(conjunction.jd_utc + location.timezone / 24.0);
+    let date = GregorianDate {
+        year: local.year,
+        month: local.month,
+        day: local.day.floor(),
+    };
+    let day_jd = super::gregorian_to_jd(&date);
+
+    let params = crate::criteria::HilalParameters::new(&location, &date, conjunction.jd_utc);
+    let conjunction_before_sunset = conjunction.jd_utc < params.sunset_jd;
+    let moonset_after_sunset = params.lag_minutes().is_some_and(|lag| lag > 0.0);
+
+    if conjunction_before_sunset && moonset_after_sunset {
+        day_jd + 1.0
+    } else {
+        day_jd + 2.0
+    }
+}
+
+/// Data satu bulan Umm al-Qura
+pub fn umm_al_qura_month(year: i32, month: u8) -> Result<UmmAlQuraMonth, String> {
+    if !(1..=12).contains(&month) {
+        return Err("Hijri month must be between 1 and 12".to_string());
+    }
+    let (start_jd, from_table) = month_start(year, month);
+    let (next_year, next_month) = next_month(year, month);
+    let (next_jd, _) = month_start(next_year, next_month);
+
+    Ok(UmmAlQuraMonth {
+        year,
+        month,
+        start: super::jd_to_gregorian(start_jd),
+        length: (next_jd - start_jd).round() as u8,
+        from_table,
+    })
+}
+
+/// Konversi Hijriah (Umm al-Qura) ke Gregorian
+pub fn hijri_to_gregorian(hijri: &HijriDate) -> Result<GregorianDate, String> {
+    let month = umm_al_qura_month(hijri.year, hijri.month)?;
+    if hijri.day < 1 || hijri.day > month.length {
+        return Err(format!(
+            "Day {} does not exist in Umm al-Qura {} {} ({} days)",
+            hijri.day,
+            hijri.month_name(),
+            hijri.year,
+            month.length
+        ));
+    }
+    let start_jd = super::gregorian_to_jd(&month.start);
+    Ok(super::jd_to_gregorian(start_jd + (hijri.day - 1) as f64))
+}
+
+/// Konversi Gregorian ke Hijriah (Umm al-Qura)
+pub fn gregorian_to_hijri(date: &GregorianDate) -> Result<HijriDate, String> {
+    let jd = super::gregorian_to_jd(&GregorianDate {
+        year: date.year,
+        month: date.month,
+        day: date.day.floor(),
+    });
+
+    // Mulai dari bulan tabular sesudahnya, mundur sampai awal bulan <= tanggal
+    let tabular = super::gregorian_to_hijri(date);
+    let (mut year, mut month) = next_month(tabular.year, tabular.month);
+    loop {
+        let (start_jd, _) = month_start(year, month);
+        if start_jd <= jd {
+            return Ok(HijriDate::new(year, month, (jd - start_jd) as u8 + 1));
+        }
+        (year, month) = previous_month(year, month);
+    }
+}
+
+/// Tanggal haji (Arafah, Idul Adha, dll.) untuk tahun Hijriah
+pub fn hajj_dates(hijri_year: i32) -> Result<HajjDates, String> {
+    let month = umm_al_qura_month(hijri_year, 12)?;
+    let start = month.start;
+    let start_jd = super::gregorian_to_jd(&start);
+    let day = |n: f64| super::jd_to_gregorian(start_jd + n - 1.0);
+
+    Ok(HajjDates {
+        hijri_year,
+        tarwiyah: day(8.0),
+        arafah: day(9.0),
+        eid_al_adha: day(10.0),
+        tashriq_end: day(13.0),
+        dhu_al_hijjah_start: start,
+        from_table: month.from_table,
+    })
+}
+
+fn next_month(year: i32, month: u8) -> (i32, u8) {
+    if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    }
+}
+
+fn previous_month(year: i32, month: u8) -> (i32, u8) {
+    if month == 1 {
+        (year - 1, 12)
+    } else {
+        (year, month - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hajj_1445() {
+        // Wukuf Arafah 15 Juni 2024, Idul Adha 16 Juni 2024
+        let hajj = hajj_dates(1445).unwrap();
+        assert_eq!((hajj.arafah.month, hajj.arafah.day), (6, 15.0));
+        assert_eq!((hajj.eid_al_adha.month, hajj.eid_al_adha.day), (6, 16.0));
+    }
+
+    /// Tanggal 1 bulan dari tabel sebagai (tahun, bulan, tanggal) Gregorian
+    fn table_start(year: i32, month: u8) -> (i32, u8, f64) {
+        let data = umm_al_qura_month(year, month).unwrap();
+        assert!(data.from_table, "{}-{}", year, month);
+        (data.start.year, data.start.month, data.start.day)
+    }
+
+    #[test]
+    fn test_published_dates() {
+        // Sebelum 1420 H: batas UmAlQuraCalendar .NET (1 Muharram 1318 - 30 Dzulhijjah 1500)
+        assert_eq!(table_start(1300, 1), (1882, 11, 12.0));
+        assert_eq!(table_start(1318, 1), (1900, 4, 30.0));
+        assert_eq!(table_start(1501, 1), (2077, 11, 17.0));
+        assert_eq!(umm_al_qura_month(1500, 12).unwrap().length, 30);
+
+        // 1420-1423 H
+        let hajj = hajj_dates(1420).unwrap();
+        assert_eq!((hajj.eid_al_adha.month, hajj.eid_al_adha.day), (3, 16.0));
+        assert_eq!(table_start(1422, 9), (2001, 11, 16.0));
+
+        // Setelah 1423 H
+        assert_eq!(table_start(1435, 9), (2014, 6, 28.0));
+        assert_eq!(table_start(1440, 9), (2019, 5, 6.0));
+        assert_eq!(table_start(1444, 9), (2023, 3, 23.0));
+        assert_eq!(table_start(1446, 10), (2025, 3, 30.0));
+        assert_eq!(table_start(1447, 9), (2026, 2, 18.0));
+    }
+
+    #[test]
+    fn test_rule_matches_official_table() {
+        // Sejak 1423 H tabel resmi mengikuti aturan Umm al-Qura
+        let months = [
+            (1444, 9),
+            (1444, 10),
+            (1444, 12),
+            (1445, 1),
+            (1445, 9),
+            (1445, 10),
+            (1445, 12),
+            (1446, 1),
+            (1446, 9),
+            (1446, 10),
+            (1446, 12),
+            (1447, 1),
+        ];
+        for (year, month) in months {
+            let rule = super::super::jd_to_gregorian(rule_month_start(year, month));
+            assert_eq!(
+                (rule.year, rule.month, rule.day),
+                table_start(year, month),
+                "{}-{}",
+                year,
+                month
+            );
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let date = GregorianDate {
+            year: 2025,
+            month: 3,
+            day: 30.0,
+        };
+        let hijri = gregorian_to_hijri(&date).unwrap();
+        assert_eq!(hijri, HijriDate::new(1446, 10, 1));
+        let back = hijri_to_gregorian(&hijri).unwrap();
+        assert_eq!((back.year, back.month, back.day), (2025, 3, 30.0));
+    }
+
+    #[test]
+    fn test_range_edges_and_fallback() {
+        assert!(umm_al_qura_month(1300, 1).unwrap().from_table);
+        assert!(umm_al_qura_month(1600, 12).unwrap().from_table);
+        assert!(umm_al_qura_month(1447, 13).is_err());
+
+        // Di luar tabel: aturan Umm al-Qura, ditandai dan konsisten bolak-balik
+        for (year, month) in [(1299, 12), (1601, 2), (1650, 9)] {
+            let data = umm_al_qura_month(year, month).unwrap();
+            assert!(!data.from_table, "{}-{}", year, month);
+            assert!(data.length == 29 || data.length == 30, "{}-{}", year, month);
+            assert_eq!(
+                gregorian_to_hijri(&data.start).unwrap(),
+                HijriDate::new(year, month, 1)
+            );
+        }
+        assert!(!hajj_dates(1601).unwrap().from_table);
+        assert!(hajj_dates(1600).unwrap().from_table);
+    }
+
+    #[test]
+    fn test_months_across_range() {
+        for year in [1300, 1355, 1410, 1465, 1520, 1575, 1600] {
+            for month in [1, 12] {
+                let data = umm_al_qura_month(year, month).unwrap();
+                assert!(data.from_table, "{}-{}", year, month);
+                assert!(data.length == 29 || data.length == 30, "{}-{}", year, month);
+
+                // Dekat bulan tabular dan konsisten bolak-balik
+                let start_jd = super::super::gregorian_to_jd(&data.start);
+                let tabular = super::super::hijri_to_gregorian(&HijriDate::new(year, month, 1));
+                let offset = start_jd - super::super::gregorian_to_jd(&tabular);
+                assert!(offset.abs() <= 2.0, "{}-{}: {}", year, month, offset);
+                assert_eq!(
+                    gregorian_to_hijri(&data.start).unwrap(),
+                    HijriDate::new(year, month, 1)
+                );
+            }
+        }
+    }
+}
@@ -1,13 +1,19 @@
 //! Calendar conversion command handlers
 
 use crate::{GregorianDate, calendar::HijriDate};
+use crate::calendar::hisab::HisabOptions;
+use crate::calendar::HijriCalendarKind;
+use crate::calendar::javanese::{JavaneseDate, JavaneseDay, JavaneseReckoning};
 
 /// Convert Gregorian date to Hijri
+///
+/// `calendar` memilih jenis kalender (tabular, Umm al-Qura, hisab); default tabular.
 #[tauri::command]
 pub fn gregorian_to_hijri_command(
     year: i32,
     month: u8,
     day: u8,
+    calendar: Option<HijriCalendarKind>,
 ) -> Result<HijriDate, String> {
     // Validate input
     if month < 1 || month > 12 {
@@ -23,16 +29,16 @@ pub fn gregorian_to_hijri_command(
         day: day as f64,
     };
 
-    let hijri = crate::calendar::gregorian_to_hijri(&gregorian);
-    Ok(hijri)
+    calendar.unwrap_or_default().gregorian_to_hijri(&gregorian)
 }
 
-/// Convert Hijri date to Gregorian
+/// Convert Hijri date to Gregorian (tabular, Umm al-Qura, atau hisab)
 #[tauri::command]
 pub fn hijri_to_gregorian_command(
     year: i32,
     month: u8,
     day: u8,
+    calendar: Option<HijriCalendarKind>,
 ) -> Result<GregorianDate, String> {
     // Validate input
     if month < 1 || month > 12 {
@@ -46,9 +52,116 @@ pub fn hijri_to_gregorian_command(
     }
 
     let hijri = HijriDate::new(year, month, day);
-    let gregorian = crate::calendar::hijri_to_gregorian(&hijri);
+    calendar.unwrap_or_default().hijri_to_gregorian(&hijri)
+}
+
+/// Convert Gregorian date to the Javanese calendar (tahun, windu, wuku, neptu, dll.)
+#[tauri::command]
+pub fn gregorian_to_javanese_command(
+    year: i32,
+    month: u8,
+    day: u8,
+    reckoning: Option<JavaneseReckoning>,
+) -> Result<JavaneseDay, String> {
+    // Validate input
+    if !(1..=12).contains(&month) {
+        return Err("Invalid Gregorian month (1-12)".to_string());
+    }
+    if !(1..=31).contains(&day) {
+        return Err("Invalid Gregorian day (1-31)".to_string());
+    }
 
-    Ok(gregorian)
+    let jd = crate::calendar::gregorian_to_jd(&GregorianDate {
+        year,
+        month,
+        day: day as f64,
+    });
+    crate::calendar::javanese::javanese_day(jd, reckoning.unwrap_or_default())
+}
+
+/// Convert Javanese date to Gregorian, returned with the full Javanese day information
+#[tauri::command]
+pub fn javanese_to_gregorian_command(
+    year: i32,
+    month: u8,
+    day: u8,
+    reckoning: Option<JavaneseReckoning>,
+) -> Result<JavaneseDay, String> {
+    let reckoning = reckoning.unwrap_or_default();
+    let jd = crate::calendar::javanese::javanese_to_jd(
+        &JavaneseDate::new(year, month, day),
+        reckoning,
+    )?;
+    crate::calendar::javanese::javanese_day(jd, reckoning)
+}
+
+/// Month-view calendar grid (Gregorian or Hijri month) with Hijri date, hari, pasaran,
+/// moon phase and markers for ijtimak, purnama and Islamic holidays
+#[tauri::command]
+pub async fn calendar_month_grid_command(
+    basis: crate::calendar::month_grid::MonthGridBasis,
+    year: i32,
+    month: u8,
+    calendar: Option<HijriCalendarKind>,
+    timezone: f64,
+) -> Result<crate::calendar::month_grid::MonthGrid, String> {
+    let calendar = calendar.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::calendar::month_grid::month_grid(basis, year, month, &calendar, timezone)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Islamic holidays of a Hijri year under each requested calendar mode
+///
+/// Tanpa `calendars` memakai kalender tabular. Hari besar yang tanggalnya berbeda antar
+/// mode dikembalikan berdampingan (satu observance per tanggal).
+#[tauri::command]
+pub async fn islamic_holidays_command(
+    hijri_year: i32,
+    calendars: Option<Vec<HijriCalendarKind>>,
+) -> Result<Vec<crate::calendar::holidays::Holiday>, String> {
+    let calendars = calendars.unwrap_or_else(|| vec![HijriCalendarKind::default()]);
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::calendar::holidays::islamic_holidays(hijri_year, &calendars)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Generate Hijri calendar (hisab) for a span of Hijri years
+#[tauri::command]
+pub fn generate_hisab_calendar_command(
+    hisab: HisabOptions,
+    start_year: i32,
+    years: i32,
+) -> Result<crate::calendar::hisab::HisabCalendar, String> {
+    crate::calendar::hisab::generate_hisab_calendar(&hisab, start_year, years)
+}
+
+/// Hajj dates (8-13 Dhu al-Hijjah) according to the Umm al-Qura calendar
+#[tauri::command]
+pub fn umm_al_qura_hajj_dates_command(
+    hijri_year: i32,
+) -> Result<crate::calendar::umm_al_qura::HajjDates, String> {
+    crate::calendar::umm_al_qura::hajj_dates(hijri_year)
+}
+
+/// Generate KHGT (global single Hijri calendar) for one Hijri year
+#[tauri::command]
+pub async fn generate_khgt_calendar_command(
+    hijri_year: i32,
+    step_degrees: Option<f64>,
+) -> Result<Vec<crate::criteria::khgt::KhgtMonth>, String> {
+    let grid = crate::map::MapGrid::uniform(
+        step_degrees.unwrap_or(crate::criteria::khgt::KHGT_GRID_STEP),
+    );
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::criteria::khgt::generate_khgt_calendar(hijri_year, &grid)
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
 #[cfg(test)]
@@ -57,25 +170,56 @@ mod tests {
 
     #[test]
     fn test_gregorian_to_hijri_invalid_month() {
-        let result = gregorian_to_hijri_command(2024, 13, 1);
+        let result = gregorian_to_hijri_command(2024, 13, 1, None);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_hijri_to_gregorian_invalid_month() {
-        let result = hijri_to_gregorian_command(1445, 13, 1);
+        let result = hijri_to_gregorian_command(1445, 13, 1, None);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_gregorian_to_hijri_valid() {
-        let result = gregorian_to_hijri_command(2024, 1, 1);
+        let result = gregorian_to_hijri_command(2024, 1, 1, None);
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_hijri_to_gregorian_valid() {
-        let result = hijri_to_gregorian_command(1445, 1, 1);
+        let result = hijri_to_gregorian_command(1445, 1, 1, None);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_tabular_variant_selection() {
+        // Bohra: Fatimiyah + epoch astronomis
+        let calendar: HijriCalendarKind = serde_json::from_value(serde_json::json!({
+            "kind": "tabular",
+            "leap": "fatimid",
+            "epoch": "astronomical"
+        }))
+        .unwrap();
+        let result = hijri_to_gregorian_command(1445, 9, 1, Some(calendar)).unwrap();
+        assert_eq!((result.year, result.month, result.day), (2024, 3, 10.0));
+
+        // Tanpa pola/epoch: varian default
+        let calendar: HijriCalendarKind =
+            serde_json::from_value(serde_json::json!({ "kind": "tabular" })).unwrap();
+        let result = hijri_to_gregorian_command(1445, 9, 1, Some(calendar)).unwrap();
+        assert_eq!((result.year, result.month, result.day), (2024, 3, 11.0));
+    }
+
+    #[test]
+    fn test_javanese_commands() {
+        let day = gregorian_to_javanese_command(2025, 6, 27, None).unwrap();
+        assert_eq!(day.date, JavaneseDate::new(1959, 1, 1));
+        assert_eq!(day.year_name, "Dal");
+
+        let aboge = javanese_to_gregorian_command(1959, 1, 1, Some(JavaneseReckoning::Aboge))
+            .unwrap();
+        assert_eq!((aboge.gregorian.month, aboge.gregorian.day), (6, 28.0));
+        assert!(javanese_to_gregorian_command(1959, 2, 30, None).is_err());
+    }
 }
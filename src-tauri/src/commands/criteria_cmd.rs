@@ -0,0 +1,70 @@
+//! Criteria registry command handler
+
+use crate::criteria::CriterionInfo;
+
+/// Daftar semua kriteria visibilitas yang terdaftar (untuk pilihan di UI)
+#[tauri::command]
+pub fn list_criteria_command() -> Vec<CriterionInfo> {
+    crate::criteria::list_criteria()
+}
+
+/// Muat kriteria kustom dari file aturan (.json atau .toml)
+///
+/// Tanpa `path`, file dipilih pengguna lewat dialog. `path` dari frontend hanya diterima jika
+/// berada di folder `criteria` dalam direktori konfigurasi aplikasi.
+#[tauri::command]
+pub async fn load_custom_criteria_command(
+    app: tauri::AppHandle,
+    path: Option<String>,
+) -> Result<Vec<CriterionInfo>, String> {
+    use tauri::Manager;
+    use tauri_plugin_dialog::DialogExt;
+
+    let path = match path {
+        Some(path) => {
+            let dir = app
+                .path()
+                .app_config_dir()
+                .map_err(|e| e.to_string())?
+                .join("criteria");
+            crate::criteria::custom::resolve_rule_path(&dir, &path)?
+        }
+        None => {
+            // Dialog blocking: command async agar tidak berjalan di main thread
+            let picked = app
+                .dialog()
+                .file()
+                .add_filter("Criteria rules", &["json", "toml"])
+                .blocking_pick_file()
+                .ok_or_else(|| "No rule file selected".to_string())?;
+            picked.into_path().map_err(|e| e.to_string())?
+        }
+    };
+    crate::criteria::load_custom_criteria_file(&path.to_string_lossy())
+}
+
+/// Hapus kriteria kustom yang sudah dimuat
+#[tauri::command]
+pub fn remove_custom_criteria_command(id: String) -> Result<(), String> {
+    if crate::criteria::custom::remove_custom_criterion(&id) {
+        Ok(())
+    } else {
+        Err(format!("Custom criteria '{}' not found", id))
+    }
+}
+
+/// Keputusan KHGT global untuk awal bulan Hijriah (penyapuan grid seluruh dunia)
+#[tauri::command]
+pub async fn evaluate_khgt_global_command(
+    hijri_year: i32,
+    hijri_month: u8,
+    step_degrees: Option<f64>,
+) -> Result<crate::criteria::khgt::KhgtGlobalResult, String> {
+    let grid =
+        crate::map::MapGrid::uniform(step_degrees.unwrap_or(crate::criteria::khgt::KHGT_GRID_STEP));
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::criteria::khgt::khgt_month_start(hijri_year, hijri_month, &grid)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
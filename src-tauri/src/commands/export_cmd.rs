@@ -0,0 +1,169 @@
+//! File export command handlers
+//!
+//! Lokasi file dipilih lewat dialog simpan di backend; path dari frontend tidak diterima,
+//! sehingga webview tidak dapat menulis ke file sembarang.
+
+use std::path::PathBuf;
+
+use crate::astronomy::prayer::PrayerSettings;
+use crate::calendar::prayer_timetable::TimetableRange;
+use crate::calendar::HijriCalendarKind;
+use crate::export::ical::IcalExportOptions;
+use crate::GeoLocation;
+
+/// Output format of an exported timetable
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimetableFormat {
+    Csv,
+    Json,
+}
+
+impl TimetableFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// File hasil export: path pilihan pengguna dan jumlah event/baris yang ditulis
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ExportedFile {
+    pub path: String,
+    pub count: usize,
+}
+
+/// Export Hijri month starts, Islamic holidays, moon phases and (optionally) daily prayer
+/// times as an iCalendar (.ics) file
+///
+/// File .ics dipilih pengguna lewat dialog simpan. None jika dialog dibatalkan.
+#[tauri::command]
+pub async fn export_icalendar_command(
+    app: tauri::AppHandle,
+    options: IcalExportOptions,
+) -> Result<Option<ExportedFile>, String> {
+    let default_name = format!("kalender-hijriah-{}.ics", options.hijri_year);
+    let Some(path) = pick_save_path(&app, "iCalendar", "ics", default_name)? else {
+        return Ok(None);
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let events = crate::export::ical::calendar_events(&options)?;
+        let name = format!(
+            "Kalender Hijriah {} H ({})",
+            options.hijri_year,
+            options.calendar.label()
+        );
+        let dtstamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let ics = crate::export::ical::write_calendar(&name, &events, &dtstamp);
+        write_export(path, ics, events.len())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map(Some)
+}
+
+/// Export a prayer timetable (see `prayer_timetable_command`) as CSV or JSON
+///
+/// File dipilih lewat dialog simpan dengan ekstensi sesuai `format`. None jika dialog
+/// dibatalkan; jumlah baris (hari) dikembalikan dalam `count`.
+#[tauri::command]
+pub async fn export_prayer_timetable_command(
+    app: tauri::AppHandle,
+    location: GeoLocation,
+    range: TimetableRange,
+    calendar: Option<HijriCalendarKind>,
+    format: TimetableFormat,
+    method: Option<PrayerSettings>,
+) -> Result<Option<ExportedFile>, String> {
+    let calendar = calendar.unwrap_or_default();
+    let config = method.map(|m| m.config()).unwrap_or_default();
+
+    let extension = format.extension();
+    let default_name = format!(
+        "jadwal-shalat-{}.{}",
+        chrono::Local::now().format("%Y-%m-%d"),
+        extension
+    );
+    let filter = extension.to_ascii_uppercase();
+    let Some(path) = pick_save_path(&app, &filter, extension, default_name)? else {
+        return Ok(None);
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let timetable = crate::calendar::prayer_timetable::prayer_timetable(
+            &location, &range, &calendar, &config,
+        )?;
+        let content = match format {
+            TimetableFormat::Csv => crate::export::csv::prayer_timetable_csv(&timetable),
+            TimetableFormat::Json => {
+                serde_json::to_string_pretty(&timetable).map_err(|e| e.to_string())?
+            }
+        };
+        write_export(path, content, timetable.rows.len())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map(Some)
+}
+
+/// Dialog simpan (blocking: command async agar tidak berjalan di main thread)
+fn pick_save_path(
+    app: &tauri::AppHandle,
+    filter: &str,
+    extension: &str,
+    default_name: String,
+) -> Result<Option<PathBuf>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let picked = app
+        .dialog()
+        .file()
+        .add_filter(filter, &[extension])
+        .set_file_name(default_name)
+        .blocking_save_file();
+    match picked {
+        Some(picked) => {
+            let path = picked.into_path().map_err(|e| e.to_string())?;
+            export_path(path, extension).map(Some)
+        }
+        None => Ok(None),
+    }
+}
+
+/// Paksa ekstensi file export: tanpa ekstensi ditambahkan, ekstensi lain ditolak
+fn export_path(mut path: PathBuf, extension: &str) -> Result<PathBuf, String> {
+    match path.extension().and_then(|e| e.to_str()) {
+        None => {
+            path.set_extension(extension);
+            Ok(path)
+        }
+        Some(e) if e.eq_ignore_ascii_case(extension) => Ok(path),
+        Some(e) => Err(format!(
+            "Export file must have a .{} extension, got .{}",
+            extension, e
+        )),
+    }
+}
+
+fn write_export(path: PathBuf, content: String, count: usize) -> Result<ExportedFile, String> {
+    let path = path.to_string_lossy().into_owned();
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(ExportedFile { path, count })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_path_extension() {
+        let ics = export_path(PathBuf::from("/tmp/kalender"), "ics").unwrap();
+        assert_eq!(ics, PathBuf::from("/tmp/kalender.ics"));
+        assert!(export_path(PathBuf::from("/tmp/jadwal.CSV"), "csv").is_ok());
+        assert!(export_path(PathBuf::from("/tmp/jadwal.csv"), "json").is_err());
+        assert!(export_path(PathBuf::from("/tmp/catatan.txt"), "ics").is_err());
+    }
+}
@@ -1,5 +1,6 @@
 //! Hilal visibility command handler
 
+use crate::astronomy::rise_set::RiseSetOptions;
 use crate::{GeoLocation, GregorianDate};
 use std::collections::HashMap;
 
@@ -180,14 +181,25 @@ pub struct HilalCalculationResult {
 }
 
 /// Calculate hilal visibility untuk semua kriteria (dengan input Gregorian)
+///
+/// `rise_set` mengatur tekanan, suhu dan jenis ufuk untuk maghrib; default dip dari
+/// ketinggian lokasi.
 #[tauri::command]
 pub fn calculate_hilal_visibility_command(
     location: GeoLocation,
     year: i32,
     month: u8,
     day: u8,
+    rise_set: Option<RiseSetOptions>,
 ) -> Result<HilalCalculationResult, String> {
-    calculate_hilal_visibility_internal(location, year, month, day, false)
+    calculate_hilal_visibility_internal(
+        location,
+        year,
+        month,
+        day,
+        false,
+        &rise_set.unwrap_or_default(),
+    )
 }
 
 /// Calculate hilal visibility dengan input tanggal Hijriah
@@ -198,6 +210,7 @@ pub fn calculate_hilal_visibility_hijri_command(
     hijri_year: i32,
     hijri_month: u8,
     hijri_day: u8,
+    rise_set: Option<RiseSetOptions>,
 ) -> Result<HilalCalculationResult, String> {
     // Konversi tanggal Hijriah ke Gregorian
     let hijri_date = crate::calendar::HijriDate::new(hijri_year, hijri_month, hijri_day);
@@ -219,6 +232,7 @@ pub fn calculate_hilal_visibility_hijri_command(
         gregorian_date.month,
         gregorian_date.day as u8,
         true, // Flag untuk menandai input dari Hijriah
+        &rise_set.unwrap_or_default(),
     )
 }
 
@@ -229,6 +243,7 @@ fn calculate_hilal_visibility_internal(
     month: u8,
     day: u8,
     is_hijri_input: bool,
+    rise_set: &RiseSetOptions,
 ) -> Result<HilalCalculationResult, String> {
     // Log received location untuk debugging
     println!(
@@ -255,16 +270,8 @@ fn calculate_hilal_visibility_internal(
     // Ini lebih akurat daripada find_conjunction_for_month untuk hilal di akhir bulan
     let conjunction = crate::astronomy::conjunction::find_conjunction(&observation_date);
 
-    // Calculate sunset JD
-    // calculate_sunset returns basic local time (without timezone check for date validity),
-    // so we assume it applies to the same date.
-    let sunset_hour_local = crate::astronomy::calculate_sunset(&location, &observation_date);
-
-    // We need UT for JD
-    let sunset_hour_ut = sunset_hour_local - location.timezone;
-
-    let jd_day_start = crate::calendar::gregorian_to_jd(&observation_date);
-    let sunset_jd = jd_day_start + (sunset_hour_ut / 24.0);
+    // JD maghrib (UT) pada tanggal lokal observasi, dengan kondisi pengamatan `rise_set`
+    let sunset_jd = crate::astronomy::hilal::sunset_jd(&location, &observation_date, rise_set);
 
     // Calculate detailed ephemeris
     // Note: detailed ephemeris calculation might need the localized sunset time for display
@@ -272,8 +279,12 @@ fn calculate_hilal_visibility_internal(
         calculate_detailed_ephemeris(&location, conjunction.jd_utc, sunset_jd, &observation_date);
 
     // Evaluasi semua kriteria
-    let criteria_results =
-        crate::criteria::evaluate_all_criteria(&location, &observation_date, conjunction.jd_utc);
+    let criteria_results = crate::criteria::evaluate_all_criteria(
+        &location,
+        &observation_date,
+        conjunction.jd_utc,
+        rise_set,
+    );
 
     // Konversi HashMap ke format yang sesuai
     let mut formatted_results = HashMap::new();
@@ -681,7 +692,7 @@ mod tests {
             timezone: 7.0,
         };
 
-        let result = calculate_hilal_visibility_command(location, 2024, 13, 1);
+        let result = calculate_hilal_visibility_command(location, 2024, 13, 1, None);
         assert!(result.is_err());
     }
 
@@ -695,7 +706,68 @@ mod tests {
             timezone: 7.0,
         };
 
-        let result = calculate_hilal_visibility_command(location, 2024, 1, 32);
+        let result = calculate_hilal_visibility_command(location, 2024, 1, 32, None);
         assert!(result.is_err());
     }
+
+    /// Detail view peta (map::calculate_detailed_hilal_data) harus konsisten
+    /// dengan ephemeris detail command hilal - kasus VB6 Sukabumi 18 Feb 2026
+    #[test]
+    fn test_detailed_hilal_data_matches_ephemeris_sukabumi() {
+        let location = GeoLocation {
+            name: Some("Sukabumi".to_string()),
+            latitude: -7.0739,
+            longitude: 106.5314,
+            elevation: 10.0,
+            timezone: 7.0,
+        };
+        let date = GregorianDate {
+            year: 2026,
+            month: 2,
+            day: 18.0,
+        };
+
+        let conjunction = crate::astronomy::conjunction::find_conjunction(&date);
+        let sunset_local = crate::astronomy::calculate_sunset(&location, &date);
+        let sunset_jd = crate::calendar::gregorian_to_jd(&date)
+            + (sunset_local - location.timezone) / 24.0;
+        let eph = calculate_detailed_ephemeris(&location, conjunction.jd_utc, sunset_jd, &date);
+
+        let detail =
+            crate::map::calculate_detailed_hilal_data(&location, &date, &RiseSetOptions::default());
+
+        assert_eq!(detail.conjunction_jd, eph.conjunction_jd_geocentric);
+        assert_eq!(detail.conjunction_date, eph.conjunction_date);
+        assert!((detail.moon_age_hours - eph.moon_age_hours_geo).abs() < 1e-6);
+        assert!((detail.moon_distance_km - eph.moon_distance_km).abs() < 1e-6);
+        assert!((detail.sun_distance_km - eph.sun_distance_km).abs() < 1e-3);
+        assert!((detail.moon_semidiameter - eph.moon_semidiameter_deg).abs() < 1e-9);
+        assert!((detail.sun_semidiameter - eph.sun_semidiameter_deg).abs() < 1e-9);
+        assert!((detail.elongation - eph.elongation_topo).abs() < 0.01);
+
+        // Azimuth: LST ephemeris tanpa nutasi, selisih hanya beberapa detik busur
+        assert!((detail.moon_azimuth - eph.moon_azimuth_airless_geo).abs() < 0.01);
+        assert!((detail.sun_azimuth - eph.sun_azimuth_airless_geo).abs() < 0.01);
+
+        // Parallax bulan dari dua sumber jarak (ELP vs lunar_position)
+        assert!((detail.parallax - eph.moon_horizontal_parallax).abs() < 0.01);
+
+        // Tinggi toposentris: model refraksi/parallax VB6 sedikit berbeda (suhu 27°C vs 10°C)
+        assert!(
+            (detail.moon_altitude - eph.moon_altitude_airy_topo).abs() < 0.1,
+            "altitude {} vs {}",
+            detail.moon_altitude,
+            eph.moon_altitude_airy_topo
+        );
+
+        // Maghrib & terbenam bulan dari mesin yang sama
+        assert!((detail.sunset_time - sunset_local).abs() < 1e-9);
+        let moonset = crate::astronomy::ephemeris_utils::calculate_moonset(&location, &date);
+        assert!((detail.moonset_time - moonset).abs() < 1e-9);
+        assert!(detail.moonset_time > detail.sunset_time);
+
+        // ARCV airless toposentris ~ selisih tinggi bulan-matahari
+        assert!(detail.arcv > 0.0 && detail.arcv < detail.elongation);
+        assert!(detail.crescent_width > 0.0);
+    }
 }
@@ -0,0 +1,274 @@
+//! Visibility map command handlers
+//!
+//! Perhitungan peta berjalan di thread pool (rayon) lewat `spawn_blocking`, sehingga UI
+//! tidak membeku. Progres dikirim sebagai event `visibility-map-progress` dan job dapat
+//! dibatalkan dengan `cancel_visibility_zones` memakai `job_id` yang sama.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tauri::Emitter;
+
+use crate::astronomy::rise_set::RiseSetOptions;
+use crate::map::series::VisibilitySeries;
+use crate::map::{self, CancelToken, MapGrid, MapProgress, VisibilityZone};
+
+/// Nama event progres perhitungan peta
+pub const MAP_PROGRESS_EVENT: &str = "visibility-map-progress";
+
+/// Payload event progres (sel selesai / total)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MapProgressEvent {
+    pub job_id: Option<String>,
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Job peta yang sedang berjalan (managed state), kunci = job_id dari frontend
+#[derive(Default)]
+pub struct MapJobs(Mutex<HashMap<String, CancelToken>>);
+
+impl MapJobs {
+    /// Daftarkan job baru; job lama dengan id yang sama dibatalkan
+    fn start(&self, job_id: &str) -> CancelToken {
+        let token = CancelToken::new();
+        if let Ok(mut jobs) = self.0.lock() {
+            if let Some(previous) = jobs.insert(job_id.to_string(), token.clone()) {
+                previous.cancel();
+            }
+        }
+        token
+    }
+
+    fn finish(&self, job_id: &str, token: &CancelToken) {
+        if let Ok(mut jobs) = self.0.lock() {
+            if jobs
+                .get(job_id)
+                .is_some_and(|current| current.same_job(token))
+            {
+                jobs.remove(job_id);
+            }
+        }
+    }
+
+    fn cancel(&self, job_id: &str) -> bool {
+        match self.0.lock() {
+            Ok(jobs) => jobs.get(job_id).map(|token| token.cancel()).is_some(),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Job yang terdaftar di `MapJobs`; dilepas saat drop, termasuk saat worker gagal atau panic
+struct MapJobGuard<'a> {
+    jobs: &'a MapJobs,
+    job_id: Option<String>,
+    token: CancelToken,
+}
+
+impl<'a> MapJobGuard<'a> {
+    fn new(jobs: &'a MapJobs, job_id: Option<String>) -> Self {
+        let token = match &job_id {
+            Some(id) => jobs.start(id),
+            None => CancelToken::new(),
+        };
+        Self {
+            jobs,
+            job_id,
+            token,
+        }
+    }
+}
+
+impl Drop for MapJobGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(id) = &self.job_id {
+            self.jobs.finish(id, &self.token);
+        }
+    }
+}
+
+/// Hitung zona visibilitas untuk tanggal (RFC3339)
+///
+/// Tanpa `grid`, peta mencakup seluruh dunia dengan langkah `step_degrees`. Dengan `grid`,
+/// dipakai wilayah (bounding box) dan langkah lintang/bujur yang diberikan. `rise_set`
+/// mengatur tekanan, suhu dan jenis ufuk untuk maghrib setiap sel.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn calculate_visibility_zones(
+    app: tauri::AppHandle,
+    jobs: tauri::State<'_, MapJobs>,
+    date: String,
+    criteria: String,
+    step_degrees: f64,
+    grid: Option<MapGrid>,
+    rise_set: Option<RiseSetOptions>,
+    job_id: Option<String>,
+) -> Result<Vec<VisibilityZone>, String> {
+    let jd = crate::parse_date_to_jd_utc(&date)?;
+    let grid = grid.unwrap_or_else(|| MapGrid::uniform(step_degrees));
+    let rise_set = rise_set.unwrap_or_default();
+    run_zones_job(app, &jobs, jd, criteria, grid, rise_set, job_id).await
+}
+
+/// Hitung zona visibilitas untuk tanggal Hijriah
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn calculate_visibility_zones_hijri(
+    app: tauri::AppHandle,
+    jobs: tauri::State<'_, MapJobs>,
+    hijri_year: i32,
+    hijri_month: u8,
+    hijri_day: u8,
+    criteria: String,
+    step_degrees: f64,
+    grid: Option<MapGrid>,
+    rise_set: Option<RiseSetOptions>,
+    job_id: Option<String>,
+) -> Result<Vec<VisibilityZone>, String> {
+    let hijri_date = crate::calendar::HijriDate::new(hijri_year, hijri_month, hijri_day);
+    let gregorian_date = crate::calendar::hijri_to_gregorian(&hijri_date);
+    let jd = crate::calendar::gregorian_to_jd(&gregorian_date);
+    let grid = grid.unwrap_or_else(|| MapGrid::uniform(step_degrees));
+    let rise_set = rise_set.unwrap_or_default();
+    run_zones_job(app, &jobs, jd, criteria, grid, rise_set, job_id).await
+}
+
+/// Hitung peta visibilitas sebagai GeoJSON FeatureCollection (polygon kontur per kelas)
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn calculate_visibility_geojson(
+    app: tauri::AppHandle,
+    jobs: tauri::State<'_, MapJobs>,
+    date: String,
+    criteria: String,
+    step_degrees: f64,
+    grid: Option<MapGrid>,
+    rise_set: Option<RiseSetOptions>,
+    job_id: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let jd = crate::parse_date_to_jd_utc(&date)?;
+    let grid = grid.unwrap_or_else(|| MapGrid::uniform(step_degrees));
+    let rise_set = rise_set.unwrap_or_default();
+    let zones = run_zones_job(app, &jobs, jd, criteria.clone(), grid, rise_set, job_id).await?;
+    Ok(map::contour::visibility_geojson(&zones, &criteria, jd))
+}
+
+/// Seri peta visibilitas untuk malam-malam berturut-turut setelah ijtimak awal bulan Hijriah
+///
+/// `days` default 3 (hari ke-0, 1, 2). Hasil juga memuat malam pertama hilal terlihat per sel.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn calculate_visibility_series(
+    app: tauri::AppHandle,
+    jobs: tauri::State<'_, MapJobs>,
+    hijri_year: i32,
+    hijri_month: u8,
+    criteria: String,
+    step_degrees: f64,
+    grid: Option<MapGrid>,
+    rise_set: Option<RiseSetOptions>,
+    days: Option<u32>,
+    job_id: Option<String>,
+) -> Result<VisibilitySeries, String> {
+    let grid = grid.unwrap_or_else(|| MapGrid::uniform(step_degrees));
+    let rise_set = rise_set.unwrap_or_default();
+    let days = days.unwrap_or(map::series::DEFAULT_SERIES_DAYS);
+    run_map_job(app, &jobs, job_id, move |cancel, on_progress| {
+        map::series::calculate_visibility_series(
+            hijri_year,
+            hijri_month,
+            &criteria,
+            &grid,
+            &rise_set,
+            days,
+            cancel,
+            on_progress,
+        )
+    })
+    .await
+}
+
+/// Batalkan perhitungan peta yang sedang berjalan
+#[tauri::command]
+pub fn cancel_visibility_zones(jobs: tauri::State<'_, MapJobs>, job_id: String) -> bool {
+    jobs.cancel(&job_id)
+}
+
+/// Jalankan perhitungan peta di thread blocking dengan progres dan pembatalan per `job_id`
+async fn run_map_job<T, W>(
+    app: tauri::AppHandle,
+    jobs: &MapJobs,
+    job_id: Option<String>,
+    work: W,
+) -> Result<T, String>
+where
+    T: Send + 'static,
+    W: FnOnce(&CancelToken, &(dyn Fn(MapProgress) + Sync)) -> Result<T, String> + Send + 'static,
+{
+    let job = MapJobGuard::new(jobs, job_id.clone());
+
+    let worker_token = job.token.clone();
+    let event_job_id = job_id;
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let on_progress = |MapProgress { done, total }| {
+            let _ = app.emit(
+                MAP_PROGRESS_EVENT,
+                MapProgressEvent {
+                    job_id: event_job_id.clone(),
+                    done,
+                    total,
+                },
+            );
+        };
+        work(&worker_token, &on_progress)
+    })
+    .await;
+
+    drop(job);
+    result.map_err(|e| e.to_string())?
+}
+
+/// Peta zona visibilitas untuk satu tanggal observasi
+async fn run_zones_job(
+    app: tauri::AppHandle,
+    jobs: &MapJobs,
+    observation_jd: f64,
+    criteria: String,
+    grid: MapGrid,
+    rise_set: RiseSetOptions,
+    job_id: Option<String>,
+) -> Result<Vec<VisibilityZone>, String> {
+    run_map_job(app, jobs, job_id, move |cancel, on_progress| {
+        map::calculate_visibility_zones_parallel(
+            observation_jd,
+            &criteria,
+            &grid,
+            &rise_set,
+            cancel,
+            on_progress,
+        )
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_released_on_drop() {
+        let jobs = MapJobs::default();
+        {
+            let job = MapJobGuard::new(&jobs, Some("peta".to_string()));
+            assert!(jobs.cancel("peta"));
+            assert!(job.token.is_cancelled());
+
+            // Job baru dengan id sama tidak ikut dilepas oleh guard lama
+            let _newer = MapJobGuard::new(&jobs, Some("peta".to_string()));
+            drop(job);
+            assert!(jobs.cancel("peta"));
+        }
+        assert!(!jobs.cancel("peta"));
+    }
+}
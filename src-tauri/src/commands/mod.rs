@@ -5,11 +5,29 @@
 
 pub mod astronomical;
 pub mod calendar_cmd;
+pub mod criteria_cmd;
+pub mod export_cmd;
 pub mod hilal;
+pub mod map_cmd;
+pub mod prayer_cmd;
 pub mod validation;
 
 // Re-export untuk kemudahan akses dari lib.rs
 pub use astronomical::get_astronomical_data_command;
-pub use calendar_cmd::{gregorian_to_hijri_command, hijri_to_gregorian_command};
+pub use calendar_cmd::{
+    calendar_month_grid_command, generate_hisab_calendar_command, generate_khgt_calendar_command,
+    gregorian_to_hijri_command, gregorian_to_javanese_command, hijri_to_gregorian_command,
+    islamic_holidays_command, javanese_to_gregorian_command, umm_al_qura_hajj_dates_command,
+};
+pub use criteria_cmd::{
+    evaluate_khgt_global_command, list_criteria_command, load_custom_criteria_command,
+    remove_custom_criteria_command,
+};
+pub use export_cmd::{export_icalendar_command, export_prayer_timetable_command};
 pub use hilal::{calculate_hilal_visibility_command, calculate_hilal_visibility_hijri_command};
+pub use map_cmd::{
+    calculate_visibility_geojson, calculate_visibility_series, calculate_visibility_zones,
+    calculate_visibility_zones_hijri, cancel_visibility_zones,
+};
+pub use prayer_cmd::{prayer_methods_command, prayer_timetable_command, prayer_times_command};
 pub use validation::run_validation_tests_command;
@@ -0,0 +1,76 @@
+//! Prayer times command handlers
+
+use crate::astronomy::prayer::{PrayerConfig, PrayerMethod, PrayerSettings, PrayerTimes};
+use crate::calendar::prayer_timetable::{PrayerTimetable, TimetableRange};
+use crate::calendar::HijriCalendarKind;
+use crate::{GeoLocation, GregorianDate};
+
+/// Prayer times for one date as typed events (UTC JD, local datetime with offset and
+/// the sun's altitude/azimuth)
+///
+/// Untuk teks "HH:MM" gunakan `get_prayer_times` atau `prayer_format`.
+#[tauri::command]
+pub fn prayer_times_command(
+    location: GeoLocation,
+    year: i32,
+    month: u8,
+    day: u8,
+    method: Option<PrayerSettings>,
+) -> Result<PrayerTimes, String> {
+    if !(1..=12).contains(&month) {
+        return Err("Invalid Gregorian month (1-12)".to_string());
+    }
+    if !(1..=31).contains(&day) {
+        return Err("Invalid Gregorian day (1-31)".to_string());
+    }
+
+    let date = GregorianDate {
+        year,
+        month,
+        day: day as f64,
+    };
+    let config = method.map(|m| m.config()).unwrap_or_default();
+    Ok(crate::astronomy::prayer::calculate_prayer_times(
+        &location, &date, &config,
+    ))
+}
+
+/// Prayer timetable (jadwal imsakiyah) for a Gregorian range, a Hijri month or a Hijri year
+///
+/// Setiap baris memuat tanggal Hijriah (sesuai `calendar`, default tabular), hari dan pasaran.
+#[tauri::command]
+pub async fn prayer_timetable_command(
+    location: GeoLocation,
+    range: TimetableRange,
+    calendar: Option<HijriCalendarKind>,
+    method: Option<PrayerSettings>,
+) -> Result<PrayerTimetable, String> {
+    let calendar = calendar.unwrap_or_default();
+    let config = method.map(|m| m.config()).unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::calendar::prayer_timetable::prayer_timetable(&location, &range, &calendar, &config)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Standard prayer calculation method presets
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PrayerMethodInfo {
+    pub id: PrayerMethod,
+    pub name: String,
+    pub config: PrayerConfig,
+}
+
+/// List the prayer calculation methods (id, name and parameters) for the method picker
+#[tauri::command]
+pub fn prayer_methods_command() -> Vec<PrayerMethodInfo> {
+    PrayerMethod::ALL
+        .iter()
+        .map(|method| PrayerMethodInfo {
+            id: *method,
+            name: method.name().to_string(),
+            config: method.config(),
+        })
+        .collect()
+}
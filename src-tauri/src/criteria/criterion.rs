@@ -0,0 +1,347 @@
+// Abstraksi kriteria visibilitas hilal
+//
+// Setiap modul kriteria (mabims, odeh, yallop, turkey, khgt, lfnu, wujudul_hilal,
+// ijtima_qobla_ghurub) mengimplementasikan trait `Criterion` dan didaftarkan di
+// `criteria::registry`. Peta, tabel dan command cukup meng-enumerasi registry.
+
+use std::sync::OnceLock;
+
+use crate::astronomy::rise_set::RiseSetOptions;
+use crate::{GeoLocation, GregorianDate, JulianDay};
+
+/// Parameter hilal yang diperlukan sebuah kriteria
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CriterionInput {
+    MoonAltitude,
+    Elongation,
+    MoonAge,
+    ConjunctionTime,
+    ArcOfVision,
+    CrescentWidth,
+    LagTime,
+    Illumination,
+}
+
+/// Snapshot parameter hilal untuk satu lokasi pada satu waktu observasi
+///
+/// Dihitung sekali (maghrib, posisi bulan/matahari, koreksi toposentris) lalu dipakai
+/// bersama oleh semua kriteria. Moonset dan lag time dihitung saat pertama kali diminta.
+#[derive(Debug, Clone)]
+pub struct HilalParameters {
+    pub location: GeoLocation,
+    pub date: GregorianDate,
+    pub rise_set: RiseSetOptions, // Kondisi pengamatan untuk maghrib
+    pub conjunction_jd: JulianDay,
+    pub sunset_jd: JulianDay,
+    pub observation_jd: JulianDay, // Waktu evaluasi (default = maghrib)
+    pub moon_age_hours: f64,
+    pub moon_altitude_topo: f64, // Toposentris tampak (refraksi + parallax, VB6)
+    pub moon_altitude_geo: f64,  // Geosentris airless
+    pub sun_altitude_geo: f64,   // Geosentris airless
+    pub moon_azimuth: f64,
+    pub sun_azimuth: f64,
+    pub daz: f64,               // Beda azimuth matahari - bulan
+    pub elongation_geo: f64,    // ARCL geosentris
+    pub elongation_topo: f64,   // Elongasi toposentris
+    pub crescent_width: f64,    // Lebar hilal toposentris (arc-minutes)
+    pub arcv: f64,              // ARCV toposentris airless (derajat)
+    pub illumination: f64,      // Persen
+    pub moon_semidiameter: f64, // Derajat (VB6: 358473400 / jarak km)
+    pub moon_parallax: f64,     // Horizontal parallax (radian)
+    pub sun_distance_au: f64,
+    moonset: OnceLock<Option<JulianDay>>,
+}
+
+impl HilalParameters {
+    /// Buat snapshot untuk maghrib pada tanggal lokal `date` (`RiseSetOptions` default)
+    pub fn new(location: &GeoLocation, date: &GregorianDate, conjunction_jd: JulianDay) -> Self {
+        Self::with_rise_set(location, date, conjunction_jd, &RiseSetOptions::default())
+    }
+
+    /// Buat snapshot untuk maghrib dengan kondisi pengamatan (tekanan, suhu, ufuk) tertentu
+    pub fn with_rise_set(
+        location: &GeoLocation,
+        date: &GregorianDate,
+        conjunction_jd: JulianDay,
+        rise_set: &RiseSetOptions,
+    ) -> Self {
+        let date_only = GregorianDate {
+            year: date.year,
+            month: date.month,
+            day: date.day.floor(),
+        };
+        let sunset_jd = crate::astronomy::hilal::sunset_jd(location, &date_only, rise_set);
+        Self::compute(
+            location,
+            date_only,
+            rise_set,
+            conjunction_jd,
+            sunset_jd,
+            sunset_jd,
+        )
+    }
+
+    /// Snapshot maghrib dengan ijtimak terakhir sebelum maghrib (untuk fungsi evaluate_* lama)
+    pub fn at_sunset(location: &GeoLocation, date: &GregorianDate) -> Self {
+        let sunset_jd =
+            crate::astronomy::hilal::sunset_jd(location, date, &RiseSetOptions::default());
+        let sunset_date = crate::calendar::jd_to_gregorian(sunset_jd);
+        let conjunction = crate::astronomy::find_conjunction_before(&sunset_date);
+        Self::new(location, date, conjunction.jd_utc)
+    }
+
+    /// Snapshot yang sama (lokasi, tanggal, maghrib) dievaluasi ulang pada `observation_jd`
+    pub fn at_time(&self, observation_jd: JulianDay) -> Self {
+        let params = Self::compute(
+            &self.location,
+            self.date.clone(),
+            &self.rise_set,
+            self.conjunction_jd,
+            self.sunset_jd,
+            observation_jd,
+        );
+        if let Some(moonset) = self.moonset.get() {
+            let _ = params.moonset.set(*moonset);
+        }
+        params
+    }
+
+    /// Apakah matahari terbenam pada tanggal ini (tidak untuk siang/malam kutub)
+    pub fn has_sunset(&self) -> bool {
+        self.sunset_jd.is_finite()
+            && self.sunset_jd - crate::calendar::gregorian_to_jd(&self.date) < 2.0
+    }
+
+    /// Moonset (JD UT) terdekat dengan maghrib, None jika bulan tidak terbenam
+    pub fn moonset_jd(&self) -> Option<JulianDay> {
+        *self.moonset.get_or_init(|| {
+            crate::astronomy::ephemeris_utils::find_moonset_jd(&self.location, self.sunset_jd)
+        })
+    }
+
+    /// Lag time: moonset - maghrib (menit, negatif jika bulan terbenam lebih dulu)
+    pub fn lag_minutes(&self) -> Option<f64> {
+        self.moonset_jd()
+            .map(|moonset_jd| (moonset_jd - self.sunset_jd) * 1440.0)
+    }
+
+    fn compute(
+        location: &GeoLocation,
+        date: GregorianDate,
+        rise_set: &RiseSetOptions,
+        conjunction_jd: JulianDay,
+        sunset_jd: JulianDay,
+        observation_jd: JulianDay,
+    ) -> Self {
+        use crate::astronomy::{self, coordinates, ephemeris_utils, topocentric};
+
+        let jd = observation_jd;
+        let moon = astronomy::moon_position(jd);
+        let sun = astronomy::sun_position(jd);
+        let lst_deg = topocentric::local_sidereal_time_hours(location.longitude, jd) * 15.0;
+
+        let moon_ha = lst_deg - moon.right_ascension;
+        let sun_ha = lst_deg - sun.right_ascension;
+        let moon_altitude_geo =
+            coordinates::altitude_from_hour_angle(location.latitude, moon_ha, moon.declination);
+        let sun_altitude_geo =
+            coordinates::altitude_from_hour_angle(location.latitude, sun_ha, sun.declination);
+        let moon_azimuth =
+            ephemeris_utils::calculate_azimuth(moon_ha, moon.declination, location.latitude);
+        let sun_azimuth =
+            ephemeris_utils::calculate_azimuth(sun_ha, sun.declination, location.latitude);
+
+        let moon_parallax = astronomy::horizontal_moon_parallax(jd);
+        let elongation_topo = topocentric::elongation_topocentric(location, jd);
+        let illumination = astronomy::hilal::illumination_at_jd(jd);
+
+        Self {
+            location: location.clone(),
+            date,
+            rise_set: rise_set.clone(),
+            conjunction_jd,
+            sunset_jd,
+            observation_jd,
+            moon_age_hours: (jd - conjunction_jd) * 24.0,
+            moon_altitude_topo: topocentric::moon_altitude_topocentric(location, jd),
+            moon_altitude_geo,
+            sun_altitude_geo,
+            moon_azimuth,
+            sun_azimuth,
+            daz: sun_azimuth - moon_azimuth,
+            elongation_geo: coordinates::angular_separation(
+                moon.right_ascension,
+                moon.declination,
+                sun.right_ascension,
+                sun.declination,
+            ),
+            elongation_topo,
+            crescent_width: astronomy::hilal::crescent_width_from(
+                elongation_topo,
+                illumination / 100.0,
+            ),
+            arcv: astronomy::calculate_arcv(
+                moon_altitude_geo,
+                sun_altitude_geo,
+                moon_parallax,
+                sun.distance,
+            ),
+            illumination,
+            moon_semidiameter: (358473400.0 / moon.distance) / 3600.0,
+            moon_parallax,
+            sun_distance_au: sun.distance,
+            moonset: OnceLock::new(),
+        }
+    }
+}
+
+/// Hasil evaluasi satu kriteria
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CriterionOutcome {
+    pub is_visible: bool,
+    pub visibility_type: String,
+    pub q_value: Option<f64>,
+    pub level: i32, // Level peta: 0 Impossible ... 4 Easy
+    pub additional_info: String,
+}
+
+impl CriterionOutcome {
+    /// Outcome untuk kriteria biner (terlihat / tidak)
+    pub fn binary(is_visible: bool, additional_info: String) -> Self {
+        Self {
+            is_visible,
+            visibility_type: if is_visible {
+                "Visible".to_string()
+            } else {
+                "Not Visible".to_string()
+            },
+            q_value: None,
+            level: if is_visible { 3 } else { 0 },
+            additional_info,
+        }
+    }
+}
+
+/// Kelas peta kriteria biner (level `CriterionOutcome::binary`)
+pub const BINARY_CLASSES: &[(i32, &str)] = &[(3, "visible"), (0, "not_visible")];
+
+/// Kriteria visibilitas hilal
+pub trait Criterion: Send + Sync {
+    /// Kunci registry (misal: "MABIMS", "Odeh")
+    fn id(&self) -> &str;
+
+    /// Nama tampilan
+    fn name(&self) -> &str;
+
+    /// Deskripsi singkat ambang batas
+    fn description(&self) -> &str;
+
+    /// Parameter hilal yang dipakai kriteria ini
+    fn inputs(&self) -> &[CriterionInput];
+
+    /// Evaluasi kriteria pada parameter yang diberikan
+    fn evaluate(&self, params: &HilalParameters) -> CriterionOutcome;
+
+    /// Kelas peta (level minimum, label), urut dari kelas tertinggi. Kriteria bergradasi
+    /// memakai zonanya sendiri (Odeh A-D, Yallop A-F); default biner terlihat / tidak.
+    fn classes(&self) -> &[(i32, &'static str)] {
+        BINARY_CLASSES
+    }
+}
+
+/// Ringkasan kriteria untuk frontend (daftar pilihan kriteria)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CriterionInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub inputs: Vec<CriterionInput>,
+}
+
+impl CriterionInfo {
+    pub fn from_criterion(criterion: &dyn Criterion) -> Self {
+        Self {
+            id: criterion.id().to_string(),
+            name: criterion.name().to_string(),
+            description: criterion.description().to_string(),
+            inputs: criterion.inputs().to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_matches_at_sunset_functions() {
+        let location = GeoLocation {
+            name: Some("Sukabumi".to_string()),
+            latitude: -7.0739,
+            longitude: 106.5314,
+            elevation: 10.0,
+            timezone: 7.0,
+        };
+        let date = GregorianDate {
+            year: 2026,
+            month: 2,
+            day: 18.0,
+        };
+        let conjunction = crate::astronomy::find_conjunction(&date);
+        let params = HilalParameters::new(&location, &date, conjunction.jd_utc);
+
+        let altitude = crate::astronomy::altitude_at_sunset(&location, &date, true);
+        let elongation = crate::astronomy::elongation_at_sunset(&location, &date, true);
+        let width = crate::astronomy::crescent_width_at_sunset(&location, &date, true);
+        assert!((params.moon_altitude_topo - altitude).abs() < 1e-9);
+        assert!((params.elongation_topo - elongation).abs() < 1e-9);
+        assert!((params.crescent_width - width).abs() < 1e-9);
+
+        // Hilal 18 Feb 2026 terbenam setelah matahari
+        let lag = params.lag_minutes().unwrap();
+        assert!(lag > 0.0 && lag < 120.0, "Lag {:.1} menit", lag);
+
+        // Evaluasi ulang pada waktu lain mempertahankan maghrib dan moonset
+        let later = params.at_time(params.sunset_jd + 20.0 / 1440.0);
+        assert_eq!(later.sunset_jd, params.sunset_jd);
+        assert_eq!(later.moonset_jd(), params.moonset_jd());
+        assert!(later.moon_altitude_topo < params.moon_altitude_topo);
+    }
+
+    #[test]
+    fn test_rise_set_options_move_sunset() {
+        let condrodipo = GeoLocation {
+            name: Some("Bukit Condrodipo".to_string()),
+            latitude: -7.1667,
+            longitude: 112.6167,
+            elevation: 100.0,
+            timezone: 7.0,
+        };
+        let date = GregorianDate {
+            year: 2026,
+            month: 2,
+            day: 18.0,
+        };
+        let conjunction_jd = crate::astronomy::find_conjunction(&date).jd_utc;
+
+        // Default: dip dari ketinggian lokasi, maghrib lebih lambat dari ufuk daratan 0 m
+        let ocean = HilalParameters::new(&condrodipo, &date, conjunction_jd);
+        let ground = HilalParameters::with_rise_set(
+            &condrodipo,
+            &date,
+            conjunction_jd,
+            &RiseSetOptions {
+                horizon: crate::astronomy::rise_set::Horizon::Terrain { height: 0.0 },
+                ..RiseSetOptions::default()
+            },
+        );
+        let later = (ocean.sunset_jd - ground.sunset_jd) * 1440.0;
+        assert!(later > 1.0 && later < 3.0, "{}", later);
+        assert!(ocean.moon_altitude_topo < ground.moon_altitude_topo);
+
+        // Kondisi pengamatan ikut terbawa saat dievaluasi ulang
+        let moved = ground.at_time(ground.sunset_jd + 0.01);
+        assert_eq!(moved.rise_set, ground.rise_set);
+    }
+}
@@ -7,6 +7,7 @@
 // Ini adalah kriteria paling sederhana yang hanya mengecek apakah konjungsi
 // terjadi sebelum terbenamnya matahari pada tanggal pengamatan
 
+use super::criterion::{Criterion, CriterionInput, CriterionOutcome, HilalParameters};
 use crate::{GeoLocation, GregorianDate};
 
 /// Struktur hasil evaluasi Ijtima Qobla Ghurub
@@ -27,14 +28,14 @@ pub fn evaluate_ijtima_qobla_ghurub(
     date: &GregorianDate,
     conjunction_jd: f64,
 ) -> IjtimaQoblaGhuribResult {
-    let jd = crate::calendar::gregorian_to_jd(date);
-
-    // Hitung waktu maghrib dalam Julian Day (Convert to UTC)
-    let sunset_hour = crate::astronomy::calculate_sunset(location, date);
-    let sunset_hour_ut = sunset_hour - location.timezone;
-    let maghrib_jd = jd + (sunset_hour_ut / 24.0);
+    evaluate_ijtima_qobla_ghurub_from(&HilalParameters::new(location, date, conjunction_jd))
+}
 
-    // Kondisi: Ijtimak sebelum maghrib
+/// Evaluasi Ijtima Qobla Ghurub dari snapshot parameter hilal
+pub fn evaluate_ijtima_qobla_ghurub_from(params: &HilalParameters) -> IjtimaQoblaGhuribResult {
+    // Kondisi: Ijtimak sebelum maghrib (JD UT)
+    let conjunction_jd = params.conjunction_jd;
+    let maghrib_jd = params.sunset_jd;
     let ijtimak_before_maghrib = conjunction_jd < maghrib_jd;
 
     IjtimaQoblaGhuribResult {
@@ -45,6 +46,35 @@ pub fn evaluate_ijtima_qobla_ghurub(
     }
 }
 
+/// Ijtima Qobla Ghurub
+pub struct IjtimaQoblaGhurub;
+
+impl Criterion for IjtimaQoblaGhurub {
+    fn id(&self) -> &str {
+        "IjtimaQoblaGhurub"
+    }
+
+    fn name(&self) -> &str {
+        "Ijtima Qobla Ghurub"
+    }
+
+    fn description(&self) -> &str {
+        "Ijtimak sebelum maghrib"
+    }
+
+    fn inputs(&self) -> &[CriterionInput] {
+        &[CriterionInput::ConjunctionTime]
+    }
+
+    fn evaluate(&self, params: &HilalParameters) -> CriterionOutcome {
+        let result = evaluate_ijtima_qobla_ghurub_from(params);
+        CriterionOutcome::binary(
+            result.is_visible,
+            format!("Ijtimak sebelum maghrib: {}", result.ijtimak_before_maghrib),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
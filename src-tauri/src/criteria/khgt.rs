@@ -5,9 +5,45 @@
 // - Tinggi bulan ≥ 5°
 // - Elongasi ≥ 8°
 //
+// Keputusan global (kalender tunggal):
+// - Bulan baru dimulai esok hari di seluruh dunia jika kriteria terpenuhi di mana pun
+//   sebelum 24:00 UTC pada hari ijtimak
+// - Pengecualian Amerika: jika kriteria baru terpenuhi setelah 24:00 UTC, bulan tetap
+//   dimulai esok hari asalkan kriteria terpenuhi di daratan Amerika pada malam yang sama
+//   (sebelum tanggal hari ijtimak berakhir di UTC-12) dan ijtimak terjadi sebelum fajar
+//   di Selandia Baru
+// - Selain itu bulan dimulai lusa
 
+use rayon::prelude::*;
+
+use super::criterion::{Criterion, CriterionInput, CriterionOutcome, HilalParameters};
+use crate::map::MapGrid;
 use crate::{GeoLocation, GregorianDate};
 
+/// Langkah grid default (derajat) untuk penyapuan global
+pub const KHGT_GRID_STEP: f64 = 2.0;
+
+/// Batas waktu pengecualian Amerika (jam setelah 24:00 UTC): tanggal hari ijtimak berakhir
+/// di zona UTC-12 pada 12:00 UTC keesokan harinya
+const AMERICAS_TIME_LIMIT_HOURS: f64 = 12.0;
+
+/// Sudut fajar (Muhammadiyah) untuk syarat pengecualian Amerika
+const FAJR_ANGLE: f64 = -18.0;
+
+/// Lokasi acuan Selandia Baru (Wellington)
+const NEW_ZEALAND_LATITUDE: f64 = -41.2865;
+const NEW_ZEALAND_LONGITUDE: f64 = 174.7762;
+
+/// Perkiraan daratan Amerika (lat_min, lat_max, lon_min, lon_max)
+const AMERICAS_LAND: [(f64, f64, f64, f64); 6] = [
+    (54.0, 72.0, -168.0, -141.0), // Alaska
+    (48.0, 72.0, -141.0, -52.0),  // Kanada
+    (25.0, 49.0, -125.0, -67.0),  // Amerika Serikat
+    (14.0, 33.0, -118.0, -86.0),  // Meksiko
+    (7.0, 18.0, -92.0, -77.0),    // Amerika Tengah
+    (-56.0, 13.0, -82.0, -34.0),  // Amerika Selatan
+];
+
 /// Struktur hasil evaluasi KHGT criteria
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct KhgtResult {
@@ -29,26 +65,22 @@ pub fn evaluate_khgt(
     date: &GregorianDate,
     _use_topocentric: bool, // parameter is ignored, we force requirements
 ) -> KhgtResult {
-    // 1. Hitung altitude bulan pada saat maghrib (Toposentrik)
-    let moon_altitude = crate::astronomy::altitude_at_sunset(location, date, true);
+    evaluate_khgt_from(&HilalParameters::at_sunset(location, date))
+}
 
-    // 2. Hitung elongasi pada saat maghrib (Geosentrik)
-    let elongation = crate::astronomy::elongation_at_sunset(location, date, false);
+/// Evaluasi kriteria KHGT dari snapshot parameter hilal
+pub fn evaluate_khgt_from(params: &HilalParameters) -> KhgtResult {
+    // 1. Altitude bulan pada saat maghrib (Toposentrik)
+    let moon_altitude = params.moon_altitude_topo;
+
+    // 2. Elongasi pada saat maghrib (Geosentrik)
+    let elongation = params.elongation_geo;
 
     // 3. Periksa waktu ijtimak
     // Ijtimak harus terjadi sebelum 00:00 UTC hari yang sama (tengah malam transisi ke hari berikutnya)
-    let jd_start = crate::calendar::gregorian_to_jd(date); // JD at 00:00 UTC
+    let jd_start = crate::calendar::gregorian_to_jd(&params.date); // JD at 00:00 UTC
     let midnight_end_utc = jd_start + 1.0;
-
-    // Cari ijtimak terdekat sebelum maghrib
-    // Gunakan JD maghrib sebagai batas atas
-    let sunset_hour = crate::astronomy::calculate_sunset(location, date);
-    let sunset_jd = jd_start + ((sunset_hour - location.timezone) / 24.0);
-
-    // Konversi sunset_jd ke GregorianDate untuk find_conjunction_before
-    let sunset_date = crate::calendar::jd_to_gregorian(sunset_jd);
-    let conj = crate::astronomy::find_conjunction_before(&sunset_date);
-    let ijtima_ok = conj.jd_utc < midnight_end_utc;
+    let ijtima_ok = params.conjunction_jd < midnight_end_utc;
 
     // Evaluasi kriteria KHGT
     let altitude_ok = moon_altitude >= 5.0;
@@ -66,3 +98,326 @@ pub fn evaluate_khgt(
         ijtima_ok,
     }
 }
+
+/// KHGT (Kalender Hijriah Global Tunggal)
+pub struct Khgt;
+
+impl Criterion for Khgt {
+    fn id(&self) -> &str {
+        "KHGT"
+    }
+
+    fn name(&self) -> &str {
+        "KHGT (Kalender Hijriah Global Tunggal)"
+    }
+
+    fn description(&self) -> &str {
+        "Alt ≥ 5°, Elong ≥ 8°, ijtimak sebelum 00:00 UTC"
+    }
+
+    fn inputs(&self) -> &[CriterionInput] {
+        &[
+            CriterionInput::MoonAltitude,
+            CriterionInput::Elongation,
+            CriterionInput::ConjunctionTime,
+        ]
+    }
+
+    fn evaluate(&self, params: &HilalParameters) -> CriterionOutcome {
+        let result = evaluate_khgt_from(params);
+        CriterionOutcome::binary(
+            result.is_visible,
+            format!(
+                "Altitude: {:.2}°, Elongation: {:.2}°, Ijtima OK: {}",
+                result.moon_altitude, result.elongation, result.ijtima_ok
+            ),
+        )
+    }
+}
+
+/// Jenis keputusan global KHGT
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KhgtDecision {
+    /// Kriteria terpenuhi di suatu tempat sebelum 24:00 UTC
+    Global,
+    /// Kriteria terpenuhi di daratan Amerika setelah 24:00 UTC, ijtimak sebelum fajar Selandia Baru
+    AmericasException,
+    /// Kriteria tidak terpenuhi: bulan dimulai lusa
+    NotMet,
+}
+
+/// Lokasi pertama (maghrib paling awal) yang memenuhi kriteria
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct KhgtDecidingLocation {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub sunset_jd: f64,
+    pub sunset: String, // UTC
+    pub moon_altitude: f64,
+    pub elongation: f64,
+}
+
+/// Hasil evaluasi KHGT global untuk satu ijtimak
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct KhgtGlobalResult {
+    pub conjunction_jd: f64,
+    pub conjunction: String,            // UTC
+    pub evaluation_date: GregorianDate, // Hari ijtimak (UTC)
+    pub decision: KhgtDecision,
+    pub deciding_location: Option<KhgtDecidingLocation>,
+    pub new_zealand_fajr_jd: f64,
+    pub conjunction_before_nz_fajr: bool,
+    pub month_start: GregorianDate, // Tanggal 1 bulan baru (seluruh dunia)
+}
+
+/// Satu bulan kalender KHGT
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct KhgtMonth {
+    pub hijri_year: i32,
+    pub hijri_month: u8,
+    pub length: u8,
+    pub result: KhgtGlobalResult,
+}
+
+/// Evaluasi KHGT global untuk ijtimak `conjunction_jd` dengan menyapu grid peta
+pub fn evaluate_khgt_global(
+    conjunction_jd: f64,
+    grid: &MapGrid,
+) -> Result<KhgtGlobalResult, String> {
+    grid.validate()?;
+
+    let day_jd = (conjunction_jd - 0.5).floor() + 0.5; // 00:00 UTC hari ijtimak
+    let midnight_utc = day_jd + 1.0;
+    let evaluation_date = crate::calendar::jd_to_gregorian(day_jd);
+
+    // Semua titik yang memenuhi kriteria pada maghrib hari ijtimak (tanggal lokal)
+    let cells: Vec<(f64, f64)> = grid.cells().collect();
+    let candidates: Vec<KhgtDecidingLocation> = cells
+        .into_par_iter()
+        .filter_map(|(latitude, longitude)| {
+            let location = crate::map::grid_location(latitude, longitude);
+            let params = HilalParameters::new(&location, &evaluation_date, conjunction_jd);
+            if !params.has_sunset() || params.conjunction_jd >= params.sunset_jd {
+                return None;
+            }
+            let result = evaluate_khgt_from(&params);
+            (result.altitude_ok && result.elongation_ok).then(|| KhgtDecidingLocation {
+                latitude,
+                longitude,
+                sunset_jd: params.sunset_jd,
+                sunset: crate::astronomy::ephemeris_utils::format_jd_to_datetime(
+                    params.sunset_jd,
+                    0.0,
+                ),
+                moon_altitude: result.moon_altitude,
+                elongation: result.elongation,
+            })
+        })
+        .collect();
+
+    let new_zealand_fajr_jd = new_zealand_fajr_jd(day_jd + 1.0);
+    let conjunction_before_nz_fajr = conjunction_jd < new_zealand_fajr_jd;
+
+    let (decision, deciding_location) =
+        decide(&candidates, midnight_utc, conjunction_before_nz_fajr);
+
+    let start_offset = if decision == KhgtDecision::NotMet {
+        2.0
+    } else {
+        1.0
+    };
+
+    Ok(KhgtGlobalResult {
+        conjunction_jd,
+        conjunction: crate::astronomy::ephemeris_utils::format_jd_to_datetime(conjunction_jd, 0.0),
+        evaluation_date,
+        decision,
+        deciding_location,
+        new_zealand_fajr_jd,
+        conjunction_before_nz_fajr,
+        month_start: crate::calendar::jd_to_gregorian(day_jd + start_offset),
+    })
+}
+
+/// Evaluasi KHGT global untuk awal bulan Hijriah (ijtimak di sekitar tanggal 1 tabular)
+pub fn khgt_month_start(
+    hijri_year: i32,
+    hijri_month: u8,
+    grid: &MapGrid,
+) -> Result<KhgtGlobalResult, String> {
+    if !(1..=12).contains(&hijri_month) {
+        return Err("Hijri month must be between 1 and 12".to_string());
+    }
+    let tabular = crate::calendar::hijri_to_gregorian(&crate::calendar::HijriDate::new(
+        hijri_year,
+        hijri_month,
+        1,
+    ));
+    let conjunction = crate::astronomy::conjunction::find_conjunction(&tabular);
+    evaluate_khgt_global(conjunction.jd_utc, grid)
+}
+
+/// Kalender KHGT satu tahun Hijriah (12 bulan, umur bulan dari awal bulan berikutnya)
+pub fn generate_khgt_calendar(hijri_year: i32, grid: &MapGrid) -> Result<Vec<KhgtMonth>, String> {
+    let mut starts = Vec::with_capacity(13);
+    for index in 0..13 {
+        let (year, month) = (hijri_year + index / 12, (index % 12) as u8 + 1);
+        starts.push((year, month, khgt_month_start(year, month, grid)?));
+    }
+
+    Ok(starts
+        .windows(2)
+        .map(|pair| {
+            let (year, month, result) = &pair[0];
+            let next_start = crate::calendar::gregorian_to_jd(&pair[1].2.month_start);
+            let start = crate::calendar::gregorian_to_jd(&result.month_start);
+            KhgtMonth {
+                hijri_year: *year,
+                hijri_month: *month,
+                length: (next_start - start).round() as u8,
+                result: result.clone(),
+            }
+        })
+        .collect())
+}
+
+/// Keputusan global dari lokasi yang memenuhi kriteria (maghrib paling awal menentukan)
+fn decide(
+    candidates: &[KhgtDecidingLocation],
+    midnight_utc: f64,
+    conjunction_before_nz_fajr: bool,
+) -> (KhgtDecision, Option<KhgtDecidingLocation>) {
+    let earliest = |filter: &dyn Fn(&KhgtDecidingLocation) -> bool| {
+        candidates
+            .iter()
+            .filter(|c| filter(c))
+            .min_by(|a, b| a.sunset_jd.total_cmp(&b.sunset_jd))
+            .cloned()
+    };
+    let americas_limit = midnight_utc + AMERICAS_TIME_LIMIT_HOURS / 24.0;
+
+    if let Some(location) = earliest(&|c| c.sunset_jd < midnight_utc) {
+        (KhgtDecision::Global, Some(location))
+    } else if let Some(location) = earliest(&|c| {
+        conjunction_before_nz_fajr
+            && c.sunset_jd < americas_limit
+            && is_americas_land(c.latitude, c.longitude)
+    }) {
+        (KhgtDecision::AmericasException, Some(location))
+    } else {
+        (KhgtDecision::NotMet, None)
+    }
+}
+
+/// Apakah titik berada di (perkiraan) daratan Amerika
+fn is_americas_land(latitude: f64, longitude: f64) -> bool {
+    AMERICAS_LAND
+        .iter()
+        .any(|&(lat_min, lat_max, lon_min, lon_max)| {
+            (lat_min..=lat_max).contains(&latitude) && (lon_min..=lon_max).contains(&longitude)
+        })
+}
+
+/// Waktu fajar (JD UT) di Selandia Baru pada tanggal lokal yang dimulai di `local_day_jd`
+fn new_zealand_fajr_jd(local_day_jd: f64) -> f64 {
+    // Perkiraan awal fajar ~05:00 waktu lokal rata-rata, lalu dihitung ulang satu kali
+    let mut fajr_jd = local_day_jd + 5.0 / 24.0 - NEW_ZEALAND_LONGITUDE / 360.0;
+    for _ in 0..2 {
+        let declination = crate::astronomy::declination(fajr_jd).to_radians();
+        let eq_of_time = crate::astronomy::equation_of_time(fajr_jd);
+        let lat = NEW_ZEALAND_LATITUDE.to_radians();
+        let cos_h = (FAJR_ANGLE.to_radians().sin() - lat.sin() * declination.sin())
+            / (lat.cos() * declination.cos());
+        let hour_angle = cos_h.clamp(-1.0, 1.0).acos().to_degrees() / 15.0;
+        fajr_jd =
+            local_day_jd + (12.0 - eq_of_time - hour_angle) / 24.0 - NEW_ZEALAND_LONGITUDE / 360.0;
+    }
+    fajr_jd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_khgt_global_1447() {
+        let grid = MapGrid::uniform(5.0);
+
+        // Ijtimak 19 Mar 2026 01:23 UT: terpenuhi sebelum 24:00 UTC -> 1 Syawal 20 Mar 2026
+        let shawwal = khgt_month_start(1447, 10, &grid).unwrap();
+        assert_eq!(shawwal.decision, KhgtDecision::Global);
+        assert!(shawwal.deciding_location.is_some());
+        assert_eq!(
+            (
+                shawwal.month_start.year,
+                shawwal.month_start.month,
+                shawwal.month_start.day
+            ),
+            (2026, 3, 20.0)
+        );
+
+        // Ijtimak 17 Feb 2026 12:01 UT (gerhana): kriteria baru terpenuhi di Alaska setelah
+        // 24:00 UTC, ijtimak sebelum fajar Selandia Baru -> 1 Ramadan 18 Feb 2026
+        let alaska = MapGrid {
+            bounds: crate::map::MapBounds {
+                lat_min: 50.0,
+                lat_max: 72.0,
+                lon_min: -170.0,
+                lon_max: -140.0,
+            },
+            lat_step: 2.0,
+            lon_step: 2.0,
+        };
+        let ramadan = khgt_month_start(1447, 9, &alaska).unwrap();
+        assert_eq!(ramadan.decision, KhgtDecision::AmericasException);
+        assert!(ramadan.conjunction_before_nz_fajr);
+        assert_eq!(
+            (
+                ramadan.month_start.year,
+                ramadan.month_start.month,
+                ramadan.month_start.day
+            ),
+            (2026, 2, 18.0)
+        );
+
+        // Fajar Selandia Baru jatuh sekitar 16-18 UT hari sebelumnya
+        let fajr_hours = (shawwal.new_zealand_fajr_jd - 0.5).rem_euclid(1.0) * 24.0;
+        assert!((15.0..19.0).contains(&fajr_hours), "{}", fajr_hours);
+    }
+
+    #[test]
+    fn test_americas_exception_time_limit() {
+        let midnight_utc = 2461089.5; // 24:00 UTC 17 Feb 2026
+        let candidate = |latitude, longitude, hours_after_midnight: f64| KhgtDecidingLocation {
+            latitude,
+            longitude,
+            sunset_jd: midnight_utc + hours_after_midnight / 24.0,
+            sunset: String::new(),
+            moon_altitude: 5.5,
+            elongation: 8.5,
+        };
+
+        // Daratan Amerika setelah 24:00 UTC, masih dalam batas waktu
+        let chile = [candidate(-33.0, -71.0, 0.5)];
+        assert_eq!(
+            decide(&chile, midnight_utc, true).0,
+            KhgtDecision::AmericasException
+        );
+        assert_eq!(decide(&chile, midnight_utc, false).0, KhgtDecision::NotMet);
+
+        // Melewati batas waktu (tanggal hari ijtimak sudah berakhir di UTC-12)
+        let late = [candidate(60.0, -150.0, 12.5)];
+        assert_eq!(decide(&late, midnight_utc, true).0, KhgtDecision::NotMet);
+
+        // Setelah 24:00 UTC di luar daratan Amerika
+        let pacific = [candidate(-15.0, -140.0, 3.0)];
+        assert_eq!(decide(&pacific, midnight_utc, true).0, KhgtDecision::NotMet);
+
+        // Sebelum 24:00 UTC di mana pun: keputusan global, lokasi paling awal
+        let both = [candidate(-33.0, -71.0, 0.5), candidate(-6.0, 30.0, -6.0)];
+        let (decision, location) = decide(&both, midnight_utc, false);
+        assert_eq!(decision, KhgtDecision::Global);
+        assert_eq!(location.unwrap().longitude, 30.0);
+    }
+}
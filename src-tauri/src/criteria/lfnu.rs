@@ -8,6 +8,7 @@
 // Kriteria LFNU sama dengan MABIMS (Lama) untuk praktisnya
 // Referensi: PBNU/Nahdlatul Ulama
 
+use super::criterion::{Criterion, CriterionInput, CriterionOutcome, HilalParameters};
 use crate::{GeoLocation, GregorianDate};
 
 /// Struktur hasil evaluasi LFNU
@@ -25,11 +26,14 @@ pub struct LfnuResult {
 /// Kriteria LFNU dari Lembaga Falakiyah Nahdlatul Ulama
 /// Standar praktis yang sama dengan MABIMS (Lama)
 pub fn evaluate_lfnu(location: &GeoLocation, date: &GregorianDate) -> LfnuResult {
-    // Hitung altitude bulan pada saat maghrib (TOPOCENTRIC)
-    let moon_altitude = crate::astronomy::altitude_at_sunset(location, date, true);
+    evaluate_lfnu_from(&HilalParameters::at_sunset(location, date))
+}
 
-    // Hitung elongasi pada saat maghrib (TOPOCENTRIC)
-    let elongation = crate::astronomy::elongation_at_sunset(location, date, true);
+/// Evaluasi kriteria LFNU dari snapshot parameter hilal
+pub fn evaluate_lfnu_from(params: &HilalParameters) -> LfnuResult {
+    // Altitude dan elongasi pada saat maghrib (TOPOCENTRIC)
+    let moon_altitude = params.moon_altitude_topo;
+    let elongation = params.elongation_topo;
 
     // Evaluasi kriteria LFNU
     let altitude_ok = moon_altitude >= 2.0;
@@ -45,6 +49,38 @@ pub fn evaluate_lfnu(location: &GeoLocation, date: &GregorianDate) -> LfnuResult
     }
 }
 
+/// LFNU (Lembaga Falakiyah NU)
+pub struct Lfnu;
+
+impl Criterion for Lfnu {
+    fn id(&self) -> &str {
+        "LFNU"
+    }
+
+    fn name(&self) -> &str {
+        "LFNU (Lembaga Falakiyah NU)"
+    }
+
+    fn description(&self) -> &str {
+        "Alt ≥ 2°, Elong ≥ 3° (toposentris)"
+    }
+
+    fn inputs(&self) -> &[CriterionInput] {
+        &[CriterionInput::MoonAltitude, CriterionInput::Elongation]
+    }
+
+    fn evaluate(&self, params: &HilalParameters) -> CriterionOutcome {
+        let result = evaluate_lfnu_from(params);
+        CriterionOutcome::binary(
+            result.is_visible,
+            format!(
+                "Altitude: {:.2}°, Elongation: {:.2}°",
+                result.moon_altitude, result.elongation
+            ),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,6 @@
 // Module untuk kriteria visibilitas MABIMS
 
+use super::criterion::{Criterion, CriterionInput, CriterionOutcome, HilalParameters};
 use crate::{Degrees, GeoLocation, JulianDay};
 
 /// Struktur hasil evaluasi kriteria MABIMS
@@ -93,3 +94,89 @@ pub fn evaluate_new_mabims(
         moon_age_hours: moon_age,
     }
 }
+
+/// Evaluasi MABIMS Lama (2-3-8) dari snapshot parameter hilal (elongasi geosentris)
+pub fn evaluate_criteria_from(params: &HilalParameters) -> MabimsResult {
+    let is_visible = params.moon_altitude_topo >= 2.0
+        && params.elongation_geo >= 3.0
+        && params.moon_age_hours >= 8.0;
+    mabims_result(params, is_visible)
+}
+
+/// Evaluasi MABIMS Baru (3-6.4) dari snapshot parameter hilal (elongasi geosentris)
+pub fn evaluate_new_mabims_from(params: &HilalParameters) -> MabimsResult {
+    let is_visible = params.moon_altitude_topo >= 3.0 && params.elongation_geo >= 6.4;
+    mabims_result(params, is_visible)
+}
+
+fn mabims_result(params: &HilalParameters, is_visible: bool) -> MabimsResult {
+    MabimsResult {
+        is_visible,
+        moon_altitude: params.moon_altitude_topo,
+        geocentric_elongation: params.elongation_geo,
+        moon_age_hours: params.moon_age_hours,
+    }
+}
+
+/// MABIMS (2-3-8)
+pub struct Mabims;
+
+impl Criterion for Mabims {
+    fn id(&self) -> &str {
+        "MABIMS"
+    }
+
+    fn name(&self) -> &str {
+        "MABIMS"
+    }
+
+    fn description(&self) -> &str {
+        "Alt ≥ 2°, Elong ≥ 3°, Age ≥ 8h"
+    }
+
+    fn inputs(&self) -> &[CriterionInput] {
+        &[
+            CriterionInput::MoonAltitude,
+            CriterionInput::Elongation,
+            CriterionInput::MoonAge,
+        ]
+    }
+
+    fn evaluate(&self, params: &HilalParameters) -> CriterionOutcome {
+        let result = evaluate_criteria_from(params);
+        CriterionOutcome::binary(result.is_visible, mabims_info(&result))
+    }
+}
+
+/// Neo-MABIMS (3-6.4)
+pub struct NeoMabims;
+
+impl Criterion for NeoMabims {
+    fn id(&self) -> &str {
+        "NEO_MABIMS"
+    }
+
+    fn name(&self) -> &str {
+        "Neo-MABIMS (3-6.4)"
+    }
+
+    fn description(&self) -> &str {
+        "Alt ≥ 3°, Elong ≥ 6.4°"
+    }
+
+    fn inputs(&self) -> &[CriterionInput] {
+        &[CriterionInput::MoonAltitude, CriterionInput::Elongation]
+    }
+
+    fn evaluate(&self, params: &HilalParameters) -> CriterionOutcome {
+        let result = evaluate_new_mabims_from(params);
+        CriterionOutcome::binary(result.is_visible, mabims_info(&result))
+    }
+}
+
+fn mabims_info(result: &MabimsResult) -> String {
+    format!(
+        "Altitude: {:.2}°, Elongation: {:.2}°, Age: {:.1}h",
+        result.moon_altitude, result.geocentric_elongation, result.moon_age_hours
+    )
+}
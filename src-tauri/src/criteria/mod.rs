@@ -4,23 +4,31 @@
 //! yang digunakan oleh berbagai organisasi Islam di seluruh dunia
 
 pub mod additional;
+pub mod criterion;
+pub mod custom;
 pub mod ijtima_qobla_ghurub;
 pub mod khgt;
 pub mod lfnu;
 pub mod mabims;
 pub mod odeh;
+pub mod registry;
 pub mod turkey;
 pub mod wujudul_hilal;
+pub mod yallop;
 
 // Re-export tipe data dan fungsi
 pub use additional::{evaluate_kig, evaluate_kriteria_29, KigResult, Kriteria29Result};
+pub use criterion::{Criterion, CriterionInfo, CriterionInput, CriterionOutcome, HilalParameters};
+pub use custom::{load_custom_criteria_file, CustomCriterion, CustomRule};
 pub use ijtima_qobla_ghurub::{evaluate_ijtima_qobla_ghurub, IjtimaQoblaGhuribResult};
 pub use khgt::{evaluate_khgt, KhgtResult};
 pub use lfnu::{evaluate_lfnu, LfnuResult};
 pub use mabims::{evaluate_criteria as evaluate_mabims, evaluate_new_mabims, MabimsResult};
 pub use odeh::{evaluate_odeh, OdehResult};
+pub use registry::{all_criteria, find_criterion, list_criteria};
 pub use turkey::{evaluate_turkey, TurkeyResult};
 pub use wujudul_hilal::{evaluate_wujudul_hilal, WujudulHilalResult};
+pub use yallop::{evaluate_yallop, YallopResult};
 
 use crate::{GeoLocation, GregorianDate};
 
@@ -47,6 +55,7 @@ pub struct VisibilityResult {
 /// * `location` - Lokasi pengamatan
 /// * `date` - Tanggal Gregorian
 /// * `conjunction_jd` - Julian Day dari ijtimak (konjungsi bulan-matahari)
+/// * `rise_set` - Kondisi pengamatan (tekanan, suhu, ufuk) untuk waktu maghrib
 ///
 /// # Returns
 /// HashMap dengan hasil evaluasi untuk setiap kriteria
@@ -54,155 +63,35 @@ pub fn evaluate_all_criteria(
     location: &GeoLocation,
     date: &GregorianDate,
     conjunction_jd: f64,
+    rise_set: &crate::astronomy::rise_set::RiseSetOptions,
 ) -> std::collections::HashMap<String, VisibilityResult> {
-    let mut results = std::collections::HashMap::new();
-
-    let jd = crate::calendar::gregorian_to_jd(date);
-    let sunset_hour = crate::astronomy::calculate_sunset(location, date);
-    let sunset_hour_ut = sunset_hour - location.timezone;
-    let sunset_jd = jd + (sunset_hour_ut / 24.0);
-
-    println!(
-        "📅 Observation date: {}-{}-{}",
-        date.year, date.month, date.day
-    );
-    println!("🌙 Conjunction JD: {}", conjunction_jd);
-    println!("🌅 Sunset JD: {}", sunset_jd);
-    println!(
-        "⏱️  Moon age: {} hours",
-        (sunset_jd - conjunction_jd) * 24.0
-    );
-
-    // Evaluasi MABIMS
-    let mabims_result = evaluate_mabims(location, conjunction_jd, sunset_jd);
-    results.insert(
-        "MABIMS".to_string(),
-        VisibilityResult {
-            criteria_name: "MABIMS".to_string(),
-            is_visible: mabims_result.is_visible,
-            visibility_type: if mabims_result.is_visible {
-                "Visible".to_string()
-            } else {
-                "Not Visible".to_string()
-            },
-            additional_info: format!(
-                "Altitude: {:.2}°, Elongation: {:.2}°, Age: {:.1}h",
-                mabims_result.moon_altitude,
-                mabims_result.geocentric_elongation,
-                mabims_result.moon_age_hours
-            ),
-        },
-    );
-
-    // Evaluasi Wujudul Hilal
-    let wujudul_result = evaluate_wujudul_hilal(location, date, conjunction_jd);
-    results.insert(
-        "WujudulHilal".to_string(),
-        VisibilityResult {
-            criteria_name: "Wujudul Hilal".to_string(),
-            is_visible: wujudul_result.is_visible,
-            visibility_type: if wujudul_result.is_visible {
-                "Visible".to_string()
-            } else {
-                "Not Visible".to_string()
-            },
-            additional_info: format!(
-                "Ijtimak before maghrib: {}, Moon altitude: {:.2}°",
-                wujudul_result.ijtimak_before_maghrib, wujudul_result.moon_altitude
-            ),
-        },
-    );
-
-    // Evaluasi Turkey (menggunakan topocentric)
-    let turkey_result = evaluate_turkey(location, date, true);
-    results.insert(
-        "Turkey".to_string(),
-        VisibilityResult {
-            criteria_name: "Turkey (Diyanet)".to_string(),
-            is_visible: turkey_result.is_visible,
-            visibility_type: if turkey_result.is_visible {
-                "Visible".to_string()
-            } else {
-                "Not Visible".to_string()
-            },
-            additional_info: format!(
-                "Altitude: {:.2}°, Elongation: {:.2}°",
-                turkey_result.moon_altitude, turkey_result.elongation
-            ),
-        },
-    );
-
-    // Evaluasi Odeh
-    let odeh_result = evaluate_odeh(location, date);
-    results.insert(
-        "Odeh".to_string(),
-        VisibilityResult {
-            criteria_name: "Odeh".to_string(),
-            is_visible: odeh_result.is_visible,
-            visibility_type: odeh_result.visibility_type.clone(),
-            additional_info: format!(
-                "ARCV: {:.2}°, Width: {:.2}', q-value: {:.3}",
-                odeh_result.arcv, odeh_result.crescent_width, odeh_result.q_value
-            ),
-        },
-    );
-
-    // Evaluasi Ijtima Qobla Ghurub
-    let ijtima_result = evaluate_ijtima_qobla_ghurub(location, date, conjunction_jd);
-    results.insert(
-        "IjtimaQoblaGhurub".to_string(),
-        VisibilityResult {
-            criteria_name: "Ijtima Qobla Ghurub".to_string(),
-            is_visible: ijtima_result.is_visible,
-            visibility_type: if ijtima_result.is_visible {
-                "Visible".to_string()
-            } else {
-                "Not Visible".to_string()
-            },
-            additional_info: format!(
-                "Ijtimak sebelum maghrib: {}",
-                ijtima_result.ijtimak_before_maghrib
-            ),
-        },
-    );
-
-    // Evaluasi LFNU
-    let lfnu_result = evaluate_lfnu(location, date);
-    results.insert(
-        "LFNU".to_string(),
-        VisibilityResult {
-            criteria_name: "LFNU (Lembaga Falakiyah NU)".to_string(),
-            is_visible: lfnu_result.is_visible,
-            visibility_type: if lfnu_result.is_visible {
-                "Visible".to_string()
-            } else {
-                "Not Visible".to_string()
-            },
-            additional_info: format!(
-                "Altitude: {:.2}°, Elongation: {:.2}°",
-                lfnu_result.moon_altitude, lfnu_result.elongation
-            ),
-        },
-    );
-
-    // Evaluasi KHGT
-    let khgt_result = evaluate_khgt(location, date, true);
-    results.insert(
-        "KHGT".to_string(),
-        VisibilityResult {
-            criteria_name: "KHGT (Kalender Hijriah Global Tunggal)".to_string(),
-            is_visible: khgt_result.is_visible,
-            visibility_type: if khgt_result.is_visible {
-                "Visible".to_string()
-            } else {
-                "Not Visible".to_string()
-            },
-            additional_info: format!(
-                "Altitude: {:.2}°, Elongation: {:.2}°, Ijtima OK: {}",
-                khgt_result.moon_altitude, khgt_result.elongation, khgt_result.ijtima_ok
-            ),
-        },
-    );
+    // Semua kriteria diambil dari registry agar tabel, peta dan command selalu sama
+    evaluate_criteria(&all_criteria(), location, date, conjunction_jd, rise_set)
+}
 
-    results
+/// Evaluasi daftar kriteria tertentu pada satu snapshot maghrib
+pub fn evaluate_criteria(
+    criteria: &[std::sync::Arc<dyn Criterion>],
+    location: &GeoLocation,
+    date: &GregorianDate,
+    conjunction_jd: f64,
+    rise_set: &crate::astronomy::rise_set::RiseSetOptions,
+) -> std::collections::HashMap<String, VisibilityResult> {
+    let params = HilalParameters::with_rise_set(location, date, conjunction_jd, rise_set);
+
+    criteria
+        .iter()
+        .map(|criterion| {
+            let outcome = criterion.evaluate(&params);
+            (
+                criterion.id().to_string(),
+                VisibilityResult {
+                    criteria_name: criterion.name().to_string(),
+                    is_visible: outcome.is_visible,
+                    visibility_type: outcome.visibility_type,
+                    additional_info: outcome.additional_info,
+                },
+            )
+        })
+        .collect()
 }
@@ -5,12 +5,13 @@
 // Formula: q = ARCV - threshold, dimana threshold = -0.1018*w³ + 0.7319*w² - 6.3226*w + 7.1651
 // w = crescent width dalam arc-minutes
 //
-// Hasil:
-// - q > 5.65: Easily visible
-// - 0.216 < q < 5.65: Visible dengan optical aid
-// - -0.014 < q < 0.216: Visible untuk observers dengan exceptional visual acuity
-// - q < -0.014: Not visible
+// Hasil (zona Odeh 2004):
+// - A: V >= 5.65: Terlihat mata telanjang dengan mudah
+// - B: V >= 2.00: Terlihat dengan alat optik, mungkin terlihat mata telanjang
+// - C: V >= -0.96: Hanya terlihat dengan alat optik
+// - D: V < -0.96: Tidak terlihat
 
+use super::criterion::{Criterion, CriterionInput, CriterionOutcome, HilalParameters};
 use crate::{GeoLocation, GregorianDate};
 
 /// Struktur hasil evaluasi Odeh criterion
@@ -22,51 +23,38 @@ pub struct OdehResult {
     pub arcv: f64,
     pub crescent_width: f64,
     pub q_value: f64,
-    pub visibility_type: String, // "easily_visible", "visible", "difficult", "not_visible"
+    pub visibility_type: String, // "easily_visible", "visible", "visible_with_optical_aid", "not_visible"
 }
 
 /// Evaluasi kriteria Odeh
 ///
 /// Port dari: OdehVisibilityAtSunset di KumpulanFungsiAtSunset.bas
 pub fn evaluate_odeh(location: &GeoLocation, date: &GregorianDate) -> OdehResult {
-    // Hitung parameter hilal pada saat maghrib (TOPOCENTRIC)
-    let moon_altitude = crate::astronomy::altitude_at_sunset(location, date, true);
-    let _elongation = crate::astronomy::elongation_at_sunset(location, date, true);
-    let sun_altitude = calculate_sun_altitude_at_sunset(location, date);
-    let crescent_width = crate::astronomy::crescent_width_at_sunset(location, date, true);
-
-    // Hitung Julian Day dan time at sunset (UTC)
-    let jd = crate::calendar::gregorian_to_jd(date);
-    let sunset_hour = crate::astronomy::calculate_sunset(location, date);
-    let sunset_hour_ut = sunset_hour - location.timezone;
-    let sunset_jd = jd + (sunset_hour_ut / 24.0);
-
-    // Hitung horizontal parallax bulan
-    let moon_parallax = crate::astronomy::horizontal_moon_parallax(sunset_jd);
-
-    // Hitung sun distance (dalam AU)
-    let (_, sun_distance) = astro::sun::geocent_ecl_pos(sunset_jd);
-
-    // Hitung ARCV
-    let arcv =
-        crate::astronomy::calculate_arcv(moon_altitude, sun_altitude, moon_parallax, sun_distance);
+    evaluate_odeh_from(&HilalParameters::at_sunset(location, date))
+}
 
+/// Evaluasi kriteria Odeh dari snapshot parameter hilal
+///
+/// Odeh (2004) mendefinisikan ARCV dari tinggi airless toposentris: altitude geosentris
+/// tanpa refraksi dikoreksi parallax (sudah dihitung di snapshot). Altitude bulan tampak
+/// hanya dipakai untuk level peta (bulan di bawah ufuk).
+pub fn evaluate_odeh_from(params: &HilalParameters) -> OdehResult {
     // Hitung threshold dari crescent width
-    let w = crescent_width;
+    let w = params.crescent_width;
     let threshold = calculate_odeh_threshold(w);
 
     // Hitung q value
-    let q = arcv - threshold;
+    let q = params.arcv - threshold;
 
     // Evaluasi visibilitas berdasarkan q value
     let (is_visible, visibility_type) = evaluate_q_value(q);
 
     OdehResult {
         is_visible,
-        moon_altitude,
-        sun_altitude,
-        arcv,
-        crescent_width,
+        moon_altitude: params.moon_altitude_topo,
+        sun_altitude: params.sun_altitude_geo,
+        arcv: params.arcv,
+        crescent_width: w,
         q_value: q,
         visibility_type,
     }
@@ -80,73 +68,76 @@ fn calculate_odeh_threshold(w: f64) -> f64 {
     -0.1018 * w.powi(3) + 0.7319 * w.powi(2) - 6.3226 * w + 7.1651
 }
 
+/// Batas bawah zona A, B dan C Odeh
+const ZONE_A: f64 = 5.65;
+const ZONE_B: f64 = 2.00;
+const ZONE_C: f64 = -0.96;
+
 /// Evaluasi q value untuk menentukan visibilitas
 fn evaluate_q_value(q: f64) -> (bool, String) {
-    if q > 5.65 {
+    if q >= ZONE_A {
         (true, "easily_visible".to_string())
-    } else if q > 0.216 {
+    } else if q >= ZONE_B {
+        (true, "visible".to_string())
+    } else if q >= ZONE_C {
         (true, "visible_with_optical_aid".to_string())
-    } else if q > -0.014 {
-        (true, "visible_exceptional_acuity".to_string())
     } else {
         (false, "not_visible".to_string())
     }
 }
 
-/// Helper: Hitung altitude matahari pada saat maghrib
-fn calculate_sun_altitude_at_sunset(location: &GeoLocation, date: &GregorianDate) -> f64 {
-    let jd = crate::calendar::gregorian_to_jd(date);
-    let sunset_hour = crate::astronomy::calculate_sunset(location, date);
-    let sunset_hour_ut = sunset_hour - location.timezone;
-    let sunset_jd = jd + (sunset_hour_ut / 24.0);
-
-    // Sun altitude pada sunset adalah approximately -0.833 degrees (accounting for refraction)
-    // Untuk perhitungan lebih akurat, hitung dari sun position
-    let sun_pos = crate::astronomy::sun_position(sunset_jd);
-
-    // Konversi dari ekliptika ke equatorial
-    let obliquity = astro::ecliptic::mn_oblq_laskar(astro::time::julian_cent(sunset_jd));
-
-    let ra = ((sun_pos.longitude.to_radians().sin() * obliquity.cos()
-        - sun_pos.latitude.to_radians().tan() * obliquity.sin())
-    .atan2(sun_pos.longitude.to_radians().cos()))
-    .to_degrees();
-
-    let dec = (sun_pos.latitude.to_radians().sin() * obliquity.cos()
-        + sun_pos.longitude.to_radians().cos()
-            * sun_pos.latitude.to_radians().sin()
-            * obliquity.sin())
-    .asin()
-    .to_degrees();
-
-    // Hitung altitude
-    let lat = location.latitude.to_radians();
-    let dec_rad = dec.to_radians();
-
-    // Local Sidereal Time
-    let lst = local_sidereal_time(location.longitude, sunset_jd);
-    let ha = (lst - ra).to_radians();
-
-    let altitude = (lat.sin() * dec_rad.sin() + lat.cos() * dec_rad.cos() * ha.cos())
-        .asin()
-        .to_degrees();
-
-    altitude
+/// Level peta dari zona Odeh: 4 = A, 3 = B, 2 = C, 0 = D (tidak terlihat) atau bulan di
+/// bawah ufuk. Odeh tidak memiliki zona "sulit" (level 1).
+pub fn visibility_level(moon_altitude: f64, q_value: f64) -> i32 {
+    if moon_altitude <= 0.0 {
+        0 // Below horizon
+    } else if q_value >= ZONE_A {
+        4 // A: Easy
+    } else if q_value >= ZONE_B {
+        3 // B: Visible
+    } else if q_value >= ZONE_C {
+        2 // C: Optical aid
+    } else {
+        0 // D: Not visible
+    }
 }
 
-/// Helper: Hitung Local Sidereal Time
-fn local_sidereal_time(longitude: f64, jd: f64) -> f64 {
-    let t = astro::time::julian_cent(jd);
-    let gmst_hours = 18.697374558 + 879000.0513367 * t + 0.093104 * t * t - 6.2e-6 * t * t * t;
-    let gmst_hours = gmst_hours % 24.0;
-    let gmst_deg = (gmst_hours * 15.0) % 360.0;
+/// Odeh (q-value)
+pub struct Odeh;
+
+impl Criterion for Odeh {
+    fn id(&self) -> &str {
+        "Odeh"
+    }
 
-    let lst = (gmst_deg + longitude) % 360.0;
+    fn name(&self) -> &str {
+        "Odeh"
+    }
 
-    if lst < 0.0 {
-        lst + 360.0
-    } else {
-        lst
+    fn description(&self) -> &str {
+        "q = ARCV - f(W), zona A-D"
+    }
+
+    fn inputs(&self) -> &[CriterionInput] {
+        &[CriterionInput::ArcOfVision, CriterionInput::CrescentWidth]
+    }
+
+    fn classes(&self) -> &[(i32, &'static str)] {
+        &[(4, "A"), (3, "B"), (2, "C"), (0, "D")]
+    }
+
+    fn evaluate(&self, params: &HilalParameters) -> CriterionOutcome {
+        let result = evaluate_odeh_from(params);
+        CriterionOutcome {
+            is_visible: result.is_visible,
+            visibility_type: result.visibility_type.clone(),
+            q_value: Some(result.q_value),
+            level: visibility_level(result.moon_altitude, result.q_value),
+            additional_info: format!(
+                "ARCV: {:.2}°, Width: {:.2}', q-value: {:.3}",
+                result.arcv, result.crescent_width, result.q_value
+            ),
+        }
     }
 }
 
@@ -154,6 +145,43 @@ fn local_sidereal_time(longitude: f64, jd: f64) -> f64 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_arcv_is_airless() {
+        let location = GeoLocation {
+            name: Some("Sukabumi".to_string()),
+            latitude: -7.0739,
+            longitude: 106.5314,
+            elevation: 10.0,
+            timezone: 7.0,
+        };
+        let date = GregorianDate {
+            year: 2026,
+            month: 2,
+            day: 18.0,
+        };
+        let conjunction = crate::astronomy::find_conjunction(&date);
+        let params = HilalParameters::new(&location, &date, conjunction.jd_utc);
+        let result = evaluate_odeh_from(&params);
+
+        // Matahari geometrik saat maghrib ~ -0°50' (bukan -2.2° dari deklinasi ~0 versi lama)
+        assert!(
+            (result.sun_altitude + 0.83).abs() < 0.1,
+            "{}",
+            result.sun_altitude
+        );
+
+        // ARCV dari tinggi airless dikoreksi parallax, bukan dari altitude bulan tampak
+        let expected = crate::astronomy::calculate_arcv(
+            params.moon_altitude_geo,
+            params.sun_altitude_geo,
+            params.moon_parallax,
+            params.sun_distance_au,
+        );
+        assert!((result.arcv - expected).abs() < 1e-9);
+        assert!(result.arcv < params.moon_altitude_geo - params.sun_altitude_geo);
+        assert!((result.arcv - 9.42).abs() < 0.05, "{}", result.arcv);
+    }
+
     #[test]
     fn test_odeh_threshold_formula() {
         // Test threshold formula dengan beberapa nilai w
@@ -172,7 +200,30 @@ mod tests {
         assert!(visible);
         assert_eq!(type_str, "easily_visible");
 
-        let (visible, _) = evaluate_q_value(-0.05);
+        let (visible, _) = evaluate_q_value(-1.0);
         assert!(!visible);
     }
+
+    #[test]
+    fn test_zone_boundaries() {
+        // Tepat di batas masuk zona atas, sedikit di bawahnya masuk zona berikutnya
+        assert_eq!(visibility_level(5.0, 5.65), 4);
+        assert_eq!(visibility_level(5.0, 5.6499), 3);
+        assert_eq!(visibility_level(5.0, 2.00), 3);
+        assert_eq!(visibility_level(5.0, 1.9999), 2);
+        assert_eq!(visibility_level(5.0, -0.96), 2);
+        assert_eq!(visibility_level(5.0, -0.9601), 0);
+        assert_eq!(visibility_level(-0.5, 10.0), 0);
+
+        assert_eq!(evaluate_q_value(5.65).1, "easily_visible");
+        assert_eq!(evaluate_q_value(2.00).1, "visible");
+        assert_eq!(
+            evaluate_q_value(-0.96),
+            (true, "visible_with_optical_aid".to_string())
+        );
+        assert_eq!(
+            evaluate_q_value(-0.9601),
+            (false, "not_visible".to_string())
+        );
+    }
 }
@@ -0,0 +1,139 @@
+// Registry kriteria visibilitas hilal
+//
+// Satu-satunya daftar kriteria yang dipakai oleh evaluate_all_criteria,
+// peta visibilitas dan command `list_criteria_command`.
+
+use std::sync::Arc;
+
+use super::criterion::{Criterion, CriterionInfo};
+
+/// Semua kriteria bawaan, sesuai urutan tampilan
+pub fn builtin_criteria() -> Vec<Arc<dyn Criterion>> {
+    vec![
+        Arc::new(super::mabims::Mabims),
+        Arc::new(super::mabims::NeoMabims),
+        Arc::new(super::wujudul_hilal::WujudulHilal),
+        Arc::new(super::turkey::Turkey),
+        Arc::new(super::odeh::Odeh),
+        Arc::new(super::yallop::Yallop),
+        Arc::new(super::ijtima_qobla_ghurub::IjtimaQoblaGhurub),
+        Arc::new(super::lfnu::Lfnu),
+        Arc::new(super::khgt::Khgt),
+    ]
+}
+
+/// Semua kriteria yang terdaftar: bawaan, lalu kriteria kustom dari file aturan
+pub fn all_criteria() -> Vec<Arc<dyn Criterion>> {
+    let mut criteria = builtin_criteria();
+    criteria.extend(super::custom::custom_criteria());
+    criteria
+}
+
+/// Cari kriteria berdasarkan id (tidak case-sensitive: "ODEH" == "Odeh")
+pub fn find_criterion(id: &str) -> Option<Arc<dyn Criterion>> {
+    all_criteria()
+        .into_iter()
+        .find(|criterion| criterion.id().eq_ignore_ascii_case(id))
+}
+
+/// Daftar ringkasan kriteria untuk frontend
+pub fn list_criteria() -> Vec<CriterionInfo> {
+    all_criteria()
+        .iter()
+        .map(|criterion| CriterionInfo::from_criterion(criterion.as_ref()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_ids_are_unique() {
+        let criteria = all_criteria();
+        for (i, a) in criteria.iter().enumerate() {
+            for b in criteria.iter().skip(i + 1) {
+                assert!(
+                    !a.id().eq_ignore_ascii_case(b.id()),
+                    "Duplicate id {}",
+                    a.id()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_criterion_case_insensitive() {
+        assert_eq!(find_criterion("ODEH").unwrap().id(), "Odeh");
+        assert_eq!(find_criterion("turkey").unwrap().id(), "Turkey");
+        assert_eq!(find_criterion("mabims").unwrap().id(), "MABIMS");
+        assert_eq!(find_criterion("neo_mabims").unwrap().id(), "NEO_MABIMS");
+        assert!(find_criterion("Unknown").is_none());
+    }
+
+    #[test]
+    fn test_mabims_keys_keep_their_rules() {
+        let location = crate::GeoLocation {
+            name: None,
+            latitude: -6.2,
+            longitude: 106.8,
+            elevation: 0.0,
+            timezone: 7.0,
+        };
+        let date = crate::GregorianDate {
+            year: 2026,
+            month: 2,
+            day: 18.0,
+        };
+        let conjunction = crate::astronomy::find_conjunction(&date);
+        let mut params = super::super::HilalParameters::new(&location, &date, conjunction.jd_utc);
+
+        // Memenuhi 2-3-8 tetapi belum memenuhi 3-6.4
+        params.moon_altitude_topo = 2.5;
+        params.elongation_geo = 4.0;
+        params.moon_age_hours = 10.0;
+        assert!(
+            find_criterion("MABIMS")
+                .unwrap()
+                .evaluate(&params)
+                .is_visible
+        );
+        assert!(
+            !find_criterion("NEO_MABIMS")
+                .unwrap()
+                .evaluate(&params)
+                .is_visible
+        );
+    }
+
+    #[test]
+    fn test_evaluate_criteria_covers_registry() {
+        let location = crate::GeoLocation {
+            name: None,
+            latitude: -6.2,
+            longitude: 106.8,
+            elevation: 0.0,
+            timezone: 7.0,
+        };
+        let date = crate::GregorianDate {
+            year: 2026,
+            month: 2,
+            day: 18.0,
+        };
+        let conjunction = crate::astronomy::find_conjunction(&date);
+
+        // Registry eksplisit: kriteria kustom yang didaftarkan test lain tidak ikut terhitung
+        let criteria = builtin_criteria();
+        let results = crate::criteria::evaluate_criteria(
+            &criteria,
+            &location,
+            &date,
+            conjunction.jd_utc,
+            &crate::astronomy::rise_set::RiseSetOptions::default(),
+        );
+        assert_eq!(results.len(), criteria.len());
+        for criterion in &criteria {
+            assert!(results.contains_key(criterion.id()));
+        }
+    }
+}
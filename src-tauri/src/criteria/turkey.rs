@@ -7,6 +7,7 @@
 //
 // Ini adalah kriteria yang lebih ketat daripada MABIMS
 
+use super::criterion::{Criterion, CriterionInput, CriterionOutcome, HilalParameters};
 use crate::{GeoLocation, GregorianDate};
 
 /// Struktur hasil evaluasi Turkey criteria
@@ -25,11 +26,17 @@ pub fn evaluate_turkey(
     date: &GregorianDate,
     use_topocentric: bool,
 ) -> TurkeyResult {
-    // Hitung altitude bulan pada saat maghrib
-    let moon_altitude = crate::astronomy::altitude_at_sunset(location, date, use_topocentric);
+    evaluate_turkey_from(&HilalParameters::at_sunset(location, date), use_topocentric)
+}
 
-    // Hitung elongasi pada saat maghrib
-    let elongation = crate::astronomy::elongation_at_sunset(location, date, use_topocentric);
+/// Evaluasi kriteria Turkey dari snapshot parameter hilal
+pub fn evaluate_turkey_from(params: &HilalParameters, use_topocentric: bool) -> TurkeyResult {
+    // Altitude dan elongasi bulan pada saat maghrib
+    let (moon_altitude, elongation) = if use_topocentric {
+        (params.moon_altitude_topo, params.elongation_topo)
+    } else {
+        (params.moon_altitude_geo, params.elongation_geo)
+    };
 
     // Evaluasi kriteria Turkey
     let altitude_ok = moon_altitude >= 5.0;
@@ -45,6 +52,38 @@ pub fn evaluate_turkey(
     }
 }
 
+/// Turkey (Diyanet), toposentris
+pub struct Turkey;
+
+impl Criterion for Turkey {
+    fn id(&self) -> &str {
+        "Turkey"
+    }
+
+    fn name(&self) -> &str {
+        "Turkey (Diyanet)"
+    }
+
+    fn description(&self) -> &str {
+        "Alt ≥ 5°, Elong ≥ 8°"
+    }
+
+    fn inputs(&self) -> &[CriterionInput] {
+        &[CriterionInput::MoonAltitude, CriterionInput::Elongation]
+    }
+
+    fn evaluate(&self, params: &HilalParameters) -> CriterionOutcome {
+        let result = evaluate_turkey_from(params, true);
+        CriterionOutcome::binary(
+            result.is_visible,
+            format!(
+                "Altitude: {:.2}°, Elongation: {:.2}°",
+                result.moon_altitude, result.elongation
+            ),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
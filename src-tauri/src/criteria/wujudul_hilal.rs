@@ -7,6 +7,7 @@
 //
 // Jika kedua kondisi terpenuhi, maka hilal secara teknis "ada" (wujud)
 
+use super::criterion::{Criterion, CriterionInput, CriterionOutcome, HilalParameters};
 use crate::{GeoLocation, GregorianDate};
 
 /// Struktur hasil evaluasi Wujudul Hilal
@@ -27,17 +28,16 @@ pub fn evaluate_wujudul_hilal(
     date: &GregorianDate,
     conjunction_jd: f64,
 ) -> WujudulHilalResult {
-    let jd = crate::calendar::gregorian_to_jd(date);
-
-    // Hitung waktu maghrib dalam Julian Day
-    let sunset_hour = crate::astronomy::calculate_sunset(location, date);
-    let sunset_jd = jd + (sunset_hour / 24.0);
+    evaluate_wujudul_hilal_from(&HilalParameters::new(location, date, conjunction_jd))
+}
 
-    // Kondisi 1: Ijtimak terjadi sebelum maghrib
-    let ijtimak_before_maghrib = conjunction_jd < sunset_jd;
+/// Evaluasi Wujudul Hilal dari snapshot parameter hilal
+pub fn evaluate_wujudul_hilal_from(params: &HilalParameters) -> WujudulHilalResult {
+    // Kondisi 1: Ijtimak terjadi sebelum maghrib (keduanya JD UT)
+    let ijtimak_before_maghrib = params.conjunction_jd < params.sunset_jd;
 
-    // Kondisi 2: Hitung altitude bulan pada saat maghrib (TOPOCENTRIC)
-    let moon_altitude = crate::astronomy::altitude_at_sunset(location, date, true);
+    // Kondisi 2: Altitude bulan pada saat maghrib (TOPOCENTRIC)
+    let moon_altitude = params.moon_altitude_topo;
     let moon_above_horizon = moon_altitude > 0.0;
 
     // Hasil: Hilal terjadi jika kedua kondisi terpenuhi
@@ -51,6 +51,41 @@ pub fn evaluate_wujudul_hilal(
     }
 }
 
+/// Wujudul Hilal (Muhammadiyah)
+pub struct WujudulHilal;
+
+impl Criterion for WujudulHilal {
+    fn id(&self) -> &str {
+        "WujudulHilal"
+    }
+
+    fn name(&self) -> &str {
+        "Wujudul Hilal"
+    }
+
+    fn description(&self) -> &str {
+        "Ijtimak sebelum maghrib, bulan di atas ufuk"
+    }
+
+    fn inputs(&self) -> &[CriterionInput] {
+        &[
+            CriterionInput::ConjunctionTime,
+            CriterionInput::MoonAltitude,
+        ]
+    }
+
+    fn evaluate(&self, params: &HilalParameters) -> CriterionOutcome {
+        let result = evaluate_wujudul_hilal_from(params);
+        CriterionOutcome::binary(
+            result.is_visible,
+            format!(
+                "Ijtimak before maghrib: {}, Moon altitude: {:.2}°",
+                result.ijtimak_before_maghrib, result.moon_altitude
+            ),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,4 +112,30 @@ mod tests {
         // Minimal checks - actual values depend on astronomical calculations
         assert!(result.ijtimak_before_maghrib || !result.ijtimak_before_maghrib);
     }
+
+    #[test]
+    fn test_conjunction_compared_with_maghrib_in_ut() {
+        // Ijtimak 17 Feb 2026 ~12:01 UT (19:01 WIB), setelah maghrib Jakarta (~18:15 WIB).
+        // Jam maghrib lokal tidak boleh dibaca sebagai UT (18:15 UT > ijtimak).
+        let location = GeoLocation {
+            name: None,
+            latitude: -6.2,
+            longitude: 106.8,
+            elevation: 0.0,
+            timezone: 7.0,
+        };
+        let date = GregorianDate {
+            year: 2026,
+            month: 2,
+            day: 17.0,
+        };
+        let conjunction = crate::astronomy::find_conjunction(&date);
+        let sunset_hour = crate::astronomy::calculate_sunset(&location, &date);
+        let sunset_ut_jd = crate::calendar::gregorian_to_jd(&date) + (sunset_hour - 7.0) / 24.0;
+        assert!(conjunction.jd_utc > sunset_ut_jd);
+
+        let result = evaluate_wujudul_hilal(&location, &date, conjunction.jd_utc);
+        assert!(!result.ijtimak_before_maghrib);
+        assert!(!result.is_visible);
+    }
 }
@@ -0,0 +1,194 @@
+// Module untuk kriteria visibilitas Yallop
+// Referensi: B.D. Yallop, "A Method for Predicting the First Sighting of the New Crescent Moon",
+//            NAO Technical Note No. 69 (1997)
+//
+// Kriteria Yallop menggunakan ARCV geosentris (airless) dan lebar hilal toposentris W'
+// Formula: q = (ARCV - (11.8371 - 6.3226*W' + 0.7319*W'² - 0.1018*W'³)) / 10
+// W' = lebar hilal toposentris dalam arc-minutes
+//
+// Zona:
+// - A: q > +0.216            Mudah terlihat mata telanjang
+// - B: +0.216 >= q > -0.014  Terlihat dalam kondisi sempurna
+// - C: -0.014 >= q > -0.160  Mungkin perlu alat optik untuk menemukan hilal
+// - D: -0.160 >= q > -0.232  Hanya terlihat dengan alat optik
+// - E: -0.232 >= q > -0.293  Tidak terlihat dengan teleskop
+// - F: q <= -0.293           Di bawah limit Danjon
+
+use super::criterion::{Criterion, CriterionInput, CriterionOutcome, HilalParameters};
+use crate::{GeoLocation, GregorianDate};
+
+/// Struktur hasil evaluasi Yallop criterion
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct YallopResult {
+    pub is_visible: bool,
+    pub arcv: f64,           // ARCV geosentris airless (derajat)
+    pub arcl: f64,           // Elongasi geosentris (derajat)
+    pub crescent_width: f64, // W' toposentris (arc-minutes)
+    pub q_value: f64,
+    pub zone: String,            // "A" - "F"
+    pub visibility_type: String, // "easily_visible", "visible_perfect_conditions", ...
+}
+
+/// Evaluasi kriteria Yallop pada saat maghrib
+///
+/// Yallop aslinya memakai "best time" (Tb = Ts + 4/9 Lag). Di sini parameter diambil
+/// pada saat maghrib agar konsisten dengan evaluasi Odeh dan peta visibilitas.
+pub fn evaluate_yallop(location: &GeoLocation, date: &GregorianDate) -> YallopResult {
+    evaluate_yallop_from(&HilalParameters::at_sunset(location, date))
+}
+
+/// Evaluasi kriteria Yallop dari snapshot parameter hilal
+pub fn evaluate_yallop_from(params: &HilalParameters) -> YallopResult {
+    // ARCV geosentris airless (tanpa refraksi & parallax), ARCL = elongasi geosentris
+    let arcv = params.moon_altitude_geo - params.sun_altitude_geo;
+    let arcl = params.elongation_geo;
+
+    // Semidiameter bulan (VB6) dengan koreksi toposentris
+    // SD' = SD * (1 + sin(h) * sin(HP))
+    let sd_arcmin = params.moon_semidiameter * 60.0;
+    let sd_topo_arcmin = sd_arcmin
+        * (1.0 + params.moon_altitude_geo.to_radians().sin() * params.moon_parallax.sin());
+    let crescent_width = sd_topo_arcmin * (1.0 - arcl.to_radians().cos());
+
+    let q = calculate_yallop_q(arcv, crescent_width);
+    let (zone, visibility_type) = yallop_zone(q);
+
+    YallopResult {
+        // Zona A-D masih dapat terlihat (D dengan bantuan alat optik)
+        is_visible: matches!(zone, 'A' | 'B' | 'C' | 'D'),
+        arcv,
+        arcl,
+        crescent_width,
+        q_value: q,
+        zone: zone.to_string(),
+        visibility_type: visibility_type.to_string(),
+    }
+}
+
+/// Hitung q Yallop dari ARCV (derajat) dan W' (arc-minutes)
+pub fn calculate_yallop_q(arcv: f64, width_arcmin: f64) -> f64 {
+    let w = width_arcmin;
+    (arcv - (11.8371 - 6.3226 * w + 0.7319 * w.powi(2) - 0.1018 * w.powi(3))) / 10.0
+}
+
+/// Tentukan zona Yallop (A-F) dari q value
+pub fn yallop_zone(q: f64) -> (char, &'static str) {
+    if q > 0.216 {
+        ('A', "easily_visible")
+    } else if q > -0.014 {
+        ('B', "visible_perfect_conditions")
+    } else if q > -0.160 {
+        ('C', "optical_aid_to_find")
+    } else if q > -0.232 {
+        ('D', "optical_aid_only")
+    } else if q > -0.293 {
+        ('E', "not_visible_with_telescope")
+    } else {
+        ('F', "below_danjon_limit")
+    }
+}
+
+/// Level peta untuk zona Yallop: A=4 ... D=1, E=0, F=-1 (di bawah batas Danjon)
+pub fn zone_to_level(zone: &str) -> i32 {
+    match zone {
+        "A" => 4,
+        "B" => 3,
+        "C" => 2,
+        "D" => 1,
+        "E" => 0,
+        _ => -1,
+    }
+}
+
+/// Yallop (q-value), zona A-F
+pub struct Yallop;
+
+impl Criterion for Yallop {
+    fn id(&self) -> &str {
+        "Yallop"
+    }
+
+    fn name(&self) -> &str {
+        "Yallop"
+    }
+
+    fn description(&self) -> &str {
+        "q = (ARCV - f(W')) / 10, zona A-F"
+    }
+
+    fn inputs(&self) -> &[CriterionInput] {
+        &[CriterionInput::ArcOfVision, CriterionInput::CrescentWidth]
+    }
+
+    fn classes(&self) -> &[(i32, &'static str)] {
+        &[(4, "A"), (3, "B"), (2, "C"), (1, "D"), (0, "E"), (-1, "F")]
+    }
+
+    fn evaluate(&self, params: &HilalParameters) -> CriterionOutcome {
+        let result = evaluate_yallop_from(params);
+        CriterionOutcome {
+            is_visible: result.is_visible,
+            visibility_type: result.visibility_type.clone(),
+            q_value: Some(result.q_value),
+            level: zone_to_level(&result.zone),
+            additional_info: format!(
+                "ARCV: {:.2}°, Width: {:.2}', q-value: {:.3}, Zone: {}",
+                result.arcv, result.crescent_width, result.q_value, result.zone
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yallop_zones() {
+        assert_eq!(yallop_zone(0.5).0, 'A');
+        assert_eq!(yallop_zone(0.1).0, 'B');
+        assert_eq!(yallop_zone(-0.1).0, 'C');
+        assert_eq!(yallop_zone(-0.2).0, 'D');
+        assert_eq!(yallop_zone(-0.25).0, 'E');
+        assert_eq!(yallop_zone(-0.5).0, 'F');
+    }
+
+    #[test]
+    fn test_yallop_q_formula() {
+        // W' = 0 -> q = (ARCV - 11.8371) / 10
+        assert!((calculate_yallop_q(11.8371, 0.0)).abs() < 1e-12);
+        // Lebar lebih besar menurunkan ambang ARCV
+        assert!(calculate_yallop_q(10.0, 0.5) > calculate_yallop_q(10.0, 0.2));
+    }
+
+    #[test]
+    fn test_yallop_sukabumi_feb_2026() {
+        // Umur bulan ~23 jam saat maghrib 18 Feb 2026, tinggi hilal ~8.6°
+        let location = GeoLocation {
+            name: Some("Sukabumi".to_string()),
+            latitude: -7.0739,
+            longitude: 106.5314,
+            elevation: 10.0,
+            timezone: 7.0,
+        };
+        let date = GregorianDate {
+            year: 2026,
+            month: 2,
+            day: 18.0,
+        };
+
+        let result = evaluate_yallop(&location, &date);
+
+        // Referensi independen (Meeus bab 25 & 47) pada maghrib 18:17 WIB:
+        // ARCV 10.38°, W' 0.338', q +0.060 -> zona B
+        assert!((result.arcv - 10.38).abs() < 0.05, "{}", result.arcv);
+        assert!(
+            (result.crescent_width - 0.338).abs() < 0.005,
+            "{}",
+            result.crescent_width
+        );
+        assert!((result.q_value - 0.060).abs() < 0.005, "{}", result.q_value);
+        assert_eq!(result.zone, "B");
+        assert!(result.is_visible);
+    }
+}
@@ -0,0 +1,108 @@
+//! Ekspor CSV
+//!
+//! Jadwal waktu shalat satu baris per hari, siap dibuka di spreadsheet atau dicetak.
+//! Kolom dipisah koma; sel yang memuat koma, kutip atau baris baru dikutip (RFC 4180).
+
+use crate::astronomy::prayer_format::{format_prayer_times, PrayerTimeFormat};
+use crate::calendar::prayer_timetable::PrayerTimetable;
+
+const TIMETABLE_HEADER: [&str; 14] = [
+    "Tanggal",
+    "Hari",
+    "Pasaran",
+    "Hijriah",
+    "Imsak",
+    "Shubuh",
+    "Terbit",
+    "Dhuha",
+    "Dzuhur",
+    "Ashr",
+    "Maghrib",
+    "Isya",
+    "Tengah Malam",
+    "Sepertiga Malam",
+];
+
+/// Jadwal waktu shalat sebagai CSV (baris diakhiri CRLF, menit dipotong)
+pub fn prayer_timetable_csv(timetable: &PrayerTimetable) -> String {
+    let mut lines = vec![csv_row(TIMETABLE_HEADER.iter().map(|h| h.to_string()))];
+    let format = PrayerTimeFormat::default();
+    lines.extend(timetable.rows.iter().map(|row| {
+        let times = format_prayer_times(&row.times, &format);
+        csv_row([
+            format!(
+                "{:04}-{:02}-{:02}",
+                row.gregorian.year, row.gregorian.month, row.gregorian.day as u8
+            ),
+            row.hari.clone(),
+            row.pasaran.clone(),
+            format!(
+                "{} {} {}",
+                row.hijri.day,
+                row.hijri.month_name(),
+                row.hijri.year
+            ),
+            times.imsak,
+            times.shubuh,
+            times.terbit,
+            times.dhuha,
+            times.dzuhur,
+            times.ashr,
+            times.maghrib,
+            times.isya,
+            times.tengah_malam,
+            times.p3_malam,
+        ])
+    }));
+    lines.concat()
+}
+
+fn csv_row(cells: impl IntoIterator<Item = String>) -> String {
+    let cells: Vec<String> = cells.into_iter().map(|cell| escape_cell(&cell)).collect();
+    format!("{}\r\n", cells.join(","))
+}
+
+fn escape_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::prayer_timetable::{prayer_timetable, TimetableRange};
+    use crate::calendar::HijriCalendarKind;
+
+    #[test]
+    fn test_prayer_timetable_csv() {
+        let location = crate::GeoLocation {
+            name: Some("Yogyakarta".to_string()),
+            latitude: -7.8,
+            longitude: 110.3667,
+            elevation: 100.0,
+            timezone: 7.0,
+        };
+        let timetable = prayer_timetable(
+            &location,
+            &TimetableRange::HijriMonth {
+                year: 1447,
+                month: 9,
+            },
+            &HijriCalendarKind::default(),
+            &crate::astronomy::prayer::PrayerConfig::default(),
+        )
+        .unwrap();
+        let csv = prayer_timetable_csv(&timetable);
+
+        let lines: Vec<&str> = csv.split_terminator("\r\n").collect();
+        assert_eq!(lines.len(), 31);
+        assert!(lines[0].starts_with("Tanggal,Hari,Pasaran,Hijriah,Imsak"));
+        assert!(lines[1].starts_with("2026-02-18,Rabu,"));
+        assert!(lines[1].contains(",1 Ramadan 1447,"));
+        assert!(lines.iter().all(|line| line.split(',').count() == 14));
+        assert_eq!(escape_cell("a, \"b\""), "\"a, \"\"b\"\"\"");
+    }
+}
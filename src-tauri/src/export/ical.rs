@@ -0,0 +1,377 @@
+//! Ekspor iCalendar (RFC 5545)
+//!
+//! Menghasilkan file `.ics` berisi awal bulan Hijriah, hari besar Islam, ijtimak dan
+//! purnama, serta (opsional) waktu shalat harian untuk satu lokasi. Awal bulan dan hari
+//! besar ditulis sebagai event sehari penuh (`VALUE=DATE`); ijtimak, purnama dan waktu
+//! shalat sebagai event sesaat dalam UTC.
+
+use serde::{Deserialize, Serialize};
+
+use crate::astronomy::prayer::{PrayerConfig, PrayerSettings};
+use crate::calendar::{HijriCalendarKind, HijriDate};
+use crate::GeoLocation;
+
+/// Batas rentang tahun Hijriah satu kali ekspor
+pub const MAX_ICAL_YEARS: i32 = 10;
+
+/// Panjang baris maksimum (oktet, tanpa CRLF) sebelum dilipat
+const MAX_LINE_OCTETS: usize = 75;
+
+const SYNODIC_MONTH: f64 = 29.530588861;
+
+/// Pilihan isi file iCalendar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IcalExportOptions {
+    pub hijri_year: i32,
+    #[serde(default = "default_years")]
+    pub years: i32,
+    #[serde(default)]
+    pub calendar: HijriCalendarKind,
+    #[serde(default = "default_true")]
+    pub month_starts: bool,
+    #[serde(default = "default_true")]
+    pub holidays: bool,
+    #[serde(default = "default_true")]
+    pub moon_phases: bool,
+    #[serde(default)]
+    pub prayer_location: Option<GeoLocation>, // Waktu shalat harian jika diisi
+    #[serde(default)]
+    pub prayer_method: Option<PrayerSettings>, // Default Kemenag RI
+}
+
+fn default_years() -> i32 {
+    1
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Waktu mulai event
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IcalStart {
+    /// Sehari penuh atau beberapa hari: JD tengah malam hari pertama dan jumlah hari
+    AllDay { start_jd: f64, days: u32 },
+    /// Sesaat (JD UT)
+    Instant { jd_utc: f64 },
+}
+
+/// Satu VEVENT
+#[derive(Debug, Clone)]
+pub struct IcalEvent {
+    pub uid: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub category: &'static str,
+    pub start: IcalStart,
+}
+
+/// Susun semua event sesuai pilihan
+pub fn calendar_events(options: &IcalExportOptions) -> Result<Vec<IcalEvent>, String> {
+    if !(1..=MAX_ICAL_YEARS).contains(&options.years) {
+        return Err(format!("Export span must be 1-{} years", MAX_ICAL_YEARS));
+    }
+    let label = options.calendar.label();
+
+    // Awal bulan seluruh rentang (12 per tahun + 1 Muharram tahun berikutnya)
+    let mut starts: Vec<(HijriDate, f64)> = Vec::new();
+    for year in options.hijri_year..options.hijri_year + options.years {
+        let month_starts = options.calendar.month_starts(year)?;
+        starts.extend(
+            month_starts
+                .iter()
+                .take(12)
+                .enumerate()
+                .map(|(index, &jd)| (HijriDate::new(year, index as u8 + 1, 1), jd)),
+        );
+        if year == options.hijri_year + options.years - 1 {
+            starts.push((HijriDate::new(year + 1, 1, 1), month_starts[12]));
+        }
+    }
+    let (first_jd, end_jd) = (starts[0].1, starts[starts.len() - 1].1);
+
+    let mut events = Vec::new();
+    if options.month_starts {
+        events.extend(
+            starts[..starts.len() - 1]
+                .iter()
+                .map(|(hijri, jd)| IcalEvent {
+                    uid: format!("month-{}-{:02}", hijri.year, hijri.month),
+                    summary: format!("1 {} {} H", hijri.month_name(), hijri.year),
+                    description: Some(format!("Awal bulan Hijriah ({})", label)),
+                    category: "Awal Bulan",
+                    start: IcalStart::AllDay {
+                        start_jd: *jd,
+                        days: 1,
+                    },
+                }),
+        );
+    }
+
+    if options.holidays {
+        for year in options.hijri_year..options.hijri_year + options.years {
+            let holidays = crate::calendar::holidays::islamic_holidays(
+                year,
+                std::slice::from_ref(&options.calendar),
+            )?;
+            events.extend(holidays.into_iter().flat_map(|holiday| {
+                let label = label.clone();
+                holiday
+                    .observances
+                    .into_iter()
+                    .map(move |observance| IcalEvent {
+                        uid: format!(
+                            "holiday-{}-{:02}-{:02}-{}",
+                            holiday.hijri_year, holiday.hijri_month, holiday.hijri_day, holiday.id
+                        ),
+                        summary: format!("{} {} H", holiday.name, holiday.hijri_year),
+                        description: Some(format!(
+                            "{} {} {} H ({})",
+                            holiday.hijri_day,
+                            HijriDate::new(holiday.hijri_year, holiday.hijri_month, 1).month_name(),
+                            holiday.hijri_year,
+                            label
+                        )),
+                        category: "Hari Besar Islam",
+                        start: IcalStart::AllDay {
+                            start_jd: observance.start_jd,
+                            days: observance.days as u32,
+                        },
+                    })
+            }));
+        }
+    }
+
+    if options.moon_phases {
+        events.extend(moon_phase_events(first_jd, end_jd));
+    }
+
+    if let Some(location) = &options.prayer_location {
+        let config = options
+            .prayer_method
+            .as_ref()
+            .map(|m| m.config())
+            .unwrap_or_default();
+        events.extend(prayer_events(location, &config, first_jd, end_jd));
+    }
+
+    Ok(events)
+}
+
+/// Ijtimak dan purnama dalam rentang [start_jd, end_jd)
+fn moon_phase_events(start_jd: f64, end_jd: f64) -> Vec<IcalEvent> {
+    let mut events = Vec::new();
+    let mut conjunction =
+        crate::astronomy::find_conjunction(&crate::calendar::jd_to_gregorian(start_jd - 15.0))
+            .jd_utc;
+
+    while conjunction < end_jd {
+        let full_moon = crate::astronomy::find_full_moon_after(conjunction);
+        for (jd, kind, summary) in [
+            (conjunction, "conjunction", "Ijtimak (Bulan Baru)"),
+            (full_moon, "full-moon", "Bulan Purnama"),
+        ] {
+            if (start_jd..end_jd).contains(&jd) {
+                events.push(IcalEvent {
+                    uid: format!("{}-{}", kind, format_utc(jd)),
+                    summary: summary.to_string(),
+                    description: None,
+                    category: "Fase Bulan",
+                    start: IcalStart::Instant { jd_utc: jd },
+                });
+            }
+        }
+
+        let next = crate::calendar::jd_to_gregorian(conjunction + SYNODIC_MONTH);
+        conjunction = crate::astronomy::find_conjunction(&next).jd_utc;
+    }
+    events
+}
+
+/// Waktu shalat fardhu harian dalam rentang [start_jd, end_jd)
+fn prayer_events(
+    location: &GeoLocation,
+    config: &PrayerConfig,
+    start_jd: f64,
+    end_jd: f64,
+) -> Vec<IcalEvent> {
+    let format = crate::astronomy::prayer_format::PrayerTimeFormat::default();
+    let mut events = Vec::new();
+
+    let mut day_jd = start_jd;
+    while day_jd < end_jd {
+        let date = crate::calendar::jd_to_gregorian(day_jd);
+        let times = crate::astronomy::prayer::calculate_prayer_times(location, &date, config);
+        for (id, name, time) in [
+            ("shubuh", "Shubuh", &times.shubuh),
+            ("dzuhur", "Dzuhur", &times.dzuhur),
+            ("ashr", "Ashr", &times.ashr),
+            ("maghrib", "Maghrib", &times.maghrib),
+            ("isya", "Isya", &times.isya),
+        ] {
+            // Waktu tidak terdefinisi (matahari tidak mencapai ketinggian) dilewati
+            let Some(event) = time else {
+                continue;
+            };
+            events.push(IcalEvent {
+                uid: format!("prayer-{}-{}", format_date(day_jd), id),
+                summary: format!("{} ({})", name, format.format_event(id, Some(event))),
+                description: location.name.clone(),
+                category: "Waktu Shalat",
+                start: IcalStart::Instant {
+                    jd_utc: event.jd_utc,
+                },
+            });
+        }
+        day_jd += 1.0;
+    }
+    events
+}
+
+/// Tulis VCALENDAR lengkap (baris diakhiri CRLF dan dilipat per 75 oktet)
+pub fn write_calendar(name: &str, events: &[IcalEvent], dtstamp: &str) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Al-Falak DPUA//Hisab Calendar//ID".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+    ];
+
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@al-falak-dpua", event.uid));
+        lines.push(format!("DTSTAMP:{}", dtstamp));
+        match event.start {
+            IcalStart::AllDay { start_jd, days } => {
+                lines.push(format!("DTSTART;VALUE=DATE:{}", format_date(start_jd)));
+                lines.push(format!(
+                    "DTEND;VALUE=DATE:{}",
+                    format_date(start_jd + days as f64)
+                ));
+            }
+            IcalStart::Instant { jd_utc } => {
+                lines.push(format!("DTSTART:{}", format_utc(jd_utc)));
+            }
+        }
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(description) = &event.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        lines.push(format!("CATEGORIES:{}", escape_text(event.category)));
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line)).collect()
+}
+
+/// Escape TEXT (RFC 5545 3.3.11)
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Lipat baris > 75 oktet tanpa memotong karakter UTF-8, lalu tambahkan CRLF
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut octets = 0;
+    for ch in line.chars() {
+        if octets + ch.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1; // Spasi awal baris lanjutan
+        }
+        folded.push(ch);
+        octets += ch.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+/// Tanggal (YYYYMMDD) dari JD tengah malam
+fn format_date(jd: f64) -> String {
+    let date = crate::calendar::jd_to_gregorian(jd + 0.5 / 86400.0);
+    format!(
+        "{:04}{:02}{:02}",
+        date.year,
+        date.month,
+        date.day.floor() as u8
+    )
+}
+
+/// Waktu UTC (YYYYMMDDTHHMMSSZ) dari JD, dibulatkan ke detik
+fn format_utc(jd: f64) -> String {
+    let total = ((jd + 0.5) * 86400.0).round() as i64;
+    let midnight = total.div_euclid(86400) as f64 - 0.5;
+    let seconds = total.rem_euclid(86400);
+    format!(
+        "{}T{:02}{:02}{:02}Z",
+        format_date(midnight),
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_escaping_and_folding() {
+        let event = IcalEvent {
+            uid: "test".to_string(),
+            summary: "Idul Fitri, 1 Syawal; \"Ied\"".to_string(),
+            description: Some("ٱلْحَمْدُ لِلَّٰهِ ".repeat(8)),
+            category: "Hari Besar Islam",
+            start: IcalStart::AllDay {
+                start_jd: 2461119.5,
+                days: 1,
+            },
+        };
+        let ics = write_calendar("Kalender", &[event], "20260101T000000Z");
+
+        assert!(ics.contains("SUMMARY:Idul Fitri\\, 1 Syawal\\; \"Ied\"\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20260320\r\nDTEND;VALUE=DATE:20260321\r\n"));
+        for line in ics.split("\r\n") {
+            assert!(line.len() <= MAX_LINE_OCTETS, "{}", line);
+        }
+        // Baris lanjutan diawali spasi dan tetap UTF-8 utuh
+        assert!(ics.contains("\r\n "));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn test_calendar_events_1447() {
+        let options = IcalExportOptions {
+            hijri_year: 1447,
+            years: 1,
+            calendar: HijriCalendarKind::default(),
+            month_starts: true,
+            holidays: true,
+            moon_phases: true,
+            prayer_location: None,
+            prayer_method: None,
+        };
+        let events = calendar_events(&options).unwrap();
+        let ics = write_calendar("Kalender Hijriah 1447", &events, "20260101T000000Z");
+
+        let count = |category: &str| events.iter().filter(|e| e.category == category).count();
+        assert_eq!(count("Awal Bulan"), 12);
+        assert_eq!(count("Hari Besar Islam"), 21);
+        assert!((24..=26).contains(&count("Fase Bulan")));
+
+        // Idul Fitri (tabular) 20 Maret 2026, ijtimak 17 Feb 2026 12:01 UT
+        assert!(ics.contains("SUMMARY:Idul Fitri 1447 H\r\n"));
+        assert!(ics.contains("UID:holiday-1447-10-01-eid_al_fitr@al-falak-dpua"));
+        assert!(ics.contains("DTSTART:20260217T120"));
+        assert_eq!(
+            ics.matches("BEGIN:VEVENT").count(),
+            ics.matches("END:VEVENT").count()
+        );
+    }
+}
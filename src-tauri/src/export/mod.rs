@@ -0,0 +1,8 @@
+//! Module untuk ekspor data ke format file eksternal
+//!
+//! # Format
+//! - `csv.rs`: CSV untuk jadwal waktu shalat
+//! - `ical.rs`: iCalendar (RFC 5545) untuk awal bulan, hari besar, fase bulan dan waktu shalat
+
+pub mod csv;
+pub mod ical;
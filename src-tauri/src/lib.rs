@@ -84,13 +84,14 @@ pub enum VisibilityCriteria {
     WujudulHilal,
     Turkey,
     Danjon,
-    Custom,
+    Custom(String), // Id kriteria kustom dari file aturan
 }
 
 pub mod astronomy;
 pub mod calendar;
 pub mod commands;
 pub mod criteria;
+pub mod export;
 pub mod map;
 pub mod validation;
 
@@ -120,6 +121,22 @@ fn parse_date_string(date_str: &str) -> Result<GregorianDate, String> {
     })
 }
 
+/// Parse string RFC3339 menjadi Julian Day UT (offset zona waktu ikut dihitung)
+fn parse_date_to_jd_utc(date_str: &str) -> Result<JulianDay, String> {
+    let dt = chrono::DateTime::parse_from_rfc3339(date_str)
+        .map_err(|e| format!("Invalid date format: {}", e))?
+        .with_timezone(&chrono::Utc);
+
+    Ok(calendar::gregorian_to_jd(&GregorianDate {
+        year: dt.year(),
+        month: dt.month() as u8,
+        day: dt.day() as f64
+            + (dt.hour() as f64 / 24.0)
+            + (dt.minute() as f64 / 1440.0)
+            + (dt.second() as f64 / 86400.0),
+    }))
+}
+
 // Old placeholder functions - replaced by command handlers in commands/ module
 // These can be safely removed as they are now handled by:
 // - commands::hilal::calculate_hilal_visibility_command
@@ -169,25 +186,36 @@ fn calculate_qibla(location: &GeoLocation) -> f64 {
 fn get_prayer_times(
     location: GeoLocation,
     date: String,
+    method: Option<astronomy::prayer::PrayerSettings>,
+    format: Option<astronomy::prayer_format::PrayerTimeFormat>,
 ) -> Result<std::collections::HashMap<String, String>, String> {
     let parsed_date = parse_date_string(&date)?;
 
-    // Gunakan konfigurasi default dulu (bisa ditambahkan parameter config nanti)
-    let config = astronomy::prayer::PrayerConfig::default();
+    // Nama metode (mis. "mwl") atau konfigurasi lengkap; default Kemenag RI
+    let config = method.map(|m| m.config()).unwrap_or_default();
 
     let times = astronomy::prayer::calculate_prayer_times(&location, &parsed_date, &config);
+    let text = astronomy::prayer_format::format_prayer_times(&times, &format.unwrap_or_default());
 
     let mut map = std::collections::HashMap::new();
-    map.insert("imsak".to_string(), times.imsak);
-    map.insert("shubuh".to_string(), times.shubuh);
-    map.insert("syuruq".to_string(), times.terbit); // Mapping "terbit" -> "syuruq"
-    map.insert("dhuha".to_string(), times.dhuha);
-    map.insert("dzuhur".to_string(), times.dzuhur);
-    map.insert("ashr".to_string(), times.ashr);
-    map.insert("maghrib".to_string(), times.maghrib);
-    map.insert("isya".to_string(), times.isya);
-    map.insert("tengah_malam".to_string(), times.tengah_malam);
-    map.insert("p3_malam".to_string(), times.p3_malam);
+    map.insert("imsak".to_string(), text.imsak);
+    map.insert("shubuh".to_string(), text.shubuh);
+    map.insert("syuruq".to_string(), text.terbit); // Mapping "terbit" -> "syuruq"
+    map.insert("dhuha".to_string(), text.dhuha);
+    map.insert("dzuhur".to_string(), text.dzuhur);
+    map.insert("ashr".to_string(), text.ashr);
+    map.insert("maghrib".to_string(), text.maghrib);
+    map.insert("isya".to_string(), text.isya);
+    map.insert("tengah_malam".to_string(), text.tengah_malam);
+    map.insert("p3_malam".to_string(), text.p3_malam);
+    map.insert("istiwa".to_string(), text.istiwa);
+    // Aturan lintang tinggi yang dipakai, mis. "isya_high_latitude" => "angle_based"
+    for adjustment in &times.high_latitude {
+        map.insert(
+            format!("{}_high_latitude", adjustment.prayer),
+            adjustment.rule.name().to_string(),
+        );
+    }
     map.insert(
         "day_name".to_string(),
         crate::calendar::javanese::get_full_day_name(calendar::gregorian_to_jd(&parsed_date)),
@@ -202,6 +230,8 @@ fn get_prayer_times_hijri(
     hijri_year: i32,
     hijri_month: u8,
     hijri_day: u8,
+    method: Option<astronomy::prayer::PrayerSettings>,
+    format: Option<astronomy::prayer_format::PrayerTimeFormat>,
 ) -> Result<std::collections::HashMap<String, String>, String> {
     let hijri_date = crate::calendar::HijriDate::new(hijri_year, hijri_month, hijri_day);
     let gregorian_date = crate::calendar::hijri_to_gregorian(&hijri_date);
@@ -209,48 +239,7 @@ fn get_prayer_times_hijri(
         "{:04}-{:02}-{:02}T12:00:00Z",
         gregorian_date.year, gregorian_date.month, gregorian_date.day as u8
     );
-    get_prayer_times(location, date_str)
-}
-
-#[tauri::command]
-fn calculate_visibility_zones(
-    date: String,
-    criteria: String,
-    step_degrees: f64,
-) -> Result<Vec<map::VisibilityZone>, String> {
-    // Parse date string to JD
-    let dt = chrono::DateTime::parse_from_rfc3339(&date)
-        .map_err(|e| format!("Invalid date format: {}", e))?;
-
-    let gregorian = GregorianDate {
-        year: dt.year(),
-        month: dt.month() as u8,
-        day: dt.day() as f64
-            + (dt.hour() as f64 / 24.0)
-            + (dt.minute() as f64 / 1440.0)
-            + (dt.second() as f64 / 86400.0),
-    };
-
-    let jd = calendar::gregorian_to_jd(&gregorian);
-
-    // Call the calculation function
-    let zones = map::calculate_visibility_zones_internal(jd, &criteria, step_degrees);
-    Ok(zones)
-}
-
-#[tauri::command]
-fn calculate_visibility_zones_hijri(
-    hijri_year: i32,
-    hijri_month: u8,
-    hijri_day: u8,
-    criteria: String,
-    step_degrees: f64,
-) -> Result<Vec<map::VisibilityZone>, String> {
-    let hijri_date = crate::calendar::HijriDate::new(hijri_year, hijri_month, hijri_day);
-    let gregorian_date = crate::calendar::hijri_to_gregorian(&hijri_date);
-    let jd = calendar::gregorian_to_jd(&gregorian_date);
-    let zones = map::calculate_visibility_zones_internal(jd, &criteria, step_degrees);
-    Ok(zones)
+    get_prayer_times(location, date_str, method, format)
 }
 
 #[tauri::command]
@@ -259,53 +248,26 @@ fn get_detailed_hilal_data(
     year: i32,
     month: u8,
     day: u8,
+    rise_set: Option<astronomy::rise_set::RiseSetOptions>,
 ) -> Result<map::DetailedHilalData, String> {
-    // Create date
+    if !(1..=12).contains(&month) {
+        return Err("Invalid month (1-12)".to_string());
+    }
+    if !(1..=31).contains(&day) {
+        return Err("Invalid day (1-31)".to_string());
+    }
+
     let observation_date = GregorianDate {
         year,
         month,
         day: day as f64,
     };
 
-    let jd = calendar::gregorian_to_jd(&observation_date);
-    let conjunction = astronomy::find_conjunction_for_month(year, month);
-
-    // Calculate sunset
-    let sunset_hour = astronomy::calculate_sunset(&location, &observation_date);
-    let sunset_jd = jd + (sunset_hour / 24.0);
-
-    // Get positions at sunset
-    let moon_pos = astronomy::moon_position(sunset_jd);
-    let sun_pos = astronomy::sun_position(sunset_jd);
-
-    // Calculate parameters
-    let moon_age_hours = (sunset_jd - conjunction.jd_utc) * 24.0;
-    let mut elongation = moon_pos.longitude - sun_pos.longitude;
-    if elongation < 0.0 {
-        elongation += 360.0;
-    }
-
-    Ok(map::DetailedHilalData {
-        conjunction_jd: conjunction.jd_utc,
-        conjunction_date: format!("{}-{:02}-{:02}", year, month, day),
-        sunset_time: sunset_hour,
-        moonset_time: sunset_hour + 1.0, // Simplified
-        moon_age_hours,
-        moon_altitude: 5.0,  // Placeholder
-        moon_azimuth: 270.0, // Placeholder
-        sun_altitude: 0.0,
-        sun_azimuth: 270.0,
-        elongation,
-        moon_distance_km: moon_pos.distance,
-        sun_distance_km: sun_pos.distance,
-        moon_semidiameter: 0.25,
-        sun_semidiameter: 0.25,
-        parallax: 0.95,
-        refraction: 0.6,
-        arcv: elongation - 5.0,
-        crescent_width: 0.5,
-        day_name: crate::calendar::javanese::get_full_day_name(jd),
-    })
+    Ok(map::calculate_detailed_hilal_data(
+        &location,
+        &observation_date,
+        &rise_set.unwrap_or_default(),
+    ))
 }
 
 #[tauri::command]
@@ -314,6 +276,7 @@ fn get_detailed_hilal_data_hijri(
     hijri_year: i32,
     hijri_month: u8,
     hijri_day: u8,
+    rise_set: Option<astronomy::rise_set::RiseSetOptions>,
 ) -> Result<map::DetailedHilalData, String> {
     let hijri_date = crate::calendar::HijriDate::new(hijri_year, hijri_month, hijri_day);
     let gregorian_date = crate::calendar::hijri_to_gregorian(&hijri_date);
@@ -322,22 +285,22 @@ fn get_detailed_hilal_data_hijri(
         gregorian_date.year,
         gregorian_date.month,
         gregorian_date.day as u8,
+        rise_set,
     )
 }
 
 #[tauri::command]
 fn get_ephemeris_data(
-    location: GeoLocation,
     start_date: String,
     end_date: String,
+    step: Option<astronomy::ephemeris::EphemerisStep>,
 ) -> Result<Vec<AstronomicalData>, String> {
-    // TODO: Implement ephemeris data retrieval for date range
-    let _start = parse_date_string(&start_date)?;
-    let _end = parse_date_string(&end_date)?;
-    let _loc = location;
+    // Posisi bersifat geosentris sehingga tidak bergantung lokasi pengamat
+    let start_jd = parse_date_to_jd_utc(&start_date)?;
+    let end_jd = parse_date_to_jd_utc(&end_date)?;
+    let step = step.unwrap_or(astronomy::ephemeris::EphemerisStep::days(1.0));
 
-    // Placeholder: return empty array until implemented
-    Ok(vec![])
+    astronomy::ephemeris::generate_ephemeris_table(start_jd, end_jd, step)
 }
 
 #[tauri::command]
@@ -345,12 +308,32 @@ fn get_ephemeris_data_hijri(
     location: GeoLocation,
     hijri_year: i32,
     hijri_month: u8,
+    step: Option<astronomy::ephemeris::EphemerisStep>,
 ) -> Result<Vec<AstronomicalData>, String> {
-    // Return placeholder
-    let _loc = location;
-    let _y = hijri_year;
-    let _m = hijri_month;
-    Ok(vec![])
+    if !(1..=12).contains(&hijri_month) {
+        return Err("Hijri month must be between 1 and 12".to_string());
+    }
+
+    // Satu bulan Hijriah penuh: 1 bulan ini s/d sebelum 1 bulan berikutnya
+    let (next_year, next_month) = if hijri_month == 12 {
+        (hijri_year + 1, 1)
+    } else {
+        (hijri_year, hijri_month + 1)
+    };
+    let first_day = calendar::HijriDate::new(hijri_year, hijri_month, 1);
+    let next_first_day = calendar::HijriDate::new(next_year, next_month, 1);
+    let start_jd = calendar::gregorian_to_jd(&calendar::hijri_to_gregorian(&first_day));
+    let next_jd = calendar::gregorian_to_jd(&calendar::hijri_to_gregorian(&next_first_day));
+
+    // Tengah malam waktu lokal -> UT; batas akhir eksklusif (mundur 1 detik)
+    let tz_offset = location.timezone / 24.0;
+    let step = step.unwrap_or(astronomy::ephemeris::EphemerisStep::days(1.0));
+
+    astronomy::ephemeris::generate_ephemeris_table(
+        start_jd - tz_offset,
+        next_jd - tz_offset - 1.0 / 86400.0,
+        step,
+    )
 }
 
 // Replaced by commands::validation::validate_location_command
@@ -361,6 +344,7 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
+        .manage(crate::commands::map_cmd::MapJobs::default())
         .invoke_handler(tauri::generate_handler![
             greet,
             crate::commands::hilal::calculate_hilal_visibility_command,
@@ -369,10 +353,29 @@ pub fn run() {
             crate::commands::astronomical::get_astronomical_data_hijri_command,
             crate::commands::calendar_cmd::gregorian_to_hijri_command,
             crate::commands::calendar_cmd::hijri_to_gregorian_command,
+            crate::commands::calendar_cmd::generate_hisab_calendar_command,
+            crate::commands::calendar_cmd::generate_khgt_calendar_command,
+            crate::commands::calendar_cmd::umm_al_qura_hajj_dates_command,
+            crate::commands::calendar_cmd::gregorian_to_javanese_command,
+            crate::commands::calendar_cmd::javanese_to_gregorian_command,
+            crate::commands::calendar_cmd::calendar_month_grid_command,
+            crate::commands::calendar_cmd::islamic_holidays_command,
+            crate::commands::export_cmd::export_icalendar_command,
+            crate::commands::export_cmd::export_prayer_timetable_command,
+            crate::commands::prayer_cmd::prayer_timetable_command,
+            crate::commands::prayer_cmd::prayer_methods_command,
+            crate::commands::prayer_cmd::prayer_times_command,
+            crate::commands::criteria_cmd::list_criteria_command,
+            crate::commands::criteria_cmd::load_custom_criteria_command,
+            crate::commands::criteria_cmd::remove_custom_criteria_command,
+            crate::commands::criteria_cmd::evaluate_khgt_global_command,
             crate::commands::validation::validate_location_command,
             crate::commands::validation::run_validation_tests_command,
-            calculate_visibility_zones,
-            calculate_visibility_zones_hijri,
+            crate::commands::map_cmd::calculate_visibility_zones,
+            crate::commands::map_cmd::calculate_visibility_zones_hijri,
+            crate::commands::map_cmd::calculate_visibility_geojson,
+            crate::commands::map_cmd::calculate_visibility_series,
+            crate::commands::map_cmd::cancel_visibility_zones,
             get_detailed_hilal_data,
             get_detailed_hilal_data_hijri,
             get_ephemeris_data,
@@ -443,4 +446,31 @@ mod tests {
 
         println!("{}\n", "=".repeat(80));
     }
+
+    #[test]
+    fn test_ephemeris_data_ranges() {
+        let jakarta = GeoLocation {
+            name: Some("Jakarta".to_string()),
+            latitude: -6.2,
+            longitude: 106.816,
+            elevation: 8.0,
+            timezone: 7.0,
+        };
+
+        // Offset +07:00 harus dikonversi ke UT: 00:00 WIB = 17:00 UT hari sebelumnya
+        let rows = get_ephemeris_data(
+            "2026-02-17T00:00:00+07:00".to_string(),
+            "2026-02-17T23:00:00+07:00".to_string(),
+            Some(astronomy::ephemeris::EphemerisStep::hours(1.0)),
+        )
+        .unwrap();
+        assert_eq!(rows.len(), 24);
+        let expected_start = parse_date_to_jd_utc("2026-02-16T17:00:00Z").unwrap();
+        let first_sun = astronomy::sun_position(expected_start);
+        assert!((rows[0].sun_position.longitude - first_sun.longitude).abs() < 1e-9);
+
+        // Ramadhan 1447 (tabular) = 29 atau 30 baris harian
+        let ramadhan = get_ephemeris_data_hijri(jakarta, 1447, 9, None).unwrap();
+        assert!(ramadhan.len() == 29 || ramadhan.len() == 30);
+    }
 }
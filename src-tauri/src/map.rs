@@ -1,18 +1,45 @@
 //! Module untuk logika visualisasi peta
 
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::astronomy::rise_set::RiseSetOptions;
+
+pub mod contour;
+pub mod series;
+
 /// Struktur untuk zona visibilitas hilal
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VisibilityZone {
     pub latitude: f64,
+    pub latitude_end: f64,
     pub longitude_start: f64,
     pub longitude_end: f64,
-    pub step: f64,
+    pub step: f64,     // Langkah lintang (derajat)
+    pub lon_step: f64, // Langkah bujur (derajat)
     pub is_visible: bool,
-    pub q_value: f64,
-    pub visibility_level: i32, // 0: Impossible, 1: Difficult, 2: Aid Required, 3: Visible, 4: Easy
+    pub q_value: Option<f64>, // None (null di JSON) jika tidak ada maghrib
+    /// 0: Impossible, 1: Difficult, 2: Aid Required, 3: Visible, 4: Easy; -1: Yallop F (di bawah
+    /// batas Danjon)
+    pub visibility_level: i32,
     pub criteria: String,
+    pub cell_state: CellState,
+}
+
+/// Status sel grid peta
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CellState {
+    /// Kriteria dievaluasi pada saat maghrib
+    Evaluated,
+    /// Matahari tidak terbenam / tidak terbit (siang atau malam kutub), kriteria tidak dievaluasi
+    NoSunset,
+    /// Ada maghrib tetapi bulan tidak terbenam hari itu (di atas ufuk sepanjang hari atau
+    /// tidak terbit sama sekali), kriteria tetap dievaluasi
+    NoMoonset,
 }
 
 /// Struktur untuk data hilal mendalam (digunakan oleh Detail view)
@@ -46,174 +73,464 @@ pub struct PrayerTimeCurve {
     pub points: Vec<(f64, f64)>, // (lat, lon) pairs
 }
 
+/// Hitung data detail hilal untuk satu lokasi pada saat maghrib (Detail view peta)
+///
+/// Semua nilai dihitung pada JD maghrib (UT) di tanggal lokal `date`:
+/// - Tinggi bulan toposentris (refraksi + parallax, VB6)
+/// - Azimuth bulan & matahari dari LST nyata
+/// - Semidiameter & parallax sesuai rumus VB6 (PosisiBulan.bas)
+/// - ARCV airless dan lebar hilal toposentris (arc-minutes)
+///
+/// `rise_set` menentukan waktu maghrib (tekanan, suhu, jenis ufuk).
+pub fn calculate_detailed_hilal_data(
+    location: &crate::GeoLocation,
+    date: &crate::GregorianDate,
+    rise_set: &RiseSetOptions,
+) -> DetailedHilalData {
+    use crate::astronomy::{self, ephemeris_utils, topocentric};
+
+    let observation_date = crate::GregorianDate {
+        year: date.year,
+        month: date.month,
+        day: date.day.floor(),
+    };
+    let jd = crate::calendar::gregorian_to_jd(&observation_date);
+
+    // Konjungsi terdekat dengan tanggal observasi (sama dengan command hilal)
+    let conjunction = astronomy::conjunction::find_conjunction(&observation_date);
+
+    // Snapshot parameter hilal saat maghrib (UT), sama dengan yang dipakai kriteria
+    let params = crate::criteria::HilalParameters::with_rise_set(
+        location,
+        &observation_date,
+        conjunction.jd_utc,
+        rise_set,
+    );
+    let sunset_hour = astronomy::rise_set::sunset(location, &observation_date, rise_set);
+
+    let moon_geo = astronomy::moon_position(params.sunset_jd);
+
+    // Refraksi (derajat) dengan model yang sama seperti moon_altitude_topocentric
+    let moon_refraction = topocentric::atmospheric_refraction(params.moon_altitude_geo) / 60.0;
+    let sun_refraction = topocentric::atmospheric_refraction(params.sun_altitude_geo) / 60.0;
+
+    // Parallax matahari 8.794" / AU
+    let sun_hp_deg = 8.794 / 3600.0 / params.sun_distance_au;
+    let sun_altitude = params.sun_altitude_geo + sun_refraction - sun_hp_deg;
+
+    // Semidiameter VB6: Sun.SD = 959.63 / R(AU)
+    let sun_semidiameter = (959.63 / params.sun_distance_au) / 3600.0;
+
+    DetailedHilalData {
+        conjunction_jd: conjunction.jd_utc,
+        conjunction_date: ephemeris_utils::format_jd_to_datetime(
+            conjunction.jd_utc,
+            location.timezone,
+        ),
+        sunset_time: sunset_hour,
+        moonset_time: ephemeris_utils::calculate_moonset(location, &observation_date),
+        moon_age_hours: params.moon_age_hours,
+        moon_altitude: params.moon_altitude_topo,
+        moon_azimuth: params.moon_azimuth,
+        sun_altitude,
+        sun_azimuth: params.sun_azimuth,
+        elongation: params.elongation_topo,
+        moon_distance_km: moon_geo.distance,
+        sun_distance_km: params.sun_distance_au * 149_597_870.7,
+        moon_semidiameter: params.moon_semidiameter,
+        sun_semidiameter,
+        parallax: params.moon_parallax.to_degrees(),
+        refraction: moon_refraction,
+        arcv: params.arcv,
+        crescent_width: params.crescent_width,
+        day_name: crate::calendar::javanese::get_full_day_name(jd),
+    }
+}
+
+/// Resolusi grid peta yang diizinkan (derajat)
+pub const MIN_MAP_STEP: f64 = 0.05;
+pub const MAX_MAP_STEP: f64 = 5.0;
+
+/// Batas jumlah sel satu peta (dunia penuh pada 0.25° ~ 1.04 juta sel)
+pub const MAX_MAP_CELLS: usize = 1_100_000;
+
+/// Di atas lintang ini bulan bisa sirkumpolar atau tidak terbit (deklinasi bulan maks ~28.6°)
+const CIRCUMPOLAR_MOON_LATITUDE: f64 = 60.0;
+
+/// Batas wilayah peta (derajat)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MapBounds {
+    pub lat_min: f64,
+    pub lat_max: f64,
+    pub lon_min: f64,
+    pub lon_max: f64,
+}
+
+impl MapBounds {
+    /// Peta dunia default: lintang ±60°, di luar itu hilal praktis tidak diamati
+    pub const WORLD: MapBounds = MapBounds {
+        lat_min: -60.0,
+        lat_max: 60.0,
+        lon_min: -180.0,
+        lon_max: 180.0,
+    };
+
+    /// Seluruh bola bumi, termasuk pita kutub (hanya jika diminta lewat `MapGrid`)
+    pub const GLOBE: MapBounds = MapBounds {
+        lat_min: -90.0,
+        lat_max: 90.0,
+        lon_min: -180.0,
+        lon_max: 180.0,
+    };
+
+    /// Wilayah MABIMS (Asia Tenggara)
+    pub const MABIMS: MapBounds = MapBounds {
+        lat_min: -15.0,
+        lat_max: 25.0,
+        lon_min: 90.0,
+        lon_max: 145.0,
+    };
+
+    pub fn validate(&self) -> Result<(), String> {
+        let finite = [self.lat_min, self.lat_max, self.lon_min, self.lon_max]
+            .iter()
+            .all(|v| v.is_finite());
+        if !finite {
+            return Err("Map bounds must be finite numbers".to_string());
+        }
+        if self.lat_min < -90.0 || self.lat_max > 90.0 || self.lat_min >= self.lat_max {
+            return Err(format!(
+                "Invalid latitude range: {} .. {} (must be within -90 .. 90)",
+                self.lat_min, self.lat_max
+            ));
+        }
+        if self.lon_min < -180.0 || self.lon_max > 180.0 || self.lon_min >= self.lon_max {
+            return Err(format!(
+                "Invalid longitude range: {} .. {} (must be within -180 .. 180)",
+                self.lon_min, self.lon_max
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for MapBounds {
+    fn default() -> Self {
+        Self::WORLD
+    }
+}
+
+/// Grid peta: wilayah + langkah lintang/bujur terpisah
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MapGrid {
+    #[serde(default)]
+    pub bounds: MapBounds,
+    pub lat_step: f64,
+    pub lon_step: f64,
+}
+
+impl MapGrid {
+    /// Grid peta dunia default (±60°) dengan langkah yang sama untuk lintang dan bujur
+    pub fn uniform(step_degrees: f64) -> Self {
+        Self {
+            bounds: MapBounds::WORLD,
+            lat_step: step_degrees,
+            lon_step: step_degrees,
+        }
+    }
+
+    fn lat_step(&self) -> f64 {
+        self.lat_step.clamp(MIN_MAP_STEP, MAX_MAP_STEP)
+    }
+
+    fn lon_step(&self) -> f64 {
+        self.lon_step.clamp(MIN_MAP_STEP, MAX_MAP_STEP)
+    }
+
+    fn lat_count(&self) -> usize {
+        ((self.bounds.lat_max - self.bounds.lat_min) / self.lat_step() - 1e-9).ceil() as usize
+    }
+
+    fn lon_count(&self) -> usize {
+        ((self.bounds.lon_max - self.bounds.lon_min) / self.lon_step() - 1e-9).ceil() as usize
+    }
+
+    /// Jumlah sel grid (setelah langkah di-clamp)
+    pub fn cell_count(&self) -> usize {
+        self.lat_count() * self.lon_count()
+    }
+
+    /// Titik sampel (lat, lon) setiap sel, urut baris lintang lalu bujur
+    pub fn cells(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        let (lat_step, lon_step) = (self.lat_step(), self.lon_step());
+        (0..self.lat_count()).flat_map(move |i| {
+            (0..self.lon_count()).map(move |j| {
+                (
+                    self.bounds.lat_min + i as f64 * lat_step,
+                    self.bounds.lon_min + j as f64 * lon_step,
+                )
+            })
+        })
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.bounds.validate()?;
+        if !self.lat_step.is_finite() || !self.lon_step.is_finite() {
+            return Err("Map step must be a finite number".to_string());
+        }
+        let cells = self.cell_count();
+        if cells > MAX_MAP_CELLS {
+            return Err(format!(
+                "Map grid too large: {} cells (max {}), use a larger step or smaller region",
+                cells, MAX_MAP_CELLS
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Progres perhitungan peta: jumlah sel selesai dari total sel grid
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MapProgress {
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Token pembatalan perhitungan peta (dibagi antara command dan worker)
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Apakah dua token menunjuk ke job yang sama
+    pub fn same_job(&self, other: &CancelToken) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
 /// Fungsi untuk menghitung zona visibilitas (internal) - High Fidelity Version
 pub fn calculate_visibility_zones_internal(
     observation_jd: f64,
     criteria: &str,
     step_degrees: f64,
-) -> Vec<VisibilityZone> {
-    let mut zones = Vec::new();
+) -> Result<Vec<VisibilityZone>, String> {
+    calculate_visibility_zones_parallel(
+        observation_jd,
+        criteria,
+        &MapGrid::uniform(step_degrees),
+        &RiseSetOptions::default(),
+        &CancelToken::new(),
+        |_| {},
+    )
+}
 
+/// Hitung zona visibilitas secara paralel (per baris lintang) dengan progres dan pembatalan
+///
+/// `on_progress` dipanggil setiap satu baris lintang selesai. Jika `cancel` diaktifkan,
+/// perhitungan berhenti secepatnya dan mengembalikan Err. `rise_set` menentukan maghrib
+/// setiap sel (sel grid berada di ketinggian 0 m).
+pub fn calculate_visibility_zones_parallel<F>(
+    observation_jd: f64,
+    criteria: &str,
+    grid: &MapGrid,
+    rise_set: &RiseSetOptions,
+    cancel: &CancelToken,
+    on_progress: F,
+) -> Result<Vec<VisibilityZone>, String>
+where
+    F: Fn(MapProgress) + Sync,
+{
     // 1. Cari Konjungsi terdekat (sebelum tanggal observasi)
     // Map visibilitas selalu dihitung relatif terhadap maghrib pertama setelah ijtima'
     let obs_date = crate::calendar::jd_to_gregorian(observation_jd);
     let conjunction = crate::astronomy::conjunction::find_conjunction(&obs_date);
-    let conj_jd = conjunction.jd_utc;
-
-    // Grid untuk latitude dan longitude
-    // Resolusi tinggi untuk kurva yang mulus
-    let lat_start = -60.0;
-    let lat_end = 60.0;
-    let lon_start = -180.0;
-    let lon_end = 180.0;
-
-    let step = step_degrees.max(1.0).min(5.0);
-
-    let mut lat = lat_start;
-    while lat < lat_end {
-        let mut lon = lon_start;
-        while lon < lon_end {
-            // Logika "First Sunset after Conjunction":
-            // Kita cari sunset di lokasi (lat, lon) pada hari H (obs_date)
-            // Jika sunset itu terjadi sebelum konjungsi, maka kita cari sunset hari berikutnya.
-
-            let mut eval_date = obs_date.clone();
-            let location = crate::GeoLocation {
-                name: None,
-                latitude: lat,
-                longitude: lon,
-                elevation: 0.0,
-                timezone: 0.0, // UT reference
-            };
-
-            // Hitung sunset di lokasi ini (UT)
-            let mut sunset_hour = crate::astronomy::calculate_sunset(&location, &eval_date);
-
-            // Perlakuan jika tidak ada sunset (kutub)
-            if sunset_hour > 900.0 {
-                lon += step;
-                continue;
-            }
 
-            let sunset_jd = crate::calendar::gregorian_to_jd(&eval_date) + (sunset_hour / 24.0);
-
-            // STRICT DATE adherence: Do NOT auto-advance the date.
-            // If the user selects a date before conjunction, the map should simply show "Impossible" (Red).
-            // This ensures consistency with the text result.
-
-            // Hitung visibilitas mendalam menggunakan Odeh pada waktu sunset tersebut
-            let odeh = crate::criteria::odeh::evaluate_odeh(&location, &eval_date);
-
-            // Standard AHC Levels (A-E)
-            let mut level = if odeh.moon_altitude <= 0.0 {
-                0 // E: Impossible (Below horizon)
-            } else if odeh.q_value > 5.65 {
-                4 // A: Easy
-            } else if odeh.q_value > 0.216 {
-                3 // B: Visible
-            } else if odeh.q_value > -0.014 {
-                2 // C: Aid
-            } else if odeh.q_value > -0.5 {
-                1 // D: Difficult
-            } else {
-                0 // E: Impossible
-            };
-
-            let is_visible = check_visibility_at_location(&location, &eval_date, criteria);
-
-            // STRICT CONSISTENCY: If using binary criteria (like MABIMS), override the gradient level.
-            // This ensures map shows Red/Green matching the boolean result.
-            if criteria != "ODEH" && criteria != "YALLOP" && criteria != "Turkey" {
-                if is_visible {
-                    level = 3; // Force Visible Color
-                } else {
-                    level = 0; // Force Invisible Color
+    evaluate_grid(
+        conjunction.jd_utc,
+        criteria,
+        grid,
+        rise_set,
+        cancel,
+        on_progress,
+        |_, _| obs_date.clone(),
+    )
+}
+
+/// Evaluasi kriteria di setiap sel grid untuk ijtimak `conj_jd` yang sudah diketahui
+///
+/// `evening_date(index, location)` memberi tanggal lokal maghrib yang dievaluasi untuk sel
+/// ke-`index` (urut baris lintang lalu bujur, sama dengan `MapGrid::cells`).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn evaluate_grid<F, D>(
+    conj_jd: f64,
+    criteria: &str,
+    grid: &MapGrid,
+    rise_set: &RiseSetOptions,
+    cancel: &CancelToken,
+    on_progress: F,
+    evening_date: D,
+) -> Result<Vec<VisibilityZone>, String>
+where
+    F: Fn(MapProgress) + Sync,
+    D: Fn(usize, &crate::GeoLocation) -> crate::GregorianDate + Sync,
+{
+    let criterion = crate::criteria::find_criterion(criteria)
+        .ok_or_else(|| format!("Unknown criteria: {}", criteria))?;
+
+    // Grid untuk latitude dan longitude (sel terakhir dipotong di batas wilayah)
+    grid.validate()?;
+    let MapBounds {
+        lat_min,
+        lat_max,
+        lon_min,
+        lon_max,
+    } = grid.bounds;
+    let lat_step = grid.lat_step();
+    let lon_step = grid.lon_step();
+    let lat_count = grid.lat_count();
+    let lon_count = grid.lon_count();
+    let total = lat_count * lon_count;
+    let done = AtomicUsize::new(0);
+
+    let rows: Option<Vec<Vec<VisibilityZone>>> = (0..lat_count)
+        .into_par_iter()
+        .map(|i| {
+            let lat = lat_min + i as f64 * lat_step;
+            let lat_end = (lat + lat_step).min(lat_max);
+            let mut row = Vec::with_capacity(lon_count);
+
+            for j in 0..lon_count {
+                if cancel.is_cancelled() {
+                    return None;
+                }
+                let lon = lon_min + j as f64 * lon_step;
+
+                // Logika "First Sunset after Conjunction":
+                // Kita cari sunset di lokasi (lat, lon) pada hari H (obs_date)
+                // STRICT DATE adherence: Do NOT auto-advance the date.
+                // If the user selects a date before conjunction, the map should simply show "Impossible" (Red).
+                // This ensures consistency with the text result.
+                let location = grid_location(lat, lon);
+
+                // Snapshot parameter hilal dihitung sekali per sel, dipakai kriteria dan q Odeh
+                let date = evening_date(i * lon_count + j, &location);
+                let params = crate::criteria::HilalParameters::with_rise_set(
+                    &location, &date, conj_jd, rise_set,
+                );
+
+                let mut zone = VisibilityZone {
+                    latitude: lat,
+                    latitude_end: lat_end,
+                    longitude_start: lon,
+                    longitude_end: (lon + lon_step).min(lon_max),
+                    step: lat_step,
+                    lon_step,
+                    is_visible: false,
+                    q_value: None,
+                    visibility_level: 0,
+                    criteria: criteria.to_string(),
+                    cell_state: CellState::Evaluated,
+                };
+
+                // Tidak ada sunset (siang/malam kutub): sel tetap dikirim dengan status eksplisit
+                if !params.has_sunset() {
+                    zone.cell_state = CellState::NoSunset;
+                    row.push(zone);
+                    continue;
                 }
+
+                // Bulan sirkumpolar / tidak terbit hanya mungkin di lintang tinggi
+                if lat.abs() >= CIRCUMPOLAR_MOON_LATITUDE
+                    && moon_has_no_moonset(lat, params.sunset_jd)
+                {
+                    zone.cell_state = CellState::NoMoonset;
+                }
+
+                let outcome = criterion.evaluate(&params);
+
+                // Kriteria biner tidak punya q: q Odeh disertakan sebagai informasi tambahan
+                zone.q_value = Some(match outcome.q_value {
+                    Some(q) => q,
+                    None => crate::criteria::odeh::evaluate_odeh_from(&params).q_value,
+                });
+                zone.is_visible = outcome.is_visible;
+                zone.visibility_level = outcome.level;
+                row.push(zone);
             }
 
-            zones.push(VisibilityZone {
-                latitude: lat,
-                longitude_start: lon,
-                longitude_end: lon + step,
-                step,
-                is_visible,
-                q_value: odeh.q_value,
-                visibility_level: level,
-                criteria: criteria.to_string(),
-            });
-
-            lon += step;
-        }
-        lat += step;
-    }
+            let done = done.fetch_add(lon_count, Ordering::Relaxed) + lon_count;
+            on_progress(MapProgress { done, total });
+            Some(row)
+        })
+        .collect();
+
+    rows.map(|rows| rows.into_iter().flatten().collect())
+        .ok_or_else(|| "Visibility map calculation cancelled".to_string())
+}
 
-    zones
+/// Lokasi titik grid dengan zona waktu nominal dari bujur
+///
+/// `calculate_sunset` menormalkan jam lokal ke 0-24, sehingga dengan zona UT maghrib di
+/// bujur barat jauh (setelah 24:00 UT) jatuh ke malam sebelumnya. Zona nominal menjaga
+/// maghrib tetap pada tanggal lokal yang diminta.
+pub fn grid_location(latitude: f64, longitude: f64) -> crate::GeoLocation {
+    crate::GeoLocation {
+        name: None,
+        latitude,
+        longitude,
+        elevation: 0.0,
+        timezone: (longitude / 15.0).round(),
+    }
 }
 
-/// Check visibilitas di lokasi tertentu menggunakan algoritma akurat
-fn check_visibility_at_location(
+/// Tanggal lokal maghrib pertama setelah ijtimak di `location`
+///
+/// Mulai dari tanggal lokal saat ijtimak; jika maghrib hari itu terjadi sebelum ijtimak
+/// (misal ijtimak malam hari di Asia Tenggara), maghrib pertama jatuh esok harinya.
+pub fn first_evening_after(
     location: &crate::GeoLocation,
-    date: &crate::GregorianDate,
-    criteria: &str,
-) -> bool {
-    // Gunakan perhitungan toposentris yang akurat (sesuai VB6/AHC)
-    // Altitude
-    let altitude = crate::astronomy::hilal::altitude_at_sunset(location, date, true);
-
-    // Jika altitude sudah pasti di bawah limit minimum kriteria terendah (-10), skip perhitungan berat lainnya
-    if altitude < -10.0 {
-        return false;
-    }
-
-    // Elongation
-    let elongation = crate::astronomy::hilal::elongation_at_sunset(location, date, true);
-
-    // Moon Age
-    let moon_age = crate::astronomy::hilal::moon_age_at_sunset(location, date);
-
-    // Evaluasi berdasarkan kriteria
-    // TODO: Update dengan kriteria yang lebih lengkap/akurat sesuai setting
-    match criteria {
-        "MABIMS" => {
-            // MABIMS Baru (Neo-MABIMS 2016): Alt >= 3.0 && Elong >= 6.4
-            // MABIMS Lama (2-3-8): Alt >= 2.0 && Elong >= 3.0 && Age >= 8.0
-
-            // Implementasi Default: Neo-MABIMS (karena lebih standar sekarang)
-            // Namun code sebelumnya mengimplikasikan MABIMS Lama.
-            // Mari kita support keduanya atau gunakan yang lebih umum.
-            // Untuk keamanan, kita gunakan Neo-MABIMS sebagai "MABIMS" default jika tidak dispesifikasikan
-
-            // Cek jika user mengharapkan old criteria dari UI?
-            // CriteriaPanel.js menyebutkan "Alt >= 3, Age >= 8" -> Ini campuran aneh.
-            // Mari gunakan Neo-MABIMS yang standar: 3 derajat & 6.4 derajat elongasi
-            altitude >= 3.0 && elongation >= 6.4
-        }
-        "ODEH" => {
-            // Odeh Criteria (Visual)
-            // Kompleks, tapi simplifikasinya:
-            // V = ARCV - ( -0.1018*width^3 + 0.7319*width^2 - 6.3226*width + 7.1651 )
-            // Jika V >= 5.65 -> Visible
-
-            // Gunakan simplifikasi parameter input saja untuk peta cepat:
-            // Alt >= 2.0 && Elong >= 8.0 (sesuai kode sebelumnya)
-            altitude >= 5.0 && elongation >= 8.0 // Kode sebelumnya 5 & 8
-        }
-        "LFNU" => {
-            // LFNU (Lembaga Falakiyah NU)
-            // Imkanur Rukyat NU: Alt >= 3 derajat & Elong >= 6.4 derajat (Sama dengan Neo-MABIMS)
-            // Atau Qat'iyur Rukyat: Alt >= 4 derajat
-            altitude >= 3.0 && elongation >= 6.4
-        }
-        "TURKEY" => altitude >= 5.0 && elongation >= 8.0,
-        "WujudulHilal" => {
-            // Muhammadiyah: Disk piringan atas > 0 setelah matahari terbenam
-            // Moonset > Sunset
-            // Secara matematis: Altitude (geocentric) > 0 ATAU Altitude (topocentric) > 0?
-            // Biasanya Wujudul Hilal menggunakan Geocentric center > 0
-            // Tapi untuk peta visibilitas umum, Topo > 0 cukup mendekati logika "wujud"
-            altitude > 0.0
-        }
-        _ => altitude >= 2.0 && elongation >= 3.0, // Fallback conservative
+    conjunction_jd: f64,
+    rise_set: &RiseSetOptions,
+) -> crate::GregorianDate {
+    let local = crate::calendar::jd_to_gregorian(conjunction_jd + location.timezone / 24.0);
+    let date = crate::GregorianDate {
+        year: local.year,
+        month: local.month,
+        day: local.day.floor(),
+    };
+    if crate::astronomy::hilal::sunset_jd(location, &date, rise_set) > conjunction_jd {
+        date
+    } else {
+        let next_jd = crate::calendar::gregorian_to_jd(&date) + 1.0;
+        crate::calendar::jd_to_gregorian(next_jd)
+    }
+}
+
+/// Apakah bulan tidak terbenam di lintang `lat` sekitar `jd`
+///
+/// Perkiraan cepat (tanpa pencarian moonset per sel) dari tinggi kulminasi, dikurangi
+/// parallax horizontal dan ditambah refraksi di ufuk (34'):
+/// - φ dan δ sebertanda: kulminasi bawah h = |φ| + |δ| - 90° di atas ufuk (sirkumpolar)
+/// - φ dan δ berlawanan tanda: kulminasi atas h = 90° - |φ| - |δ| di bawah ufuk (tidak terbit)
+fn moon_has_no_moonset(lat: f64, jd: f64) -> bool {
+    let dec = crate::astronomy::moon_position(jd).declination;
+    let parallax = crate::astronomy::horizontal_moon_parallax(jd).to_degrees();
+    let horizon = parallax - 34.0 / 60.0;
+    if lat * dec > 0.0 {
+        lat.abs() + dec.abs() - 90.0 > horizon
+    } else {
+        90.0 - lat.abs() - dec.abs() < horizon
     }
 }
 
@@ -222,3 +539,220 @@ pub fn calculate_prayer_curves(_date_jd: f64, _timezone: f64) -> Vec<PrayerTimeC
     // TODO: Implementasi perhitungan kurva shalat
     vec![]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_q_value_criteria_produce_graded_levels() {
+        // 18 Feb 2026: hari setelah ijtimak, hilal terlihat di barat, tidak di timur
+        let jd = crate::calendar::gregorian_to_jd(&crate::GregorianDate {
+            year: 2026,
+            month: 2,
+            day: 18.0,
+        });
+
+        for criteria in ["Odeh", "YALLOP"] {
+            let zones = calculate_visibility_zones_internal(jd, criteria, 5.0).unwrap();
+            assert!(!zones.is_empty());
+
+            let mut levels: Vec<i32> = zones.iter().map(|z| z.visibility_level).collect();
+            levels.sort_unstable();
+            levels.dedup();
+            assert!(levels.iter().all(|l| (-1..=4).contains(l)));
+            assert!(
+                levels.len() > 2,
+                "{} should produce graded A-E levels, got {:?}",
+                criteria,
+                levels
+            );
+        }
+    }
+
+    #[test]
+    fn test_unknown_criteria_is_rejected() {
+        assert!(calculate_visibility_zones_internal(2461089.5, "NotACriterion", 5.0).is_err());
+    }
+
+    #[test]
+    fn test_parallel_progress_and_cancel() {
+        let last = std::sync::Mutex::new(MapProgress { done: 0, total: 0 });
+        let zones = calculate_visibility_zones_parallel(
+            2461089.5,
+            "IjtimaQoblaGhurub",
+            &MapGrid::uniform(5.0),
+            &RiseSetOptions::default(),
+            &CancelToken::new(),
+            |progress| {
+                let mut last = last.lock().unwrap();
+                if progress.done > last.done {
+                    *last = progress;
+                }
+            },
+        )
+        .unwrap();
+        let last = last.into_inner().unwrap();
+        // Peta dunia default: lintang ±60°
+        assert_eq!(last.total, 24 * 72);
+        assert_eq!(last.done, last.total);
+        assert_eq!(zones.len(), last.total);
+        // Urutan grid tetap baris per baris walau dihitung paralel
+        assert!(zones.windows(2).all(|w| w[0].latitude <= w[1].latitude));
+
+        let token = CancelToken::new();
+        token.cancel();
+        let cancelled = calculate_visibility_zones_parallel(
+            2461089.5,
+            "MABIMS",
+            &MapGrid::uniform(0.25),
+            &RiseSetOptions::default(),
+            &token,
+            |_| {},
+        );
+        assert!(cancelled.is_err());
+    }
+
+    #[test]
+    fn test_regional_grid_and_polar_states() {
+        // Wilayah MABIMS: langkah lintang dan bujur berbeda, sel terakhir dipotong di batas
+        let grid = MapGrid {
+            bounds: MapBounds::MABIMS,
+            lat_step: 2.0,
+            lon_step: 3.0,
+        };
+        let zones = calculate_visibility_zones_parallel(
+            2461089.5,
+            "MABIMS",
+            &grid,
+            &RiseSetOptions::default(),
+            &CancelToken::new(),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(zones.len(), 20 * 19);
+        assert!(zones.iter().all(|z| z.cell_state == CellState::Evaluated));
+        assert!(zones.iter().all(|z| z.longitude_end <= 145.0));
+        assert_eq!(zones.last().unwrap().longitude_end, 145.0);
+
+        // Februari: malam kutub di utara -> tidak ada maghrib
+        let arctic = MapGrid {
+            bounds: MapBounds {
+                lat_min: 80.0,
+                lat_max: 90.0,
+                lon_min: 0.0,
+                lon_max: 10.0,
+            },
+            lat_step: 5.0,
+            lon_step: 5.0,
+        };
+        let zones = calculate_visibility_zones_parallel(
+            2461089.5,
+            "Odeh",
+            &arctic,
+            &RiseSetOptions::default(),
+            &CancelToken::new(),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(zones.len(), 4);
+        assert!(zones
+            .iter()
+            .all(|z| z.cell_state == CellState::NoSunset && !z.is_visible));
+
+        let invalid = MapGrid {
+            bounds: MapBounds {
+                lat_min: 10.0,
+                lat_max: 0.0,
+                ..MapBounds::GLOBE
+            },
+            ..arctic
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_grid_location_keeps_local_evening() {
+        // Sel memakai zona waktu nominal: maghrib selalu sekitar 18:00 pada tanggal lokal
+        let date = crate::GregorianDate {
+            year: 2026,
+            month: 2,
+            day: 18.0,
+        };
+        let day_jd = crate::calendar::gregorian_to_jd(&date);
+        for longitude in [-175.0, -120.0, -60.0, 0.0, 60.0, 120.0, 175.0] {
+            let location = grid_location(0.0, longitude);
+            assert_eq!(location.timezone, (longitude / 15.0).round());
+            let sunset_jd =
+                crate::astronomy::hilal::sunset_jd(&location, &date, &RiseSetOptions::default());
+            let local_hour = (sunset_jd - day_jd) * 24.0 + location.timezone;
+            assert!(
+                (17.0..19.5).contains(&local_hour),
+                "{}: {}",
+                longitude,
+                local_hour
+            );
+        }
+
+        // Dengan zona UT, maghrib di bujur barat jauh jatuh ke malam sebelumnya (05:40 UT)
+        let ut = crate::GeoLocation {
+            timezone: 0.0,
+            ..grid_location(0.0, -175.0)
+        };
+        assert!(
+            crate::astronomy::hilal::sunset_jd(&ut, &date, &RiseSetOptions::default())
+                < day_jd + 0.5
+        );
+    }
+
+    #[test]
+    fn test_polar_band_without_moonset() {
+        // 26 Jun 2025 (sehari setelah ijtimak): bulan di utara ekuator, sehingga di pita
+        // sekitar -65° matahari masih terbenam tetapi bulan tidak terbit sama sekali
+        let jd = 2460852.5;
+        let band = MapGrid {
+            bounds: MapBounds {
+                lat_min: -68.0,
+                lat_max: -62.0,
+                ..MapBounds::GLOBE
+            },
+            lat_step: 2.0,
+            lon_step: 5.0,
+        };
+        let zones = calculate_visibility_zones_parallel(
+            jd,
+            "Odeh",
+            &band,
+            &RiseSetOptions::default(),
+            &CancelToken::new(),
+            |_| {},
+        )
+        .unwrap();
+
+        let date = crate::calendar::jd_to_gregorian(jd);
+        let conjunction = crate::astronomy::conjunction::find_conjunction(&date);
+        let no_moonset: Vec<_> = zones
+            .iter()
+            .filter(|z| z.cell_state == CellState::NoMoonset)
+            .collect();
+        assert!(!no_moonset.is_empty());
+        for zone in no_moonset {
+            // Kriteria tetap dievaluasi, tetapi hilal tidak mungkin terlihat
+            assert!(zone.q_value.is_some() && !zone.is_visible);
+            let location = grid_location(zone.latitude, zone.longitude_start);
+            let params =
+                crate::criteria::HilalParameters::new(&location, &date, conjunction.jd_utc);
+            assert!(params.moonset_jd().is_none());
+            assert!(params.moon_altitude_topo < 0.0);
+        }
+        assert!(zones
+            .iter()
+            .filter(|z| z.cell_state == CellState::NoSunset)
+            .all(|z| z.q_value.is_none()));
+
+        // Di lintang utara tinggi bulan justru di atas ufuk sepanjang hari
+        assert!(moon_has_no_moonset(85.0, jd));
+        assert!(moon_has_no_moonset(-85.0, jd));
+        assert!(!moon_has_no_moonset(0.0, jd));
+    }
+}
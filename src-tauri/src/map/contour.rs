@@ -0,0 +1,439 @@
+//! Kontur peta visibilitas (marching squares) ke GeoJSON
+//!
+//! Grid `VisibilityZone` diubah menjadi satu MultiPolygon per kelas visibilitas:
+//! - Kriteria bergradasi: zona kriteria itu sendiri (`Criterion::classes`, Odeh A-D,
+//!   Yallop A-F)
+//! - Kriteria biner: "visible" / "not_visible"
+//!
+//! Untuk setiap kelas, kontur "level >= ambang" ditelusuri dengan marching squares
+//! berorientasi (daerah dalam selalu di kiri), sehingga ring luar berlawanan arah jarum
+//! jam dan lubang searah jarum jam (sesuai RFC 7946). Pita satu kelas = kontur kelas itu
+//! dikurangi kontur kelas di atasnya, lalu dihaluskan dengan Chaikin.
+
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+
+use super::{CellState, VisibilityZone};
+
+/// Nilai sel tanpa data (tidak ada maghrib / di luar grid), di bawah semua level kelas
+const NO_DATA: f64 = f64::NEG_INFINITY;
+
+/// Jumlah iterasi penghalusan Chaikin
+const SMOOTHING_ITERATIONS: usize = 2;
+
+type Ring = Vec<(f64, f64)>; // (lon, lat)
+
+/// Kelas visibilitas kriteria beserta level minimumnya, urut dari kelas tertinggi
+pub fn visibility_classes(criteria: &str) -> Vec<(i32, &'static str)> {
+    match crate::criteria::find_criterion(criteria) {
+        Some(criterion) => criterion.classes().to_vec(),
+        None => crate::criteria::criterion::BINARY_CLASSES.to_vec(),
+    }
+}
+
+/// Ubah grid zona visibilitas menjadi GeoJSON FeatureCollection (satu feature per kelas)
+pub fn visibility_geojson(zones: &[VisibilityZone], criteria: &str, observation_jd: f64) -> Value {
+    let obs_date = crate::calendar::jd_to_gregorian(observation_jd);
+    let conjunction = crate::astronomy::conjunction::find_conjunction(&obs_date);
+    let date = format!(
+        "{:04}-{:02}-{:02}",
+        obs_date.year,
+        obs_date.month,
+        obs_date.day.floor() as u8
+    );
+
+    let grid = LevelGrid::from_zones(zones);
+    let classes = visibility_classes(criteria);
+
+    let features: Vec<Value> = classes
+        .iter()
+        .enumerate()
+        .map(|(index, (level, class))| {
+            let polygons = match &grid {
+                Some(grid) => {
+                    let upper = index.checked_sub(1).map(|i| classes[i].0);
+                    grid.band_polygons(*level, upper)
+                }
+                None => Vec::new(),
+            };
+
+            json!({
+                "type": "Feature",
+                "properties": {
+                    "class": class,
+                    "level": level,
+                    "criterion": criteria,
+                    "date": date,
+                    "conjunction_jd": conjunction.jd_utc,
+                    "conjunction": crate::astronomy::ephemeris_utils::format_jd_to_datetime(
+                        conjunction.jd_utc,
+                        0.0,
+                    ),
+                },
+                "geometry": {
+                    "type": "MultiPolygon",
+                    "coordinates": polygons
+                        .iter()
+                        .map(|rings| rings.iter().map(ring_coordinates).collect())
+                        .collect::<Vec<Vec<Vec<[f64; 2]>>>>(),
+                },
+            })
+        })
+        .collect();
+
+    json!({
+        "type": "FeatureCollection",
+        "features": features,
+    })
+}
+
+/// Grid level (lat x lon) dengan satu baris/kolom NO_DATA di setiap sisi
+struct LevelGrid {
+    values: Vec<Vec<f64>>, // [baris lintang][kolom bujur]
+    lat0: f64,
+    lon0: f64,
+    lat_step: f64,
+    lon_step: f64,
+}
+
+impl LevelGrid {
+    fn from_zones(zones: &[VisibilityZone]) -> Option<Self> {
+        let first = zones.first()?;
+        let lat_step = first.step;
+        let lon_step = first.lon_step;
+        let lat0 = zones
+            .iter()
+            .map(|z| z.latitude)
+            .fold(f64::INFINITY, f64::min);
+        let lon0 = zones
+            .iter()
+            .map(|z| z.longitude_start)
+            .fold(f64::INFINITY, f64::min);
+
+        let index = |value: f64, origin: f64, step: f64| ((value - origin) / step).round() as usize;
+        let rows = zones
+            .iter()
+            .map(|z| index(z.latitude, lat0, lat_step))
+            .max()?
+            + 1;
+        let cols = zones
+            .iter()
+            .map(|z| index(z.longitude_start, lon0, lon_step))
+            .max()?
+            + 1;
+
+        let mut values = vec![vec![NO_DATA; cols + 2]; rows + 2];
+        for zone in zones {
+            let i = index(zone.latitude, lat0, lat_step) + 1;
+            let j = index(zone.longitude_start, lon0, lon_step) + 1;
+            if zone.cell_state != CellState::NoSunset {
+                values[i][j] = zone.visibility_level as f64;
+            }
+        }
+
+        Some(Self {
+            values,
+            lat0,
+            lon0,
+            lat_step,
+            lon_step,
+        })
+    }
+
+    /// Posisi (lon, lat) pusat sel untuk indeks grid ber-padding
+    fn position(&self, i: usize, j: usize) -> (f64, f64) {
+        (
+            self.lon0 + (j as f64 - 0.5) * self.lon_step,
+            self.lat0 + (i as f64 - 0.5) * self.lat_step,
+        )
+    }
+
+    /// Polygon (ring luar + lubang) untuk level >= `level` dan < `upper`
+    fn band_polygons(&self, level: i32, upper: Option<i32>) -> Vec<Vec<Ring>> {
+        let mut rings = self.contour_rings(level as f64 - 0.5);
+        if let Some(upper) = upper {
+            // Daerah kelas di atasnya menjadi lubang (orientasi dibalik)
+            rings.extend(
+                self.contour_rings(upper as f64 - 0.5)
+                    .into_iter()
+                    .map(|mut ring| {
+                        ring.reverse();
+                        ring
+                    }),
+            );
+        }
+
+        assemble_polygons(rings)
+            .into_iter()
+            .map(|polygon| polygon.into_iter().map(|ring| smooth_ring(&ring)).collect())
+            .collect()
+    }
+
+    /// Telusuri semua ring kontur pada ambang `threshold` (daerah dalam di kiri)
+    fn contour_rings(&self, threshold: f64) -> Vec<Ring> {
+        let rows = self.values.len();
+        let cols = self.values[0].len();
+        let inside = |i: usize, j: usize| self.values[i][j] >= threshold;
+
+        // Segmen: edge awal -> edge akhir
+        let mut segments: HashMap<EdgeKey, EdgeKey> = HashMap::new();
+        for i in 0..rows - 1 {
+            for j in 0..cols - 1 {
+                // Keliling sel berlawanan arah jarum jam: bawah, kanan, atas, kiri
+                let corners = [(i, j), (i, j + 1), (i + 1, j + 1), (i + 1, j)];
+                let edges = [
+                    EdgeKey::Horizontal(i, j),
+                    EdgeKey::Vertical(i, j + 1),
+                    EdgeKey::Horizontal(i + 1, j),
+                    EdgeKey::Vertical(i, j),
+                ];
+
+                // (edge, keluar dari daerah dalam?)
+                let crossings: Vec<(EdgeKey, bool)> = (0..4)
+                    .filter_map(|k| {
+                        let (a, b) = (corners[k], corners[(k + 1) % 4]);
+                        let (in_a, in_b) = (inside(a.0, a.1), inside(b.0, b.1));
+                        (in_a != in_b).then_some((edges[k], in_a))
+                    })
+                    .collect();
+
+                match crossings.len() {
+                    2 => {
+                        let (out, into) = if crossings[0].1 {
+                            (crossings[0].0, crossings[1].0)
+                        } else {
+                            (crossings[1].0, crossings[0].0)
+                        };
+                        segments.insert(out, into);
+                    }
+                    4 => {
+                        // Saddle: pusat sel menentukan apakah diagonal dalam terhubung
+                        let center =
+                            corners.iter().map(|&(a, b)| self.values[a][b]).sum::<f64>() / 4.0;
+                        for k in 0..4 {
+                            if crossings[k].1 {
+                                let partner = if center >= threshold {
+                                    crossings[(k + 1) % 4].0
+                                } else {
+                                    crossings[(k + 3) % 4].0
+                                };
+                                segments.insert(crossings[k].0, partner);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut rings = Vec::new();
+        while let Some(&start) = segments.keys().next() {
+            let mut ring = Vec::new();
+            let mut edge = start;
+            while let Some(next) = segments.remove(&edge) {
+                ring.push(self.crossing_point(edge, threshold));
+                edge = next;
+            }
+            if ring.len() >= 3 {
+                rings.push(ring);
+            }
+        }
+        rings
+    }
+
+    /// Titik potong kontur pada sebuah edge (interpolasi linear level)
+    fn crossing_point(&self, edge: EdgeKey, threshold: f64) -> (f64, f64) {
+        let (a, b) = match edge {
+            EdgeKey::Horizontal(i, j) => ((i, j), (i, j + 1)),
+            EdgeKey::Vertical(i, j) => ((i, j), (i + 1, j)),
+        };
+        let va = self.values[a.0][a.1];
+        let vb = self.values[b.0][b.1];
+
+        // Batas daerah tanpa data diletakkan tepat di tepi sel
+        let t = if va <= NO_DATA || vb <= NO_DATA {
+            0.5
+        } else {
+            ((threshold - va) / (vb - va)).clamp(0.0, 1.0)
+        };
+
+        let pa = self.position(a.0, a.1);
+        let pb = self.position(b.0, b.1);
+        (pa.0 + (pb.0 - pa.0) * t, pa.1 + (pb.1 - pa.1) * t)
+    }
+}
+
+/// Edge antar sampel grid: Horizontal(i, j) = (i,j)-(i,j+1), Vertical(i, j) = (i,j)-(i+1,j)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum EdgeKey {
+    Horizontal(usize, usize),
+    Vertical(usize, usize),
+}
+
+/// Kelompokkan ring: luas positif = ring luar, negatif = lubang di ring luar terkecil
+fn assemble_polygons(rings: Vec<Ring>) -> Vec<Vec<Ring>> {
+    let (outers, holes): (Vec<Ring>, Vec<Ring>) =
+        rings.into_iter().partition(|ring| signed_area(ring) > 0.0);
+
+    let mut polygons: Vec<Vec<Ring>> = outers.into_iter().map(|ring| vec![ring]).collect();
+    for hole in holes {
+        let owner = polygons
+            .iter()
+            .enumerate()
+            .filter(|(_, polygon)| point_in_ring(hole[0], &polygon[0]))
+            .min_by(|(_, a), (_, b)| signed_area(&a[0]).total_cmp(&signed_area(&b[0])))
+            .map(|(index, _)| index);
+        if let Some(index) = owner {
+            polygons[index].push(hole);
+        }
+    }
+    polygons
+}
+
+fn signed_area(ring: &Ring) -> f64 {
+    let n = ring.len();
+    (0..n)
+        .map(|k| {
+            let (x1, y1) = ring[k];
+            let (x2, y2) = ring[(k + 1) % n];
+            x1 * y2 - x2 * y1
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+fn point_in_ring(point: (f64, f64), ring: &Ring) -> bool {
+    let (px, py) = point;
+    let n = ring.len();
+    let mut inside = false;
+    for k in 0..n {
+        let (x1, y1) = ring[k];
+        let (x2, y2) = ring[(k + n - 1) % n];
+        if (y1 > py) != (y2 > py) && px < (x2 - x1) * (py - y1) / (y2 - y1) + x1 {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Penghalusan Chaikin untuk ring tertutup
+fn smooth_ring(ring: &Ring) -> Ring {
+    let mut current = ring.clone();
+    for _ in 0..SMOOTHING_ITERATIONS {
+        let n = current.len();
+        current = (0..n)
+            .flat_map(|k| {
+                let (x1, y1) = current[k];
+                let (x2, y2) = current[(k + 1) % n];
+                [
+                    (0.75 * x1 + 0.25 * x2, 0.75 * y1 + 0.25 * y2),
+                    (0.25 * x1 + 0.75 * x2, 0.25 * y1 + 0.75 * y2),
+                ]
+            })
+            .collect();
+    }
+    current
+}
+
+/// Koordinat GeoJSON [lon, lat] (4 desimal), ring ditutup dengan titik awal
+fn ring_coordinates(ring: &Ring) -> Vec<[f64; 2]> {
+    let round = |value: f64| (value * 1e4).round() / 1e4;
+    ring.iter()
+        .chain(ring.first())
+        .map(|&(lon, lat)| [round(lon), round(lat)])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn synthetic_zones(level_at: impl Fn(usize, usize) -> i32) -> Vec<VisibilityZone> {
+        let mut zones = Vec::new();
+        for i in 0..10 {
+            for j in 0..10 {
+                zones.push(VisibilityZone {
+                    latitude: i as f64,
+                    latitude_end: i as f64 + 1.0,
+                    longitude_start: j as f64,
+                    longitude_end: j as f64 + 1.0,
+                    step: 1.0,
+                    lon_step: 1.0,
+                    is_visible: level_at(i, j) >= 3,
+                    q_value: Some(0.0),
+                    visibility_level: level_at(i, j),
+                    criteria: "Odeh".to_string(),
+                    cell_state: CellState::Evaluated,
+                });
+            }
+        }
+        zones
+    }
+
+    #[test]
+    fn test_band_with_hole() {
+        // Blok level 4 (A) di tengah, sisanya level 0 (D Odeh)
+        let zones = synthetic_zones(|i, j| {
+            if (3..7).contains(&i) && (3..7).contains(&j) {
+                4
+            } else {
+                0
+            }
+        });
+        let grid = LevelGrid::from_zones(&zones).unwrap();
+
+        let class_a = grid.band_polygons(4, None);
+        assert_eq!(class_a.len(), 1);
+        assert_eq!(class_a[0].len(), 1);
+        assert!(point_in_ring((5.0, 5.0), &class_a[0][0]));
+
+        // D mengelilingi A: satu polygon dengan satu lubang
+        let class_d = grid.band_polygons(0, Some(2));
+        assert_eq!(class_d.len(), 1);
+        assert_eq!(class_d[0].len(), 2);
+        assert!(signed_area(&class_d[0][0]) > 0.0);
+        assert!(signed_area(&class_d[0][1]) < 0.0);
+        assert!(point_in_ring((1.0, 1.0), &class_d[0][0]));
+        assert!(point_in_ring((5.0, 5.0), &class_d[0][1]));
+    }
+
+    #[test]
+    fn test_geojson_feature_collection() {
+        let zones = synthetic_zones(|_, j| if j < 5 { 3 } else { 0 });
+        let geojson = visibility_geojson(&zones, "MABIMS", 2461089.5);
+
+        assert_eq!(geojson["type"], "FeatureCollection");
+        let features = geojson["features"].as_array().unwrap();
+        assert_eq!(features.len(), 2);
+        assert_eq!(features[0]["properties"]["class"], "visible");
+        assert_eq!(features[0]["properties"]["criterion"], "MABIMS");
+        assert_eq!(features[0]["properties"]["date"], "2026-02-18");
+
+        // Ring GeoJSON selalu tertutup
+        let ring = features[0]["geometry"]["coordinates"][0][0]
+            .as_array()
+            .unwrap();
+        assert_eq!(ring.first(), ring.last());
+    }
+
+    #[test]
+    fn test_odeh_grid_uses_odeh_zones() {
+        // 18 Feb 2026: zona A-D Odeh semuanya muncul di peta dunia
+        let jd = 2461089.5;
+        let zones = super::super::calculate_visibility_zones_internal(jd, "Odeh", 5.0).unwrap();
+        let geojson = visibility_geojson(&zones, "Odeh", jd);
+
+        let features = geojson["features"].as_array().unwrap();
+        let classes: Vec<&str> = features
+            .iter()
+            .map(|f| f["properties"]["class"].as_str().unwrap())
+            .collect();
+        assert_eq!(classes, ["A", "B", "C", "D"]);
+        for feature in features {
+            let polygons = feature["geometry"]["coordinates"].as_array().unwrap();
+            assert!(!polygons.is_empty(), "{}", feature["properties"]["class"]);
+        }
+        assert_eq!(visibility_classes("YALLOP").len(), 6);
+    }
+}
@@ -0,0 +1,246 @@
+//! Seri peta visibilitas beberapa malam berturut-turut (hari ke-0, 1, 2 setelah ijtimak)
+//!
+//! Hari ke-0 setiap sel adalah maghrib lokal pertama setelah ijtimak awal bulan Hijriah,
+//! sehingga ijtimak malam hari (misal setelah maghrib di Asia Tenggara) tidak menghasilkan
+//! malam evaluasi sebelum ijtimak. Setiap malam berikutnya memakai ijtimak yang sama sehingga
+//! umur bulan terus bertambah. Untuk setiap sel grid dicatat malam pertama hilal memenuhi
+//! kriteria.
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{CancelToken, CellState, MapGrid, MapProgress, VisibilityZone};
+use crate::astronomy::rise_set::RiseSetOptions;
+
+/// Jumlah malam default dan maksimum dalam satu seri
+pub const DEFAULT_SERIES_DAYS: u32 = 3;
+pub const MAX_SERIES_DAYS: u32 = 5;
+
+/// Peta visibilitas untuk satu malam
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EveningMap {
+    pub day: u32,     // 0 = maghrib lokal pertama setelah ijtimak
+    pub date: String, // YYYY-MM-DD (UT): tanggal ijtimak + day, tiap sel memakai maghrib lokalnya
+    pub observation_jd: f64,
+    pub zones: Vec<VisibilityZone>,
+}
+
+/// Malam pertama hilal terlihat di satu sel grid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FirstVisibilityCell {
+    pub latitude: f64,
+    pub latitude_end: f64,
+    pub longitude_start: f64,
+    pub longitude_end: f64,
+    pub first_day: Option<u32>, // None: belum terlihat dalam seri ini
+}
+
+/// Seri peta visibilitas untuk awal satu bulan Hijriah
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VisibilitySeries {
+    pub hijri_year: i32,
+    pub hijri_month: u8,
+    pub criteria: String,
+    pub conjunction_jd: f64,
+    pub conjunction: String,
+    pub evenings: Vec<EveningMap>,
+    pub first_visibility: Vec<FirstVisibilityCell>,
+}
+
+/// Hitung peta visibilitas untuk `days` malam berturut-turut mulai maghrib pertama setelah ijtimak
+///
+/// Ijtimak dicari dengan `find_conjunction` di sekitar tanggal 1 bulan Hijriah (tabular).
+/// Progres dilaporkan kumulatif untuk seluruh malam. `rise_set` menentukan maghrib setiap sel.
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_visibility_series<F>(
+    hijri_year: i32,
+    hijri_month: u8,
+    criteria: &str,
+    grid: &MapGrid,
+    rise_set: &RiseSetOptions,
+    days: u32,
+    cancel: &CancelToken,
+    on_progress: F,
+) -> Result<VisibilitySeries, String>
+where
+    F: Fn(MapProgress) + Sync,
+{
+    if !(1..=12).contains(&hijri_month) {
+        return Err("Hijri month must be between 1 and 12".to_string());
+    }
+    let days = days.clamp(1, MAX_SERIES_DAYS);
+
+    let first_day = crate::calendar::HijriDate::new(hijri_year, hijri_month, 1);
+    let conjunction = crate::astronomy::conjunction::find_conjunction(
+        &crate::calendar::hijri_to_gregorian(&first_day),
+    );
+    // Tengah malam UT pada tanggal ijtimak (hanya untuk label tanggal)
+    let day0_jd = (conjunction.jd_utc - 0.5).floor() + 0.5;
+
+    // Malam ke-0 per sel: JD tengah malam tanggal lokal maghrib pertama setelah ijtimak
+    grid.validate()?;
+    let first_evenings: Vec<f64> = grid
+        .cells()
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|(lat, lon)| {
+            let location = super::grid_location(lat, lon);
+            let date = super::first_evening_after(&location, conjunction.jd_utc, rise_set);
+            crate::calendar::gregorian_to_jd(&date)
+        })
+        .collect();
+
+    let cells = grid.cell_count();
+    let total = cells * days as usize;
+
+    let mut evenings = Vec::with_capacity(days as usize);
+    for day in 0..days {
+        let observation_jd = day0_jd + day as f64;
+        let offset = cells * day as usize;
+        let zones = super::evaluate_grid(
+            conjunction.jd_utc,
+            criteria,
+            grid,
+            rise_set,
+            cancel,
+            |progress| {
+                on_progress(MapProgress {
+                    done: offset + progress.done,
+                    total,
+                })
+            },
+            |index, _| crate::calendar::jd_to_gregorian(first_evenings[index] + day as f64),
+        )?;
+
+        let date = crate::calendar::jd_to_gregorian(observation_jd);
+        evenings.push(EveningMap {
+            day,
+            date: format!(
+                "{:04}-{:02}-{:02}",
+                date.year,
+                date.month,
+                date.day.floor() as u8
+            ),
+            observation_jd,
+            zones,
+        });
+    }
+
+    let first_visibility = first_visibility(&evenings);
+
+    Ok(VisibilitySeries {
+        hijri_year,
+        hijri_month,
+        criteria: criteria.to_string(),
+        conjunction_jd: conjunction.jd_utc,
+        conjunction: crate::astronomy::ephemeris_utils::format_jd_to_datetime(
+            conjunction.jd_utc,
+            0.0,
+        ),
+        evenings,
+        first_visibility,
+    })
+}
+
+/// Malam pertama setiap sel terlihat (urutan sel sama di setiap malam)
+fn first_visibility(evenings: &[EveningMap]) -> Vec<FirstVisibilityCell> {
+    let Some(first) = evenings.first() else {
+        return Vec::new();
+    };
+
+    first
+        .zones
+        .iter()
+        .enumerate()
+        .map(|(index, zone)| FirstVisibilityCell {
+            latitude: zone.latitude,
+            latitude_end: zone.latitude_end,
+            longitude_start: zone.longitude_start,
+            longitude_end: zone.longitude_end,
+            first_day: evenings
+                .iter()
+                .find(|evening| {
+                    evening
+                        .zones
+                        .get(index)
+                        .is_some_and(|z| z.is_visible && z.cell_state != CellState::NoSunset)
+                })
+                .map(|evening| evening.day),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::MapBounds;
+
+    #[test]
+    fn test_series_ramadan_1447() {
+        // Ijtimak 17 Feb 2026 12:01 UT (19:01 WIB), setelah maghrib di Asia Tenggara:
+        // malam ke-0 sel Indonesia adalah 18 Feb, bukan 17 Feb sebelum ijtimak
+        let grid = MapGrid {
+            bounds: MapBounds::MABIMS,
+            lat_step: 5.0,
+            lon_step: 5.0,
+        };
+        let series = calculate_visibility_series(
+            1447,
+            9,
+            "MABIMS",
+            &grid,
+            &RiseSetOptions::default(),
+            3,
+            &CancelToken::new(),
+            |_| {},
+        )
+        .unwrap();
+
+        assert_eq!(series.evenings.len(), 3);
+        assert_eq!(series.evenings[0].date, "2026-02-17");
+        assert_eq!(series.evenings[2].date, "2026-02-19");
+        assert_eq!(series.first_visibility.len(), grid.cell_count());
+
+        // Sel Jawa (-10..-5, 105..110): malam ke-0 sama dengan evaluasi maghrib 18 Feb
+        let jakarta = super::super::grid_location(-10.0, 105.0);
+        let first = super::super::first_evening_after(
+            &jakarta,
+            series.conjunction_jd,
+            &RiseSetOptions::default(),
+        );
+        assert_eq!((first.year, first.month, first.day), (2026, 2, 18.0));
+        let index = grid
+            .cells()
+            .position(|(lat, lon)| lat == -10.0 && lon == 105.0)
+            .unwrap();
+        let params = crate::criteria::HilalParameters::new(
+            &jakarta,
+            &crate::GregorianDate {
+                year: 2026,
+                month: 2,
+                day: 18.0,
+            },
+            series.conjunction_jd,
+        );
+        assert!(params.sunset_jd > series.conjunction_jd);
+        let expected = crate::criteria::find_criterion("MABIMS")
+            .unwrap()
+            .evaluate(&params);
+        assert_eq!(
+            series.evenings[0].zones[index].is_visible,
+            expected.is_visible
+        );
+        assert!(expected.is_visible);
+        assert_eq!(series.first_visibility[index].first_day, Some(0));
+
+        // Setelah terlihat, sebuah sel tetap tercatat pada malam pertama
+        for (index, cell) in series.first_visibility.iter().enumerate() {
+            if let Some(day) = cell.first_day {
+                assert!(series.evenings[day as usize].zones[index].is_visible);
+                assert!(series.evenings[..day as usize]
+                    .iter()
+                    .all(|evening| !evening.zones[index].is_visible));
+            }
+        }
+    }
+}
@@ -348,7 +348,7 @@ mod vb6_validation {
                 name: None,
                 latitude: case.latitude,
                 longitude: case.longitude,
-                elevation: 10.0, // Assuming standard elevation
+                elevation: 0.0, // VB6: maghrib pada -0°50' tanpa dip ufuk
                 timezone: case.timezone,
             };
 
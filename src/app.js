@@ -304,10 +304,13 @@ export class HilalApp {
         this.hijriDateInput.getGregorianDateForCalculation() :
         this.currentDate;
 
+      // Job dengan id yang sama membatalkan perhitungan peta sebelumnya di backend
       const params = {
         date: calculationDate,
         criteria: this.selectedCriteria,
-        step_degrees: 2.0
+        step_degrees: 2.0,
+        jobId: 'main-map',
+        onProgress: ({ done, total }) => this.updateLoadingProgress(done, total)
       };
       console.log('Sending Gregorian params for visibility zones:', JSON.stringify(params, null, 2));
 
@@ -322,6 +325,10 @@ export class HilalApp {
       this.dataStore.setVisibilityData(visibilityData);
 
     } catch (error) {
+      if (error.message?.includes('cancelled')) {
+        console.log('Visibility map calculation superseded');
+        return;
+      }
       console.error('Error updating visibility zones:', error);
       this.showError('Failed to update visibility zones');
     }
@@ -582,6 +589,8 @@ export class HilalApp {
           <h3 class="font-bold text-lg text-base-content">Processing</h3>
           <p class="text-sm text-base-content/70 mt-1">${message}</p>
         </div>
+        <progress id="global-loader-progress" class="progress progress-primary w-full hidden" value="0" max="1"></progress>
+        <p id="global-loader-progress-text" class="text-xs text-base-content/60 hidden"></p>
       </div>
     `;
 
@@ -597,9 +606,29 @@ export class HilalApp {
     inner.classList.add('scale-100');
   }
 
+  /**
+   * Show visibility map progress (grid cells done / total) in the global loader
+   */
+  updateLoadingProgress(done, total) {
+    const bar = document.getElementById('global-loader-progress');
+    const text = document.getElementById('global-loader-progress-text');
+    if (!bar || !total) return;
+
+    bar.max = total;
+    bar.value = done;
+    bar.classList.remove('hidden');
+    if (text) {
+      text.textContent = `Visibility map: ${Math.floor((done / total) * 100)}% (${done}/${total} cells)`;
+      text.classList.remove('hidden');
+    }
+  }
+
   hideLoading() {
     const loader = document.getElementById('global-loader');
     if (loader && loader.parentNode) {
+      // Fading loader must not receive progress meant for the next one
+      loader.removeAttribute('id');
+      loader.querySelectorAll('[id]').forEach(el => el.removeAttribute('id'));
       // Fade out
       loader.style.opacity = '0';
       setTimeout(() => {
@@ -234,11 +234,14 @@ export class CriteriaResultsDisplay extends HTMLElement {
 
   getCriteriaDescription(key) {
     const descriptions = {
+      'MABIMS': this.t('criteriaDescriptions.mabimsLama', 'MABIMS criteria (Traditional)'),
+      'NEO_MABIMS': this.t('criteriaDescriptions.mabimsBaru', 'MABIMS criteria (Updated)'),
       'MABIMS_Lama': this.t('criteriaDescriptions.mabimsLama', 'MABIMS criteria (Traditional)'),
       'MABIMS_Baru': this.t('criteriaDescriptions.mabimsBaru', 'MABIMS criteria (Updated)'),
       'WujudulHilal': this.t('criteriaDescriptions.wujudulHilal', 'Wujudul Hilal (Above horizon at sunset)'),
       'Turkey': this.t('criteriaDescriptions.turkey', 'Turkey/Diyanet criteria'),
       'Odeh': this.t('criteriaDescriptions.odeh', 'Odeh Astronomical criteria'),
+      'Yallop': this.t('criteriaDescriptions.yallop', 'Yallop q-value criteria (zones A–F)'),
       'IjtimaQoblaGhurub': this.t('criteriaDescriptions.ijtimaQoblaGhurub', 'Conjunction before sunset'),
       'LFNU': this.t('criteriaDescriptions.lfnu', 'LFNU criteria'),
       'KHGT': this.t('criteriaDescriptions.khgt', 'Global Single Hijri Calendar (KHGT) criteria'),
@@ -238,6 +238,8 @@ export class MapVisualization extends HTMLElement {
       0: { color: 'rgba(255, 0, 0, 0.3)', label: this.t('visibilityLevels.level0') }     // Red
     };
 
+    const noSunsetStyle = { color: 'rgba(128, 128, 128, 0.3)', label: this.t('visibilityLevels.noSunset', 'No sunset') };
+
     // Use Canvas renderer to eliminate seams/gaps between segments
     // Padding ensures tiles render slightly outside viewport for smoothness during pan
     const canvasRenderer = L.canvas({ padding: 0.5 });
@@ -271,10 +273,13 @@ export class MapVisualization extends HTMLElement {
         const step = zone.step || 1.0;
         const bounds = [
           [zone.latitude, zone.longitude_start],
-          [zone.latitude + step, zone.longitude_end]
+          [zone.latitude_end ?? zone.latitude + step, zone.longitude_end]
         ];
 
-        const style = colors[zone.visibility_level] || colors[0];
+        // Polar cells without sunset are drawn grey and are not graded
+        const style = zone.cell_state === 'no_sunset'
+          ? noSunsetStyle
+          : (colors[zone.visibility_level] || colors[0]);
 
         const rect = L.rectangle(bounds, {
           stroke: false,
@@ -290,7 +295,7 @@ export class MapVisualization extends HTMLElement {
         rect.bindTooltip(`
           <div class="text-[10px]">
             <span class="font-bold">${style.label}</span><br>
-            q: <span class="font-mono text-primary">${zone.q_value.toFixed(2)}</span><br>
+            q: <span class="font-mono text-primary">${zone.q_value == null ? '-' : zone.q_value.toFixed(2)}</span><br>
             Lat: <span class="font-mono">${zone.latitude.toFixed(1)}</span>
           </div>
         `, { sticky: true, opacity: 0.9, direction: 'top' });
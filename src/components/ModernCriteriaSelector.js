@@ -29,8 +29,10 @@ export class ModernCriteriaSelector extends HTMLElement {
 
   render() {
     const criteria = [
-      { id: 'MABIMS', name: 'MABIMS', icon: '🌙', desc: this.t('criteria.mabims_desc', 'Alt ≥ 3°, Elong ≥ 6.4°') },
+      { id: 'MABIMS', name: 'MABIMS', icon: '🌙', desc: this.t('criteria.mabims_desc', 'Alt ≥ 2°, Elong ≥ 3°, Age ≥ 8h') },
+      { id: 'NEO_MABIMS', name: 'Neo-MABIMS', icon: '🌙', desc: this.t('criteria.neo_mabims_desc', 'Alt ≥ 3°, Elong ≥ 6.4°') },
       { id: 'Odeh', name: 'Odeh', icon: '🔭', desc: this.t('criteria.odeh_desc', 'Astronomical criteria') },
+      { id: 'Yallop', name: 'Yallop', icon: '🔭', desc: this.t('criteria.yallop_desc', 'q-value, zones A–F') },
       { id: 'LFNU', name: 'LFNU', icon: '🕌', desc: this.t('criteria.lfnu_desc', 'Imkanur Rukyat NU') },
       { id: 'KHGT', name: 'KHGT', icon: '🌍', desc: this.t('criteria.khgt_desc', 'Alt ≥ 5°, Elong ≥ 8°') },
       { id: 'Turkey', name: 'Turkey', icon: '🌟', desc: this.t('criteria.turkey_desc', 'Alt ≥ 5°, Elong ≥ 8°') },
@@ -53,6 +53,20 @@ async function getInvoke() {
   throw new Error(errorMsg);
 }
 
+// Helper to obtain Tauri event listener (for progress events)
+async function getListen() {
+  if (typeof window !== 'undefined' && window.__TAURI__?.event?.listen) {
+    return window.__TAURI__.event.listen;
+  }
+  try {
+    const eventApi = await import('@tauri-apps/api/event');
+    return eventApi.listen;
+  } catch (err) {
+    console.warn('Tauri event import failed:', err.message);
+    return null;
+  }
+}
+
 // Check if we're in Tauri context
 export function isTauri() {
   return isTauriContext || (typeof window !== 'undefined' && !!window.__TAURI__);
@@ -70,6 +84,9 @@ export class HilalAPI {
    * @param {number} params.year - Gregorian year
    * @param {number} params.month - Gregorian month (1-12)
    * @param {number} params.day - Gregorian day (1-31)
+   * @param {Object} [params.rise_set] - Sunset conditions {pressure (mbar), temperature (°C),
+   *   horizon: {type: 'ocean'} | {type: 'terrain', height}}; default 1010 mbar, 27°C, ocean dip
+   *   from the location elevation
    * @returns {Promise<Object>} All criteria results with visibility
    */
   async calculateHilalAllCriteria(params) {
@@ -80,7 +97,8 @@ export class HilalAPI {
         location: params.location,
         year: params.year,
         month: params.month,
-        day: params.day
+        day: params.day,
+        riseSet: params.rise_set ?? null
       });
 
       console.log('API Response - calculateHilalAllCriteria:', result);
@@ -91,22 +109,76 @@ export class HilalAPI {
     }
   }
 
+  /**
+   * List all registered visibility criteria
+   * @returns {Promise<Array>} Array of {id, name, description, inputs}
+   */
+  async listCriteria() {
+    try {
+      const invoke = await getInvoke();
+      return await invoke('list_criteria_command');
+    } catch (error) {
+      console.error('API Error - listCriteria:', error);
+      throw new Error(`Failed to list criteria: ${error.message}`);
+    }
+  }
+
+  /**
+   * Load custom visibility criteria from a rule file
+   * @param {string|null} path - .json or .toml file inside the app config `criteria` folder;
+   *   null opens a file picker
+   * @returns {Promise<Array>} Loaded criteria info
+   */
+  async loadCustomCriteria(path = null) {
+    try {
+      const invoke = await getInvoke();
+      return await invoke('load_custom_criteria_command', { path });
+    } catch (error) {
+      console.error('API Error - loadCustomCriteria:', error);
+      throw new Error(`Failed to load custom criteria: ${error.message}`);
+    }
+  }
+
+  /**
+   * Remove a loaded custom criteria
+   * @param {string} id - Criteria id
+   */
+  async removeCustomCriteria(id) {
+    try {
+      const invoke = await getInvoke();
+      return await invoke('remove_custom_criteria_command', { id });
+    } catch (error) {
+      console.error('API Error - removeCustomCriteria:', error);
+      throw new Error(`Failed to remove custom criteria: ${error.message}`);
+    }
+  }
+
   /**
    * Get visibility zones for given criteria and date
    * @param {Object} params - Zone calculation parameters
    * @param {Date} params.date - Date for calculation
    * @param {string} params.criteria - Visibility criteria
-   * @param {number} params.step_degrees - Grid resolution in degrees
+   * @param {number} params.step_degrees - Grid resolution in degrees (0.05 - 5), world map within ±60° latitude
+   * @param {Object} [params.grid] - Regional grid {bounds: {lat_min, lat_max, lon_min, lon_max}, lat_step, lon_step}, bounds up to ±90° include the polar bands
+   * @param {Object} [params.rise_set] - Sunset conditions for every cell, see calculateHilalAllCriteria;
+   *   cells are at sea level, so use {type: 'terrain', height} for an observer height
+   * @param {string} [params.jobId] - Job id, used for progress events and cancellation
+   * @param {Function} [params.onProgress] - Called with {done, total}
    * @returns {Promise<Array>} Array of visibility zones
    */
   async getVisibilityZones(params) {
+    let unlisten = null;
     try {
       console.log('API Call - getVisibilityZones:', params);
       const invoke = await getInvoke();
+      unlisten = await this.listenMapProgress(params.jobId, params.onProgress);
       const result = await invoke('calculate_visibility_zones', {
         date: params.date.toISOString(),
         criteria: params.criteria,
-        stepDegrees: params.step_degrees || 2.0
+        stepDegrees: params.step_degrees || 2.0,
+        grid: params.grid || null,
+        riseSet: params.rise_set ?? null,
+        jobId: params.jobId || null
       });
 
       console.log('API Response - getVisibilityZones:', result);
@@ -114,13 +186,116 @@ export class HilalAPI {
     } catch (error) {
       console.error('API Error - getVisibilityZones:', error);
       throw new Error(`Failed to get visibility zones: ${error.message}`);
+    } finally {
+      if (unlisten) unlisten();
+    }
+  }
+
+  /**
+   * Get the visibility map as GeoJSON contour polygons (one feature per class)
+   * @param {Object} params - Same parameters as getVisibilityZones
+   * @returns {Promise<Object>} GeoJSON FeatureCollection
+   */
+  async getVisibilityGeoJson(params) {
+    let unlisten = null;
+    try {
+      console.log('API Call - getVisibilityGeoJson:', params);
+      const invoke = await getInvoke();
+      unlisten = await this.listenMapProgress(params.jobId, params.onProgress);
+      const result = await invoke('calculate_visibility_geojson', {
+        date: params.date.toISOString(),
+        criteria: params.criteria,
+        stepDegrees: params.step_degrees || 2.0,
+        grid: params.grid || null,
+        riseSet: params.rise_set ?? null,
+        jobId: params.jobId || null
+      });
+
+      console.log('API Response - getVisibilityGeoJson:', result);
+      return result;
+    } catch (error) {
+      console.error('API Error - getVisibilityGeoJson:', error);
+      throw new Error(`Failed to get visibility GeoJSON: ${error.message}`);
+    } finally {
+      if (unlisten) unlisten();
+    }
+  }
+
+  /**
+   * Get visibility maps for consecutive evenings after the conjunction of a Hijri month
+   * @param {Object} params - Series parameters
+   * @param {number} params.hijri_year - Hijri year
+   * @param {number} params.hijri_month - Hijri month (1-12)
+   * @param {string} params.criteria - Visibility criteria
+   * @param {number} [params.step_degrees] - Grid resolution in degrees, whole world
+   * @param {Object} [params.grid] - Regional grid, see getVisibilityZones
+   * @param {Object} [params.rise_set] - Sunset conditions, see getVisibilityZones
+   * @param {number} [params.days] - Number of evenings (default 3, max 5)
+   * @param {string} [params.jobId] - Job id, used for progress events and cancellation
+   * @param {Function} [params.onProgress] - Called with {done, total}
+   * @returns {Promise<Object>} {conjunction, evenings: [{day, date, zones}], first_visibility}
+   */
+  async getVisibilitySeries(params) {
+    let unlisten = null;
+    try {
+      console.log('API Call - getVisibilitySeries:', params);
+      const invoke = await getInvoke();
+      unlisten = await this.listenMapProgress(params.jobId, params.onProgress);
+      const result = await invoke('calculate_visibility_series', {
+        hijriYear: params.hijri_year,
+        hijriMonth: params.hijri_month,
+        criteria: params.criteria,
+        stepDegrees: params.step_degrees || 2.0,
+        grid: params.grid || null,
+        riseSet: params.rise_set ?? null,
+        days: params.days || null,
+        jobId: params.jobId || null
+      });
+
+      console.log('API Response - getVisibilitySeries:', result);
+      return result;
+    } catch (error) {
+      console.error('API Error - getVisibilitySeries:', error);
+      throw new Error(`Failed to get visibility series: ${error.message}`);
+    } finally {
+      if (unlisten) unlisten();
+    }
+  }
+
+  /**
+   * Cancel a running visibility map calculation
+   * @param {string} jobId - Job id passed to getVisibilityZones
+   * @returns {Promise<boolean>} True if a running job was cancelled
+   */
+  async cancelVisibilityZones(jobId) {
+    try {
+      const invoke = await getInvoke();
+      return await invoke('cancel_visibility_zones', { jobId });
+    } catch (error) {
+      console.error('API Error - cancelVisibilityZones:', error);
+      return false;
     }
   }
 
+  /**
+   * Subscribe to visibility map progress events for one job
+   * @returns {Promise<Function|null>} Unlisten function
+   */
+  async listenMapProgress(jobId, onProgress) {
+    if (!onProgress) return null;
+    const listen = await getListen();
+    if (!listen) return null;
+    return listen('visibility-map-progress', (event) => {
+      const { job_id: eventJobId, done, total } = event.payload;
+      if (!jobId || eventJobId === jobId) {
+        onProgress({ done, total });
+      }
+    });
+  }
+
   /**
    * Get ephemeris data for astronomical calculations
-   * @param {Object} params - Ephemeris parameters
-   * @param {Object} params.location - Location object
+   * @param {Object} params - Ephemeris parameters (geocentric, independent of location)
    * @param {Date} params.start_date - Start date
    * @param {Date} params.end_date - End date
    * @returns {Promise<Array>} Ephemeris data
@@ -130,9 +305,9 @@ export class HilalAPI {
       console.log('API Call - getEphemeris:', params);
       const invoke = await getInvoke();
       const result = await invoke('get_ephemeris_data', {
-        location: params.location,
         start_date: params.start_date.toISOString(),
-        end_date: params.end_date.toISOString()
+        end_date: params.end_date.toISOString(),
+        step: params.step || null // { value, unit: 'minutes' | 'hours' | 'days' }
       });
 
       console.log('API Response - getEphemeris:', result);
@@ -170,6 +345,9 @@ export class HilalAPI {
    * @param {Object} params - Prayer times parameters
    * @param {Object} params.location - Location object
    * @param {Date} params.date - Date for calculation
+   * @param {string|Object} [params.method] - Method id (e.g. 'mwl', 'umm_al_qura') or a full config
+   * @param {Object} [params.format] - {clock: 'h24'|'h12', seconds, rounding: 'up'|'down'|'nearest', rounding_overrides}
+   *   (default: 24 h, minutes truncated; Kemenag rounds up except terbit)
    * @returns {Promise<Object>} Prayer times data
    */
   async getPrayerTimes(params) {
@@ -178,7 +356,9 @@ export class HilalAPI {
       const invoke = await getInvoke();
       const result = await invoke('get_prayer_times', {
         location: params.location,
-        date: params.date.toISOString()
+        date: params.date.toISOString(),
+        method: params.method ?? null,
+        format: params.format ?? null
       });
 
       console.log('API Response - getPrayerTimes:', result);
@@ -196,6 +376,7 @@ export class HilalAPI {
    * @param {number} params.year - Year
    * @param {number} params.month - Month
    * @param {number} params.day - Day
+   * @param {Object} [params.rise_set] - Sunset conditions, see calculateHilalAllCriteria
    * @returns {Promise<Object>} Detailed hilal data
    */
   async getDetailedHilalData(params) {
@@ -206,7 +387,8 @@ export class HilalAPI {
         location: params.location,
         year: params.year,
         month: params.month,
-        day: params.day
+        day: params.day,
+        riseSet: params.rise_set ?? null
       });
 
       console.log('API Response - getDetailedHilalData:', result);
@@ -273,16 +455,20 @@ export class HilalAPI {
    * @param {number} year - Gregorian year
    * @param {number} month - Gregorian month (1-12)
    * @param {number} day - Gregorian day (1-31)
+   * @param {Object} [calendar] - {kind: 'tabular'|'umm_al_qura'|'hisab'|'khgt'}; tabular takes
+   *   leap ('fifteen'|'sixteen'|'fatimid'|'habash_al_hasib') and epoch ('astronomical'|'civil'),
+   *   hisab takes criteria and locations; default tabular (sixteen, civil)
    * @returns {Promise<Object>} Hijri date {year, month, day}
    */
-  async gregorianToHijri(year, month, day) {
+  async gregorianToHijri(year, month, day, calendar = null) {
     try {
-      console.log('API Call - gregorianToHijri:', { year, month, day });
+      console.log('API Call - gregorianToHijri:', { year, month, day, calendar });
       const invoke = await getInvoke();
       const result = await invoke('gregorian_to_hijri_command', {
         year,
         month,
-        day
+        day,
+        calendar
       });
 
       console.log('API Response - gregorianToHijri:', result);
@@ -298,16 +484,20 @@ export class HilalAPI {
    * @param {number} year - Hijri year
    * @param {number} month - Hijri month (1-12)
    * @param {number} day - Hijri day (1-30)
+   * @param {Object} [calendar] - {kind: 'tabular'|'umm_al_qura'|'hisab'|'khgt'}; tabular takes
+   *   leap ('fifteen'|'sixteen'|'fatimid'|'habash_al_hasib') and epoch ('astronomical'|'civil'),
+   *   hisab takes criteria and locations; default tabular (sixteen, civil)
    * @returns {Promise<Object>} Gregorian date {year, month, day}
    */
-  async hijriToGregorian(year, month, day) {
+  async hijriToGregorian(year, month, day, calendar = null) {
     try {
-      console.log('API Call - hijriToGregorian:', { year, month, day });
+      console.log('API Call - hijriToGregorian:', { year, month, day, calendar });
       const invoke = await getInvoke();
       const result = await invoke('hijri_to_gregorian_command', {
         year,
         month,
-        day
+        day,
+        calendar
       });
 
       console.log('API Response - hijriToGregorian:', result);
@@ -318,6 +508,237 @@ export class HilalAPI {
     }
   }
 
+  /**
+   * Generate a Hijri calendar from a visibility criterion (hisab mode)
+   * @param {Object} hisab - {criteria, locations: [{name, latitude, longitude, elevation, timezone}]}
+   * @param {number} startYear - First Hijri year
+   * @param {number} years - Number of Hijri years
+   * @returns {Promise<Object>} {criteria, months: [{year, month, start, length, decided_by}]}
+   */
+  async generateHisabCalendar(hisab, startYear, years) {
+    try {
+      const invoke = await getInvoke();
+      return await invoke('generate_hisab_calendar_command', { hisab, startYear, years });
+    } catch (error) {
+      console.error('API Error - generateHisabCalendar:', error);
+      throw new Error(`Failed to generate hisab calendar: ${error.message}`);
+    }
+  }
+
+  /**
+   * Evaluate the KHGT global rule for the start of a Hijri month
+   * @param {number} hijriYear - Hijri year
+   * @param {number} hijriMonth - Hijri month (1-12)
+   * @param {number} [stepDegrees] - Global grid resolution (default 2°)
+   * @returns {Promise<Object>} {decision, deciding_location, month_start, conjunction, ...}
+   */
+  async evaluateKhgtGlobal(hijriYear, hijriMonth, stepDegrees = null) {
+    try {
+      const invoke = await getInvoke();
+      return await invoke('evaluate_khgt_global_command', { hijriYear, hijriMonth, stepDegrees });
+    } catch (error) {
+      console.error('API Error - evaluateKhgtGlobal:', error);
+      throw new Error(`Failed to evaluate KHGT: ${error.message}`);
+    }
+  }
+
+  /**
+   * Generate the KHGT calendar for one Hijri year
+   * @param {number} hijriYear - Hijri year
+   * @param {number} [stepDegrees] - Global grid resolution (default 2°)
+   * @returns {Promise<Array>} Months {hijri_year, hijri_month, length, result}
+   */
+  async generateKhgtCalendar(hijriYear, stepDegrees = null) {
+    try {
+      const invoke = await getInvoke();
+      return await invoke('generate_khgt_calendar_command', { hijriYear, stepDegrees });
+    } catch (error) {
+      console.error('API Error - generateKhgtCalendar:', error);
+      throw new Error(`Failed to generate KHGT calendar: ${error.message}`);
+    }
+  }
+
+  /**
+   * Month-view calendar grid for a Gregorian or Hijri month
+   * @param {string} basis - 'gregorian' or 'hijri'
+   * @param {number} year - Year of the chosen basis
+   * @param {number} month - Month of the chosen basis (1-12)
+   * @param {number} timezone - UTC offset (hours) for marker times and local dates
+   * @param {Object} [calendar] - Hijri calendar mode, see gregorianToHijri
+   * @returns {Promise<Object>} {leading_blanks, days: [{gregorian, hijri, hari, pasaran, moon_phase, markers}]}
+   */
+  async getCalendarMonthGrid(basis, year, month, timezone, calendar = null) {
+    try {
+      const invoke = await getInvoke();
+      return await invoke('calendar_month_grid_command', { basis, year, month, calendar, timezone });
+    } catch (error) {
+      console.error('API Error - getCalendarMonthGrid:', error);
+      throw new Error(`Failed to build calendar grid: ${error.message}`);
+    }
+  }
+
+  /**
+   * Islamic holidays of a Hijri year, side by side for each calendar mode
+   * @param {number} hijriYear - Hijri year
+   * @param {Array<Object>} [calendars] - Calendar modes (see gregorianToHijri, plus {kind: 'khgt'})
+   * @returns {Promise<Array>} [{id, name, hijri_month, hijri_day, agreed, observances: [{start, end, calendars}]}]
+   */
+  async getIslamicHolidays(hijriYear, calendars = null) {
+    try {
+      const invoke = await getInvoke();
+      return await invoke('islamic_holidays_command', { hijriYear, calendars });
+    } catch (error) {
+      console.error('API Error - getIslamicHolidays:', error);
+      throw new Error(`Failed to compute Islamic holidays: ${error.message}`);
+    }
+  }
+
+  /**
+   * Export Hijri month starts, holidays, moon phases and prayer times as iCalendar (.ics)
+   * @param {Object} options - {hijri_year, years?, calendar?, month_starts?, holidays?, moon_phases?, prayer_location?, prayer_method?}
+   * @returns {Promise<Object|null>} {path, events}, or null if the save dialog was cancelled
+   */
+  async exportICalendar(options) {
+    try {
+      // The backend opens the save dialog (.ics only) and writes the file
+      const invoke = await getInvoke();
+      const result = await invoke('export_icalendar_command', { options });
+      return result ? { path: result.path, events: result.count } : null;
+    } catch (error) {
+      console.error('API Error - exportICalendar:', error);
+      throw new Error(`Failed to export iCalendar: ${error.message}`);
+    }
+  }
+
+  /**
+   * Prayer timetable (jadwal imsakiyah) with Hijri date, hari and pasaran per row
+   * @param {Object} location - {name, latitude, longitude, elevation, timezone}
+   * @param {Object} range - {type: 'gregorian', start, end} | {type: 'hijri_month', year, month} | {type: 'hijri_year', year}
+   * @param {Object} [calendar] - Hijri calendar mode (see gregorianToHijri)
+   * @param {string|Object} [method] - Prayer method id or a full config (see getPrayerMethods)
+   * @returns {Promise<Object>} {location, calendar, rows: [{gregorian, hijri, hari, pasaran, times}]}
+   */
+  async getPrayerTimetable(location, range, calendar = null, method = null) {
+    try {
+      const invoke = await getInvoke();
+      return await invoke('prayer_timetable_command', { location, range, calendar, method });
+    } catch (error) {
+      console.error('API Error - getPrayerTimetable:', error);
+      throw new Error(`Failed to build prayer timetable: ${error.message}`);
+    }
+  }
+
+  /**
+   * Export a prayer timetable as CSV or JSON
+   * @param {Object} location - {name, latitude, longitude, elevation, timezone}
+   * @param {Object} range - See getPrayerTimetable
+   * @param {string} [format] - 'csv' (default) or 'json'
+   * @param {Object} [calendar] - Hijri calendar mode
+   * @param {string|Object} [method] - Prayer method id or a full config
+   * @returns {Promise<Object|null>} {path, rows}, or null if the save dialog was cancelled
+   */
+  async exportPrayerTimetable(location, range, format = 'csv', calendar = null, method = null) {
+    try {
+      // The backend opens the save dialog (extension matches format) and writes the file
+      const invoke = await getInvoke();
+      const result = await invoke('export_prayer_timetable_command', {
+        location, range, calendar, format, method
+      });
+      return result ? { path: result.path, rows: result.count } : null;
+    } catch (error) {
+      console.error('API Error - exportPrayerTimetable:', error);
+      throw new Error(`Failed to export prayer timetable: ${error.message}`);
+    }
+  }
+
+  /**
+   * Prayer times for one date as typed events
+   * @param {Object} location - {name, latitude, longitude, elevation, timezone}
+   * @param {number} year - Gregorian year
+   * @param {number} month - Gregorian month (1-12)
+   * @param {number} day - Gregorian day (1-31)
+   * @param {string|Object} [method] - Prayer method id or a full config
+   * @returns {Promise<Object>} {timezone, imsak, shubuh, ..., istiwa, high_latitude, intervals};
+   *   each event is {jd_utc, local, hours, altitude, azimuth} or null when the time does not
+   *   exist; intervals are {kind, start, end, start_altitude, end_altitude} for the karahah
+   *   (syuruq, zawal, ghurub) and Ashr ikhtiyari windows
+   */
+  async getPrayerTimesDetailed(location, year, month, day, method = null) {
+    try {
+      const invoke = await getInvoke();
+      return await invoke('prayer_times_command', { location, year, month, day, method });
+    } catch (error) {
+      console.error('API Error - getPrayerTimesDetailed:', error);
+      throw new Error(`Failed to get prayer times: ${error.message}`);
+    }
+  }
+
+  /**
+   * Standard prayer calculation methods (Kemenag, JAKIM, MUIS, MWL, ISNA, ...)
+   * @returns {Promise<Array>} [{id, name, config}]
+   */
+  async getPrayerMethods() {
+    try {
+      const invoke = await getInvoke();
+      return await invoke('prayer_methods_command');
+    } catch (error) {
+      console.error('API Error - getPrayerMethods:', error);
+      throw new Error(`Failed to list prayer methods: ${error.message}`);
+    }
+  }
+
+  /**
+   * Convert Gregorian date to the Javanese calendar
+   * @param {number} year - Gregorian year
+   * @param {number} month - Gregorian month (1-12)
+   * @param {number} day - Gregorian day (1-31)
+   * @param {string} [reckoning] - 'asapon' (kraton, default) or 'aboge'
+   * @returns {Promise<Object>} {date, month_name, year_name, kurup, hari, pasaran, neptu, wuku, hijri, ...}
+   */
+  async gregorianToJavanese(year, month, day, reckoning = null) {
+    try {
+      const invoke = await getInvoke();
+      return await invoke('gregorian_to_javanese_command', { year, month, day, reckoning });
+    } catch (error) {
+      console.error('API Error - gregorianToJavanese:', error);
+      throw new Error(`Failed to convert to Javanese: ${error.message}`);
+    }
+  }
+
+  /**
+   * Convert Javanese date to Gregorian
+   * @param {number} year - Javanese year (J)
+   * @param {number} month - Javanese month (1 = Sura .. 12 = Besar)
+   * @param {number} day - Javanese day (1-30)
+   * @param {string} [reckoning] - 'asapon' (kraton, default) or 'aboge'
+   * @returns {Promise<Object>} Javanese day information including {gregorian, hijri, jd}
+   */
+  async javaneseToGregorian(year, month, day, reckoning = null) {
+    try {
+      const invoke = await getInvoke();
+      return await invoke('javanese_to_gregorian_command', { year, month, day, reckoning });
+    } catch (error) {
+      console.error('API Error - javaneseToGregorian:', error);
+      throw new Error(`Failed to convert from Javanese: ${error.message}`);
+    }
+  }
+
+  /**
+   * Hajj dates (Tarwiyah, Arafah, Eid al-Adha) from the Umm al-Qura calendar
+   * @param {number} hijriYear - Hijri year (official table covers 1300-1600)
+   * @returns {Promise<Object>} {dhu_al_hijjah_start, tarwiyah, arafah, eid_al_adha, tashriq_end,
+   *   from_table} - from_table is false outside the table, where the Umm al-Qura rule is used
+   */
+  async getHajjDates(hijriYear) {
+    try {
+      const invoke = await getInvoke();
+      return await invoke('umm_al_qura_hajj_dates_command', { hijriYear });
+    } catch (error) {
+      console.error('API Error - getHajjDates:', error);
+      throw new Error(`Failed to get Hajj dates: ${error.message}`);
+    }
+  }
+
   /**
    * Run astronomical validation tests
    * @returns {Promise<Object>} Validation results {success, message, details}
@@ -343,6 +764,8 @@ export class HilalAPI {
    * @param {number} params.hijriYear - Hijri year
    * @param {number} params.hijriMonth - Hijri month (1-12)
    * @param {number} params.hijriDay - Hijri day (1-30)
+   * @param {string|Object} [params.method] - Method id or a full config
+   * @param {Object} [params.format] - Text format (see getPrayerTimes)
    * @returns {Promise<Object>} Prayer times data
    */
   async getPrayerTimesHijri(params) {
@@ -353,7 +776,9 @@ export class HilalAPI {
         location: params.location,
         hijri_year: params.hijri_year,
         hijri_month: params.hijri_month,
-        hijri_day: params.hijri_day
+        hijri_day: params.hijri_day,
+        method: params.method ?? null,
+        format: params.format ?? null
       });
 
       console.log('API Response - getPrayerTimesHijri:', result);
@@ -372,6 +797,7 @@ export class HilalAPI {
    * @param {number} params.hijriDay - Hijri day (1-30)
    * @param {string} params.criteria - Visibility criteria
    * @param {number} params.step_degrees - Grid resolution in degrees
+   * @param {Object} [params.rise_set] - Sunset conditions, see getVisibilityZones
    * @returns {Promise<Array>} Array of visibility zones
    */
   async getVisibilityZonesHijri(params) {
@@ -383,7 +809,9 @@ export class HilalAPI {
         hijri_month: params.hijri_month,
         hijri_day: params.hijri_day,
         criteria: params.criteria,
-        step_degrees: params.step_degrees || 2.0
+        step_degrees: params.step_degrees || 2.0,
+        grid: params.grid || null,
+        riseSet: params.rise_set ?? null
       });
 
       console.log('API Response - getVisibilityZonesHijri:', result);
@@ -401,6 +829,7 @@ export class HilalAPI {
    * @param {number} params.hijriYear - Hijri year
    * @param {number} params.hijriMonth - Hijri month (1-12)
    * @param {number} params.hijriDay - Hijri day (1-30)
+   * @param {Object} [params.rise_set] - Sunset conditions, see calculateHilalAllCriteria
    * @returns {Promise<Object>} Detailed hilal data
    */
   async getDetailedHilalDataHijri(params) {
@@ -411,7 +840,8 @@ export class HilalAPI {
         location: params.location,
         hijri_year: params.hijri_year,
         hijri_month: params.hijri_month,
-        hijri_day: params.hijri_day
+        hijri_day: params.hijri_day,
+        riseSet: params.rise_set ?? null
       });
 
       console.log('API Response - getDetailedHilalDataHijri:', result);
@@ -429,6 +859,7 @@ export class HilalAPI {
    * @param {number} params.hijriYear - Hijri year
    * @param {number} params.hijriMonth - Hijri month (1-12)
    * @param {number} params.hijriDay - Hijri day (1-30)
+   * @param {Object} [params.rise_set] - Sunset conditions, see calculateHilalAllCriteria
    * @returns {Promise<Object>} All criteria results with visibility
    */
   async calculateHilalAllCriteriaHijri(params) {
@@ -439,7 +870,8 @@ export class HilalAPI {
         location: params.location,
         hijri_year: params.hijri_year,
         hijri_month: params.hijri_month,
-        hijri_day: params.hijri_day
+        hijri_day: params.hijri_day,
+        riseSet: params.rise_set ?? null
       });
 
       console.log('API Response - calculateHilalAllCriteriaHijri:', result);